@@ -0,0 +1,68 @@
+//! Throughput of large batch launches against an in-process mock server.
+//!
+//! Run with `cargo bench --bench launch`. Each case launches a batch with
+//! [`NessusClient::launch_scans_parallel`] and reports scans per second and
+//! the number of requests the server saw. The run fails if a case launches
+//! fewer than [`MIN_SCANS_PER_SEC`] scans per second, or sends more than
+//! one launch request per scan.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use nessus_launcher::{ApiVersion, LaunchOptions, NessusClient, NessusConfig};
+use std::time::Instant;
+
+const BATCH_SIZES: [u32; 2] = [500, 2000];
+const CONCURRENCY: [usize; 2] = [16, 64];
+
+/// Slowest acceptable launch rate against a local server, with ample
+/// headroom for busy CI machines.
+const MIN_SCANS_PER_SEC: f64 = 2000.0;
+
+fn client(base: &str, concurrency: usize) -> NessusClient {
+    let config = NessusConfig {
+        host: base.into(),
+        username: "bench".into(),
+        password: "bench".into(),
+        x_api_token: Some("bench".into()),
+    };
+    NessusClient::builder(config)
+        .with_launch_options(LaunchOptions::new().with_concurrency(concurrency))
+        .with_session("bench")
+        .with_api_version(ApiVersion::V7)
+        .build()
+        .expect("valid benchmark client")
+}
+
+#[tokio::main]
+async fn main() {
+    let (base, seen) =
+        common::mock_server_fn(|_| common::Response::json(200, r#"{"scan_uuid":"bench"}"#)).await;
+
+    for scans in BATCH_SIZES {
+        for concurrency in CONCURRENCY {
+            let client = client(&base, concurrency);
+            let before = seen.lock().unwrap().len();
+            let started = Instant::now();
+            let report = client
+                .launch_scans_parallel((1..=scans).collect())
+                .await
+                .unwrap();
+            let elapsed = started.elapsed();
+            let requests = seen.lock().unwrap().len() - before;
+            let rate = f64::from(scans) / elapsed.as_secs_f64();
+
+            println!(
+                "launch {scans:>5} scans, concurrency {concurrency:>3}: {:>8.2?} \
+                 ({:>8.0} scans/s, {} requests)",
+                elapsed, rate, requests
+            );
+            assert_eq!(report.succeeded.len(), scans as usize);
+            assert_eq!(requests, scans as usize, "one launch request per scan");
+            assert!(
+                rate >= MIN_SCANS_PER_SEC,
+                "{rate:.0} scans/s is below the floor of {MIN_SCANS_PER_SEC}"
+            );
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Compiles the gRPC service definition when the `grpc` feature is enabled.
+
+fn main() {
+    #[cfg(feature = "grpc")]
+    compile_protos();
+}
+
+#[cfg(feature = "grpc")]
+fn compile_protos() {
+    // Use the vendored protoc unless the environment names one, so building
+    // doesn't require protoc to be installed.
+    if std::env::var_os("PROTOC").is_none() {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc is available");
+        // SAFETY: build scripts are single-threaded.
+        unsafe { std::env::set_var("PROTOC", protoc) };
+    }
+
+    tonic_prost_build::configure()
+        .build_client(false)
+        .compile_protos(&["proto/launcher.proto"], &["proto"])
+        .expect("proto/launcher.proto compiles");
+}
@@ -0,0 +1,154 @@
+//! Batches of scans launched by the `serve` daemon.
+//!
+//! The REST and gRPC APIs share one [`BatchStore`]. Each batch's state is
+//! published through a [`watch`] channel, so callers can read it at any time
+//! or follow it as scans are launched. Finished batches are recorded in
+//! the history file, for `history`.
+
+use crate::history;
+use chrono::Utc;
+use futures::StreamExt;
+use nessus_launcher::history::LaunchRecord;
+use nessus_launcher::{LaunchReport, NessusClient};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use tokio::sync::watch;
+use tracing::{error, info};
+
+/// Where a launched batch stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchStatus {
+    /// Scans are still being launched.
+    Running,
+    /// Every scan was attempted; see the report for failures.
+    Completed,
+    /// The batch could not be launched at all, e.g. because login failed.
+    Failed,
+}
+
+/// A batch of scans launched through the API.
+#[derive(Debug, Clone, Serialize)]
+pub struct Batch {
+    /// Batch identifier.
+    pub id: String,
+    /// Scans requested, in request order.
+    pub scan_ids: Vec<u32>,
+    /// Where the batch stands.
+    pub status: BatchStatus,
+    /// Per-scan outcome so far; complete once the batch has completed.
+    pub report: LaunchReport,
+    /// Why the batch failed, if it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The batches launched since the daemon started, kept in memory.
+pub struct BatchStore {
+    client: Arc<NessusClient>,
+    history: Arc<PathBuf>,
+    batches: RwLock<HashMap<String, watch::Sender<Batch>>>,
+}
+
+impl BatchStore {
+    /// An empty store launching scans with `client` and recording them in
+    /// the history file at `history`.
+    pub fn new(client: NessusClient, history: PathBuf) -> Self {
+        Self {
+            client: Arc::new(client),
+            history: Arc::new(history),
+            batches: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The client batches are launched with.
+    pub fn client(&self) -> &NessusClient {
+        &self.client
+    }
+
+    /// Start launching `scan_ids` in the background and return the new batch.
+    ///
+    /// `requested_by` is who the caller says asked for the launch, recorded
+    /// as the user in the history file. The daemon's API token is shared,
+    /// so it cannot tell callers apart itself.
+    pub fn launch(&self, scan_ids: Vec<u32>, requested_by: Option<String>) -> Batch {
+        self.start(scan_ids, None, requested_by)
+    }
+
+    /// Start launching `scan_ids` for the schedule `name`, recording the
+    /// schedule as the trigger in the history file.
+    pub fn launch_scheduled(&self, name: &str, scan_ids: Vec<u32>) -> Batch {
+        self.start(scan_ids, Some(format!("schedule {name}")), None)
+    }
+
+    fn start(&self, scan_ids: Vec<u32>, trigger: Option<String>, user: Option<String>) -> Batch {
+        let batch = Batch {
+            id: uuid::Uuid::new_v4().to_string(),
+            scan_ids,
+            status: BatchStatus::Running,
+            report: LaunchReport::default(),
+            error: None,
+        };
+        let (tx, _) = watch::channel(batch.clone());
+        self.batches
+            .write()
+            .expect("batch store lock poisoned")
+            .insert(batch.id.clone(), tx.clone());
+        info!("Batch {} launching scans {:?}", batch.id, batch.scan_ids);
+
+        let client = Arc::clone(&self.client);
+        let history_path = Arc::clone(&self.history);
+        let scan_ids = batch.scan_ids.clone();
+        let trigger = trigger.unwrap_or_else(|| format!("serve batch {}", batch.id));
+        tokio::spawn(async move {
+            let launched_at = Utc::now();
+            let mut outcomes = match client.launch_scans_stream(scan_ids.clone()).await {
+                Ok(outcomes) => outcomes,
+                Err(e) => {
+                    error!("Batch {} failed: {}", tx.borrow().id, e);
+                    tx.send_modify(|batch| {
+                        batch.status = BatchStatus::Failed;
+                        batch.error = Some(e.to_string());
+                    });
+                    let records = history::failed(
+                        &scan_ids,
+                        &trigger,
+                        user.as_deref(),
+                        launched_at,
+                        &e.to_string(),
+                    );
+                    history::record(&history_path, records).await;
+                    return;
+                }
+            };
+            while let Some(outcome) = outcomes.next().await {
+                tx.send_modify(|batch| batch.report.push(outcome));
+            }
+            tx.send_modify(|batch| batch.status = BatchStatus::Completed);
+
+            let records = LaunchRecord::from_report(
+                &tx.borrow().report,
+                &trigger,
+                user.as_deref(),
+                launched_at,
+                Utc::now(),
+            );
+            history::record(&history_path, records).await;
+        });
+
+        batch
+    }
+
+    /// The current state of the batch with the given ID.
+    pub fn get(&self, id: &str) -> Option<Batch> {
+        self.watch(id).map(|rx| rx.borrow().clone())
+    }
+
+    /// A receiver following the state of the batch with the given ID.
+    pub fn watch(&self, id: &str) -> Option<watch::Receiver<Batch>> {
+        let batches = self.batches.read().expect("batch store lock poisoned");
+        batches.get(id).map(watch::Sender::subscribe)
+    }
+}
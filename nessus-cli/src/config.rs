@@ -0,0 +1,148 @@
+//! Optional configuration file for the CLI.
+//!
+//! Settings that don't fit on a command line, such as field mappings for
+//! external sinks, live in a TOML file. It is read from `--config`, or from
+//! `nessus-cli.toml` in the working directory if that exists.
+//!
+//! Named profiles (`[profiles.<name>]`, selected with `--profile`) override
+//! the top-level notification settings, e.g. to post production runs to a
+//! different Teams channel than staging runs.
+//!
+//! `[[schedules]]` entries make the `serve` daemon launch scans at a local
+//! time of day in a given time zone, e.g. within a site's maintenance window.
+
+use nessus_launcher::history::SlaPolicy;
+use nessus_launcher::notify::Notifier;
+use nessus_launcher::notify::pagerduty::{PagerDutyConfig, PagerDutyNotifier};
+use nessus_launcher::notify::teams::{TeamsConfig, TeamsNotifier};
+use nessus_launcher::notify::webhook::{WebhookConfig, WebhookNotifier};
+use nessus_launcher::schedule::Schedule;
+use nessus_launcher::sink::servicenow::ServiceNowConfig;
+use nessus_launcher::{NessusError, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Name of the configuration file picked up when `--config` is not given.
+pub const DEFAULT_CONFIG_FILE: &str = "nessus-cli.toml";
+
+/// Contents of the CLI configuration file.
+#[derive(Debug, Default, Deserialize)]
+pub struct CliConfig {
+    /// ServiceNow sink settings.
+    #[serde(default)]
+    pub servicenow: Option<ServiceNowConfig>,
+    /// Microsoft Teams notification settings.
+    #[serde(default)]
+    pub teams: Option<TeamsConfig>,
+    /// Templated webhooks.
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    /// Remediation windows for `sla` and `results --fail-on-sla`.
+    #[serde(default)]
+    pub sla: Option<SlaPolicy>,
+    /// Named profiles, selected with `--profile`.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    /// Recurring launches run by `serve`.
+    #[serde(default)]
+    pub schedules: Vec<ScheduledLaunch>,
+}
+
+/// Scans the `serve` daemon launches on a [`Schedule`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduledLaunch {
+    /// Name of the schedule, recorded as the trigger in the history file.
+    pub name: String,
+    /// Scans to launch.
+    pub scan_ids: Vec<u32>,
+    /// When to launch them.
+    #[serde(flatten)]
+    pub schedule: Schedule,
+    /// Profile whose notification settings apply to the schedule's batches,
+    /// instead of the one given with `--profile`.
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Raise PagerDuty incidents for failed launches (configured via
+    /// `PAGERDUTY_ROUTING_KEY`).
+    #[serde(default)]
+    pub pagerduty: bool,
+}
+
+/// Settings of a named profile, overriding the top-level ones.
+#[derive(Debug, Default, Deserialize)]
+pub struct Profile {
+    /// Microsoft Teams notification settings.
+    #[serde(default)]
+    pub teams: Option<TeamsConfig>,
+    /// Templated webhooks, replacing the top-level ones when present.
+    #[serde(default)]
+    pub webhooks: Option<Vec<WebhookConfig>>,
+}
+
+impl CliConfig {
+    /// Load the configuration from `path`, or from [`DEFAULT_CONFIG_FILE`]
+    /// if no path is given. A missing default file yields an empty config.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => Path::new(DEFAULT_CONFIG_FILE),
+            None => return Ok(Self::default()),
+        };
+
+        let raw = std::fs::read_to_string(path)?;
+        toml::from_str(&raw).map_err(|e| {
+            NessusError::Config(format!("Invalid config file {}: {e}", path.display()))
+        })
+    }
+
+    /// The ServiceNow settings, or an error if the file has none.
+    pub fn servicenow(&self) -> Result<ServiceNowConfig> {
+        self.servicenow.clone().ok_or_else(|| {
+            NessusError::Config("Missing [servicenow] section in config file".into())
+        })
+    }
+
+    /// The Teams settings of `profile`, falling back to the top-level ones.
+    ///
+    /// Returns `None` if Teams notifications are not configured.
+    pub fn teams(&self, profile: Option<&str>) -> Result<Option<TeamsConfig>> {
+        let profile_teams = self.profile(profile)?.and_then(|p| p.teams.clone());
+        Ok(profile_teams.or_else(|| self.teams.clone()))
+    }
+
+    /// The webhooks of `profile`, falling back to the top-level ones.
+    pub fn webhooks(&self, profile: Option<&str>) -> Result<Vec<WebhookConfig>> {
+        let profile_webhooks = self.profile(profile)?.and_then(|p| p.webhooks.clone());
+        Ok(profile_webhooks.unwrap_or_else(|| self.webhooks.clone()))
+    }
+
+    /// The notifiers configured for `profile`, plus PagerDuty if `pagerduty`
+    /// is set.
+    pub fn notifiers(
+        &self,
+        profile: Option<&str>,
+        pagerduty: bool,
+    ) -> Result<Vec<Box<dyn Notifier>>> {
+        let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+        if pagerduty {
+            notifiers.push(Box::new(PagerDutyNotifier::new(PagerDutyConfig::from_env()?)?));
+        }
+        if let Some(teams) = self.teams(profile)? {
+            notifiers.push(Box::new(TeamsNotifier::new(teams)?));
+        }
+        for webhook in self.webhooks(profile)? {
+            notifiers.push(Box::new(WebhookNotifier::new(webhook)?));
+        }
+        Ok(notifiers)
+    }
+
+    fn profile(&self, name: Option<&str>) -> Result<Option<&Profile>> {
+        name.map(|name| {
+            self.profiles
+                .get(name)
+                .ok_or_else(|| NessusError::Config(format!("Unknown profile: {name}")))
+        })
+        .transpose()
+    }
+}
@@ -0,0 +1,228 @@
+//! `consume`: launch scans from requests on a Redis list, enabled by the
+//! `redis` feature.
+//!
+//! Other systems (e.g. a SOAR platform) push launch requests onto the
+//! request list and read outcomes from the results list:
+//!
+//! ```json
+//! {"id": "incident-4711", "scan_ids": [5, 8], "requested_by": "soar"}
+//! ```
+//!
+//! `requested_by`, if given, is recorded as the user of the launches.
+//!
+//! Each request is moved to the consumer's own `<queue>:processing:<consumer>`
+//! list while its scans launch and removed once its result event is
+//! published, so requests in flight when a consumer stops are put back on
+//! the queue when the consumer of that name next starts. Several consumers
+//! can share a queue as long as their names differ. Delivery is at least
+//! once: a request whose scans launched just before a crash is launched
+//! again. Launches are recorded in the history file, for `history`.
+
+use crate::history;
+use chrono::Utc;
+use nessus_launcher::history::LaunchRecord;
+use nessus_launcher::{KeepAliveOptions, LaunchReport, NessusClient, NessusError, Result};
+use redis::AsyncCommands;
+use redis::Direction;
+use redis::aio::MultiplexedConnection;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// How long one blocking pop waits before checking for shutdown again.
+const POP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where requests are read from and results written to.
+#[derive(Debug, Clone)]
+pub struct QueueConfig {
+    /// Redis connection URL, e.g. `redis://127.0.0.1/`.
+    pub url: String,
+    /// List launch requests are popped from.
+    pub queue: String,
+    /// List result events are pushed to.
+    pub results: String,
+    /// Name of this consumer, unique among the consumers of `queue` and
+    /// stable across restarts.
+    pub consumer: String,
+}
+
+impl QueueConfig {
+    /// List holding this consumer's requests in flight.
+    fn processing(&self) -> String {
+        format!("{}:processing:{}", self.queue, self.consumer)
+    }
+}
+
+/// A launch request read from the queue.
+#[derive(Debug, Deserialize)]
+struct LaunchRequest {
+    /// Caller-chosen ID echoed in the result event.
+    #[serde(default)]
+    id: Option<String>,
+    scan_ids: Vec<u32>,
+    /// Who asked for the launch, for the history file.
+    #[serde(default)]
+    requested_by: Option<String>,
+}
+
+/// An event published to the results list.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum ResultEvent {
+    /// Every scan of the request was attempted.
+    LaunchCompleted {
+        request_id: Option<String>,
+        report: LaunchReport,
+    },
+    /// The request's scans could not be launched at all.
+    LaunchFailed {
+        request_id: Option<String>,
+        error: String,
+    },
+    /// The message was not a valid launch request.
+    InvalidRequest { message: String, error: String },
+}
+
+/// Consume launch requests until Ctrl-C, recording launches in the history
+/// file at `history`.
+///
+/// # Errors
+///
+/// Returns an error if logging in to Nessus fails or the Redis connection
+/// fails or drops.
+pub async fn consume(client: NessusClient, config: QueueConfig, history: &Path) -> Result<()> {
+    let redis = redis::Client::open(config.url.as_str()).map_err(redis_error)?;
+    let mut conn = redis
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(redis_error)?;
+
+    // One session for the consumer's lifetime instead of a login per request.
+    let _keep_alive = client
+        .keep_session_alive(KeepAliveOptions::default())
+        .await?;
+
+    requeue_unfinished(&mut conn, &config).await?;
+    info!(
+        "Consuming launch requests from Redis list {} as {}; results go to {}",
+        config.queue, config.consumer, config.results
+    );
+
+    loop {
+        let popped: Option<String> = tokio::select! {
+            popped = conn.blmove(
+                &config.queue,
+                config.processing(),
+                Direction::Left,
+                Direction::Right,
+                POP_TIMEOUT.as_secs_f64(),
+            ) => popped.map_err(redis_error)?,
+            _ = tokio::signal::ctrl_c() => break,
+        };
+        let Some(message) = popped else {
+            continue;
+        };
+
+        let event = handle(&client, &message, history).await;
+        let payload = serde_json::to_string(&event)?;
+        let _: () = conn
+            .rpush(&config.results, payload)
+            .await
+            .map_err(redis_error)?;
+        let _: () = conn
+            .lrem(config.processing(), 1, &message)
+            .await
+            .map_err(redis_error)?;
+    }
+
+    info!("Stopped consuming launch requests");
+    Ok(())
+}
+
+/// Launch the scans of one request, record them in `history`, and describe
+/// the outcome.
+async fn handle(client: &NessusClient, message: &str, history: &Path) -> ResultEvent {
+    let request: LaunchRequest = match serde_json::from_str(message) {
+        Ok(request) => request,
+        Err(e) => {
+            warn!("Ignoring invalid launch request {}: {}", message, e);
+            return ResultEvent::InvalidRequest {
+                message: message.to_string(),
+                error: e.to_string(),
+            };
+        }
+    };
+
+    info!(
+        "Launch request {} for scans {:?}",
+        request.id.as_deref().unwrap_or("(no id)"),
+        request.scan_ids
+    );
+    let trigger = format!(
+        "queue request {}",
+        request.id.as_deref().unwrap_or("(no id)")
+    );
+    let launched_at = Utc::now();
+    match client.launch_scans_parallel(request.scan_ids.clone()).await {
+        Ok(report) | Err(NessusError::Launch(report)) => {
+            let records = LaunchRecord::from_report(
+                &report,
+                &trigger,
+                request.requested_by.as_deref(),
+                launched_at,
+                Utc::now(),
+            );
+            history::record(history, records).await;
+            ResultEvent::LaunchCompleted {
+                request_id: request.id,
+                report,
+            }
+        }
+        Err(e) => {
+            error!("Launch request failed: {}", e);
+            let records = history::failed(
+                &request.scan_ids,
+                &trigger,
+                request.requested_by.as_deref(),
+                launched_at,
+                &e.to_string(),
+            );
+            history::record(history, records).await;
+            ResultEvent::LaunchFailed {
+                request_id: request.id,
+                error: e.to_string(),
+            }
+        }
+    }
+}
+
+/// Put requests left in this consumer's processing list by a previous run
+/// back on the queue, ahead of newer requests. Other consumers' lists are
+/// left alone, since their requests may still be in flight.
+async fn requeue_unfinished(conn: &mut MultiplexedConnection, config: &QueueConfig) -> Result<()> {
+    let mut requeued = 0;
+    loop {
+        let moved: Option<String> = conn
+            .lmove(
+                config.processing(),
+                &config.queue,
+                Direction::Right,
+                Direction::Left,
+            )
+            .await
+            .map_err(redis_error)?;
+        if moved.is_none() {
+            break;
+        }
+        requeued += 1;
+    }
+    if requeued > 0 {
+        warn!("Requeued {} unfinished launch request(s)", requeued);
+    }
+    Ok(())
+}
+
+fn redis_error(e: redis::RedisError) -> NessusError {
+    NessusError::Other(format!("Redis error: {e}"))
+}
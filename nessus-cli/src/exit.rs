@@ -0,0 +1,156 @@
+//! Process exit codes.
+//!
+//! The exit code is a stable contract for wrapper scripts:
+//!
+//! | Code | Meaning                                                   |
+//! |------|-----------------------------------------------------------|
+//! | 0    | Success                                                   |
+//! | 1    | Any other error (network, server, I/O, ...)               |
+//! | 2    | One or more scans failed to launch                        |
+//! | 3    | Authentication failed                                     |
+//! | 4    | Configuration error, or an unsupported Nessus version     |
+//! | 5    | A gate was breached (severity, EPSS, KEV, or SLA)         |
+
+use nessus_launcher::{LaunchReport, NessusError};
+use std::fmt;
+use std::process::ExitCode;
+
+/// Exit code for success.
+pub const SUCCESS: u8 = 0;
+/// Exit code for errors without a more specific code.
+pub const ERROR: u8 = 1;
+/// Exit code when one or more scans failed to launch.
+pub const LAUNCH_FAILED: u8 = 2;
+/// Exit code when authentication failed.
+pub const AUTH_FAILED: u8 = 3;
+/// Exit code for configuration errors and unsupported Nessus versions.
+pub const CONFIG_ERROR: u8 = 4;
+/// Exit code when a gate was breached.
+pub const GATE_BREACHED: u8 = 5;
+
+/// Why the CLI failed.
+#[derive(Debug)]
+pub enum CliError {
+    /// An error from the library.
+    Nessus(NessusError),
+    /// Some scans of a batch failed to launch.
+    LaunchFailed {
+        /// Number of scans that failed or were skipped.
+        failed: usize,
+        /// Number of scans in the batch.
+        total: usize,
+    },
+    /// Findings breached a gate or SLA.
+    GateBreached(String),
+}
+
+/// Result type of the CLI's commands.
+pub type CliResult<T> = std::result::Result<T, CliError>;
+
+impl CliError {
+    /// The error for `report`, if any scan failed to launch.
+    pub fn from_launch(report: &LaunchReport) -> Option<Self> {
+        (!report.is_success()).then(|| CliError::LaunchFailed {
+            failed: report.total() - report.succeeded.len(),
+            total: report.total(),
+        })
+    }
+
+    /// The process exit code for this error.
+    pub fn code(&self) -> u8 {
+        match self {
+            CliError::Nessus(NessusError::Auth(_)) => AUTH_FAILED,
+            CliError::Nessus(NessusError::Config(_) | NessusError::UnsupportedVersion(_)) => {
+                CONFIG_ERROR
+            }
+            CliError::Nessus(NessusError::Launch(_)) => LAUNCH_FAILED,
+            CliError::Nessus(_) => ERROR,
+            CliError::LaunchFailed { .. } => LAUNCH_FAILED,
+            CliError::GateBreached(_) => GATE_BREACHED,
+        }
+    }
+
+    /// The process exit code for this error, as an [`ExitCode`].
+    pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from(self.code())
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Nessus(e) => e.fmt(f),
+            CliError::LaunchFailed { failed, total } => {
+                write!(f, "{failed} of {total} scan(s) failed to launch")
+            }
+            CliError::GateBreached(msg) => write!(f, "Gate breached: {msg}"),
+        }
+    }
+}
+
+impl From<NessusError> for CliError {
+    fn from(e: NessusError) -> Self {
+        CliError::Nessus(e)
+    }
+}
+
+impl From<serde_json::Error> for CliError {
+    fn from(e: serde_json::Error) -> Self {
+        CliError::Nessus(e.into())
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(e: std::io::Error) -> Self {
+        CliError::Nessus(e.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nessus_launcher::{LaunchFailure, UnexpectedResponse};
+
+    #[test]
+    fn test_every_error_maps_to_its_exit_code() {
+        let failed_report = LaunchReport {
+            succeeded: vec![5],
+            failed: vec![LaunchFailure {
+                scan_id: 8,
+                error: "HTTP 500".into(),
+            }],
+            skipped: Vec::new(),
+        };
+        let cases = [
+            (NessusError::Auth("bad password".into()).into(), AUTH_FAILED),
+            (NessusError::Config("no scans".into()).into(), CONFIG_ERROR),
+            (
+                NessusError::UnsupportedVersion("Nessus 6".into()).into(),
+                CONFIG_ERROR,
+            ),
+            (
+                NessusError::Launch(failed_report.clone()).into(),
+                LAUNCH_FAILED,
+            ),
+            (NessusError::Other("timeout".into()).into(), ERROR),
+            (
+                NessusError::UnexpectedResponse(UnexpectedResponse::new("GET /scans", "eof", ""))
+                    .into(),
+                ERROR,
+            ),
+            (std::io::Error::other("disk full").into(), ERROR),
+            (serde_json::from_str::<u32>("x").unwrap_err().into(), ERROR),
+            (
+                CliError::from_launch(&failed_report).unwrap(),
+                LAUNCH_FAILED,
+            ),
+            (CliError::GateBreached("1 breach(es)".into()), GATE_BREACHED),
+        ];
+
+        for (error, code) in cases {
+            assert_eq!(error.code(), code, "{error}");
+        }
+        assert!(CliError::from_launch(&LaunchReport::default()).is_none());
+        assert_ne!(SUCCESS, ERROR);
+    }
+}
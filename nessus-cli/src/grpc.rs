@@ -0,0 +1,147 @@
+//! The gRPC side of `serve`, enabled by the `grpc` feature.
+//!
+//! Exposes the same batches as the REST API (see `proto/launcher.proto`),
+//! plus `WatchBatch`, which streams a batch's state as each scan is launched
+//! instead of making callers poll. Calls must carry `authorization: Bearer
+//! <token>` metadata with the token from `NESSUS_CLI_API_TOKEN`.
+
+use crate::batches::{self, BatchStore};
+use crate::serve::{ApiToken, shutdown_signal};
+use futures::Stream;
+use futures::stream::{self, StreamExt};
+use nessus_launcher::Result;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::info;
+
+/// Types and service traits generated from `proto/launcher.proto`.
+pub mod proto {
+    tonic::include_proto!("nessus.launcher.v1");
+}
+
+use proto::launcher_server::{Launcher, LauncherServer};
+
+/// Serve the gRPC API on `listen` until Ctrl-C.
+///
+/// # Errors
+///
+/// Returns an error if `listen` cannot be bound.
+pub async fn serve(
+    token: Arc<ApiToken>,
+    batches: Arc<BatchStore>,
+    listen: SocketAddr,
+) -> Result<()> {
+    let service = LauncherServer::with_interceptor(LauncherService { batches }, move |request| {
+        authorize(&token, request)
+    });
+
+    info!("Serving the gRPC API on {}", listen);
+    tonic::transport::Server::builder()
+        .add_service(service)
+        .serve_with_shutdown(listen, shutdown_signal())
+        .await
+        .map_err(|e| nessus_launcher::NessusError::Other(format!("gRPC server failed: {e}")))
+}
+
+/// Reject calls without the expected bearer token.
+fn authorize(token: &ApiToken, request: Request<()>) -> std::result::Result<Request<()>, Status> {
+    let authorization = request
+        .metadata()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok());
+
+    if token.accepts(authorization) {
+        Ok(request)
+    } else {
+        Err(Status::unauthenticated("Missing or invalid bearer token"))
+    }
+}
+
+struct LauncherService {
+    batches: Arc<BatchStore>,
+}
+
+type BatchStream = Pin<Box<dyn Stream<Item = std::result::Result<proto::Batch, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl Launcher for LauncherService {
+    async fn launch_batch(
+        &self,
+        request: Request<proto::LaunchBatchRequest>,
+    ) -> std::result::Result<Response<proto::Batch>, Status> {
+        let request = request.into_inner();
+        if request.scan_ids.is_empty() {
+            return Err(Status::invalid_argument("scan_ids is empty"));
+        }
+        let requested_by = Some(request.requested_by).filter(|r| !r.is_empty());
+        Ok(Response::new(
+            self.batches.launch(request.scan_ids, requested_by).into(),
+        ))
+    }
+
+    async fn get_batch(
+        &self,
+        request: Request<proto::GetBatchRequest>,
+    ) -> std::result::Result<Response<proto::Batch>, Status> {
+        let id = request.into_inner().id;
+        self.batches
+            .get(&id)
+            .map(|batch| Response::new(batch.into()))
+            .ok_or_else(|| Status::not_found(format!("No batch {id}")))
+    }
+
+    type WatchBatchStream = BatchStream;
+
+    async fn watch_batch(
+        &self,
+        request: Request<proto::GetBatchRequest>,
+    ) -> std::result::Result<Response<Self::WatchBatchStream>, Status> {
+        let id = request.into_inner().id;
+        let rx = self
+            .batches
+            .watch(&id)
+            .ok_or_else(|| Status::not_found(format!("No batch {id}")))?;
+
+        // Send the current state, then every change until the batch is done.
+        let updates = stream::unfold((Some(rx), true), |(rx, first)| async move {
+            let mut rx = rx?;
+            if !first && rx.changed().await.is_err() {
+                return None;
+            }
+            let batch = rx.borrow_and_update().clone();
+            let done = batch.status != batches::BatchStatus::Running;
+            let next = (!done).then_some(rx);
+            Some((Ok(batch.into()), (next, false)))
+        });
+        Ok(Response::new(updates.boxed()))
+    }
+}
+
+impl From<batches::Batch> for proto::Batch {
+    fn from(batch: batches::Batch) -> Self {
+        let status = match batch.status {
+            batches::BatchStatus::Running => proto::BatchStatus::Running,
+            batches::BatchStatus::Completed => proto::BatchStatus::Completed,
+            batches::BatchStatus::Failed => proto::BatchStatus::Failed,
+        };
+        Self {
+            id: batch.id,
+            scan_ids: batch.scan_ids,
+            status: status.into(),
+            succeeded: batch.report.succeeded,
+            failed: batch
+                .report
+                .failed
+                .into_iter()
+                .map(|f| proto::LaunchFailure {
+                    scan_id: f.scan_id,
+                    error: f.error,
+                })
+                .collect(),
+            skipped: batch.report.skipped,
+            error: batch.error.unwrap_or_default(),
+        }
+    }
+}
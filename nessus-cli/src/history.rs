@@ -0,0 +1,194 @@
+//! The launch history kept in the `--history` file.
+//!
+//! Every launch, whether from the command line, the `serve` daemon, or the
+//! queue consumer, is recorded with what triggered it and who asked for it.
+//! `nessus-cli history` lists the records:
+//!
+//! ```bash
+//! nessus-cli history --scan 5 --since 7d
+//! nessus-cli history --since 2024-03-01 --json
+//! ```
+
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use nessus_launcher::history::{HistoryStore, LaunchOutcome, LaunchRecord};
+use nessus_launcher::{NessusError, Result};
+use serde::Serialize;
+use std::path::Path;
+use tracing::{info, warn};
+
+/// A launch record as printed by `history --json`.
+#[derive(Serialize)]
+struct Entry<'a> {
+    #[serde(flatten)]
+    record: &'a LaunchRecord,
+    duration_secs: f64,
+}
+
+/// Who is running the CLI: the GitHub Actions actor, or the local user.
+pub fn user() -> Option<String> {
+    ["GITHUB_ACTOR", "USER", "USERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+}
+
+/// Add `records` to the history file at `path`.
+///
+/// The file is updated under its lock on a blocking thread, so concurrent
+/// batches, and other processes, keep each other's records. A history that
+/// cannot be written is logged rather than failing the launch it describes.
+pub async fn record(path: &Path, records: Vec<LaunchRecord>) {
+    if records.is_empty() {
+        return;
+    }
+    let file = path.to_path_buf();
+    let saved = tokio::task::spawn_blocking(move || {
+        HistoryStore::update(file, |history| history.record_launches(records))
+    })
+    .await
+    .unwrap_or_else(|e| Err(NessusError::Other(format!("History update panicked: {e}"))));
+    match saved {
+        Ok(()) => info!("Recorded launches in {}", path.display()),
+        Err(e) => warn!("Could not record launches in {}: {}", path.display(), e),
+    }
+}
+
+/// Records of `scan_ids` failing to launch at all for `user`, with
+/// `error`, e.g. because login failed.
+pub fn failed(
+    scan_ids: &[u32],
+    trigger: &str,
+    user: Option<&str>,
+    launched_at: DateTime<Utc>,
+    error: &str,
+) -> Vec<LaunchRecord> {
+    scan_ids
+        .iter()
+        .map(|&scan_id| LaunchRecord {
+            scan_id,
+            launched_at,
+            completed_at: Utc::now(),
+            trigger: trigger.to_string(),
+            user: user.map(str::to_string),
+            outcome: LaunchOutcome::Failed,
+            status: None,
+            error: Some(error.to_string()),
+        })
+        .collect()
+}
+
+/// Print the launches in the history file at `path`, of `scan_id` only if
+/// given, since `since` if given, as a table or as JSON.
+pub fn show(path: &Path, scan_id: Option<u32>, since: Option<&str>, json: bool) -> Result<()> {
+    let since = since.map(parse_since).transpose()?;
+    let history = HistoryStore::open(path)?;
+    let launches = history.launches(scan_id, since);
+
+    if json {
+        let entries: Vec<Entry> = launches
+            .iter()
+            .map(|record| Entry {
+                record,
+                duration_secs: record.duration().num_milliseconds() as f64 / 1000.0,
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    for launch in &launches {
+        let mut line = format!(
+            "{}  scan {:<6} {:<9} {:>9}  {}",
+            launch.launched_at.format("%Y-%m-%d %H:%M:%S"),
+            launch.scan_id,
+            launch.outcome,
+            format_duration(launch.duration()),
+            launch.trigger
+        );
+        if let Some(user) = &launch.user {
+            line.push_str(&format!(" by {user}"));
+        }
+        if let Some(status) = launch.status {
+            line.push_str(&format!(", finished {status}"));
+        }
+        if let Some(error) = &launch.error {
+            line.push_str(&format!(": {error}"));
+        }
+        println!("{line}");
+    }
+    if launches.is_empty() {
+        info!("No recorded launches in {}", path.display());
+    }
+    Ok(())
+}
+
+/// Parse `--since`: an age such as `30m`, `12h`, `7d`, or `2w`, a date
+/// such as `2024-03-01`, or an RFC 3339 timestamp.
+fn parse_since(since: &str) -> Result<DateTime<Utc>> {
+    let invalid = || {
+        NessusError::Config(format!(
+            "Invalid --since '{since}': expected an age like 7d or 12h, or a date like 2024-03-01"
+        ))
+    };
+
+    if let Ok(at) = DateTime::parse_from_rfc3339(since) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).ok_or_else(invalid)?.and_utc());
+    }
+
+    let unit_start = since
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = since.split_at(unit_start);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let age = match unit {
+        "m" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        "w" => TimeDelta::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+    Utc::now().checked_sub_signed(age).ok_or_else(invalid)
+}
+
+/// `duration` in the largest fitting unit, e.g. `850ms`, `12.4s`, or `3h05m`.
+fn format_duration(duration: TimeDelta) -> String {
+    let millis = duration.num_milliseconds().max(0);
+    match millis {
+        0..1_000 => format!("{millis}ms"),
+        1_000..60_000 => format!("{:.1}s", millis as f64 / 1000.0),
+        60_000..3_600_000 => format!("{}m{:02}s", millis / 60_000, millis / 1000 % 60),
+        _ => format!("{}h{:02}m", millis / 3_600_000, millis / 60_000 % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since_accepts_ages_and_dates() {
+        let age = Utc::now() - parse_since("1w").unwrap();
+        assert!((age - TimeDelta::weeks(1)).num_seconds().abs() < 5);
+        assert_eq!(
+            parse_since("2024-03-01").unwrap().to_rfc3339(),
+            "2024-03-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_since("2024-03-01T12:00:00+02:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2024-03-01T10:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_since_rejects_ages_out_of_range() {
+        for since in ["100000000w", "99999999999999999999d", "7y", "d", ""] {
+            let err = parse_since(since).unwrap_err();
+            assert!(matches!(err, NessusError::Config(_)), "{since}: {err}");
+        }
+    }
+}
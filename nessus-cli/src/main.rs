@@ -10,53 +10,1159 @@
 //! nessus-cli --scan 5 --scan 8
 //! ```
 //!
+//! Launch every scan whose name matches a glob:
+//!
+//! ```bash
+//! nessus-cli launch --match 'prod-*-weekly'
+//! ```
+//!
+//! Launch the scans of a plan file, each with its own targets, retries,
+//! waiting, and export (see [`plan`]):
+//!
+//! ```bash
+//! nessus-cli launch --plan weekly.toml
+//! ```
+//!
 //! Use default scan IDs from `DEFAULT_SCAN_IDS` in `.env`:
 //!
 //! ```bash
 //! nessus-cli
 //! ```
+//!
+//! Fetch high and critical findings of scan 5, failing if any is a known
+//! exploited vulnerability:
+//!
+//! ```bash
+//! nessus-cli results 5 --min-severity high --fail-on-kev
+//! ```
+//!
+//! File ServiceNow records for scans that fail to launch, using the field
+//! mapping from `nessus-cli.toml`:
+//!
+//! ```bash
+//! nessus-cli --scan 5 --servicenow
+//! ```
+//!
+//! Run as a GitHub Actions step, annotating gate breaches and writing a job
+//! summary:
+//!
+//! ```bash
+//! nessus-cli --output github results 5 --fail-on-severity critical
+//! ```
+//!
+//! List the launches of scan 5 in the last week, with who triggered them,
+//! their outcomes, and durations:
+//!
+//! ```bash
+//! nessus-cli history --scan 5 --since 7d
+//! ```
+//!
+//! Serve a REST API so other services can launch scans without Nessus
+//! credentials (see [`serve`]):
+//!
+//! ```bash
+//! NESSUS_CLI_API_TOKEN=... nessus-cli serve --listen 0.0.0.0:8080
+//! ```
+//!
+//! ## Exit codes
+//!
+//! `0` on success, `1` for errors without a more specific code, `2` if any
+//! scan failed to launch, `3` if authentication failed, `4` for configuration
+//! errors, and `5` if a gate or SLA was breached. See [`exit`].
+
+mod batches;
+mod config;
+#[cfg(feature = "redis")]
+mod consume;
+mod exit;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod history;
+mod picker;
+mod plan;
+mod schedules;
+mod serve;
 
-use clap::Parser;
+use chrono::Utc;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use config::CliConfig;
 use dotenvy::dotenv;
-use nessus_launcher::{NessusClient, NessusConfig, Result};
-use tracing::info;
+use exit::{CliError, CliResult};
+use nessus_launcher::enrich::epss::EpssClient;
+use nessus_launcher::enrich::kev::KevClient;
+use nessus_launcher::enrich::nvd::NvdClient;
+use nessus_launcher::export::csv::{CsvColumn, CsvExporter};
+use nessus_launcher::export::gitlab::GitlabExporter;
+use nessus_launcher::export::jsonl;
+use nessus_launcher::history::{HistoryStore, LaunchRecord};
+use nessus_launcher::notify::{Notifier, NotifyEvent};
+use nessus_launcher::report::github::{self, Annotation};
+use nessus_launcher::report::html::{HtmlRenderer, render_trend};
+use nessus_launcher::report::markdown::MarkdownRenderer;
+use nessus_launcher::report::{Report, SeverityCounts};
+use nessus_launcher::sink::defectdojo::{DefectDojoClient, DefectDojoConfig};
+use nessus_launcher::sink::jira::{JiraClient, JiraConfig};
+use nessus_launcher::sink::servicenow::ServiceNowClient;
+#[cfg(feature = "s3")]
+use nessus_launcher::upload::s3::{S3Store, ServerSideEncryption};
+#[cfg(any(feature = "s3", feature = "azure", feature = "gcs"))]
+use nessus_launcher::upload::{self, ObjectNameTemplate};
+#[cfg(feature = "s3")]
+use nessus_launcher::upload::{ArtifactStore, UploadTarget};
+use nessus_launcher::{
+    Finding, GatePolicy, LaunchReport, NessusClient, NessusConfig, NessusError, Result,
+    ResultFilter, ScanNamePattern, ScanRequest, Severity,
+};
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use tracing::{error, info};
 use tracing_subscriber::FmtSubscriber;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::SubscriberExt;
 
 /// Command-line arguments for the Nessus CLI.
 #[derive(Parser, Debug)]
 #[command(name = "nessus-cli")]
 #[command(about = "Launch Nessus scans via CLI")]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
+    /// Scans to launch when no subcommand is given.
+    #[command(flatten)]
+    launch: LaunchArgs,
+
+    /// Configuration file (default: `nessus-cli.toml` if present).
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// History file launches and results are recorded in and trends are
+    /// computed from.
+    #[arg(long, global = true, default_value = ".nessus-history.json")]
+    history: PathBuf,
+
+    /// Profile from the configuration file whose notification settings apply.
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// How to report outcomes to the surrounding CI system.
+    #[arg(long, global = true, value_enum, default_value_t = OutputMode::Text)]
+    output: OutputMode,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// How launch and gate outcomes are reported.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputMode {
+    /// Log messages only.
+    Text,
+    /// GitHub Actions workflow commands and a job summary.
+    Github,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Launch scans; the same as giving no subcommand.
+    Launch(LaunchArgs),
+    /// Fetch the findings of a scan, optionally enriching and gating them.
+    Results(Box<ResultsArgs>),
+    /// Wait for a scan to finish and import its results into DefectDojo.
+    Defectdojo(DefectDojoArgs),
+    /// Show how a scan's findings developed over its recorded runs.
+    Trend(TrendArgs),
+    /// List a scan's open findings that exceed their severity's SLA window.
+    Sla(SlaArgs),
+    /// List past launches: when, what triggered them, and how they went.
+    History(HistoryArgs),
+    /// Import a `.nessus` file as a new scan and print its ID.
+    Import(ImportArgs),
+    /// Serve a REST API for launching scans and fetching their results.
+    Serve(ServeArgs),
+    /// Launch scans from requests on a Redis list, publishing result events.
+    #[cfg(feature = "redis")]
+    Consume(ConsumeArgs),
+}
+
+/// Arguments for the `consume` subcommand.
+///
+/// The Redis URL is read from `REDIS_URL` (default: `redis://127.0.0.1/`).
+#[cfg(feature = "redis")]
+#[derive(Args, Debug)]
+struct ConsumeArgs {
+    /// List launch requests are popped from.
+    #[arg(long, default_value = "nessus:launch")]
+    queue: String,
+
+    /// List result events are pushed to.
+    #[arg(long, default_value = "nessus:launch:results")]
+    results: String,
+
+    /// Name of this consumer. Give every consumer of a queue its own, and
+    /// keep it across restarts so unfinished requests are requeued.
+    #[arg(long, default_value = "default")]
+    consumer: String,
+}
+
+/// Arguments for the `serve` subcommand.
+///
+/// Clients authenticate with the bearer token from `NESSUS_CLI_API_TOKEN`.
+#[derive(Args, Debug)]
+struct ServeArgs {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+
+    /// Also serve the gRPC API, with streaming batch updates, on this address.
+    #[cfg(feature = "grpc")]
+    #[arg(long)]
+    grpc_listen: Option<SocketAddr>,
+}
+
+/// Arguments for launching scans, with the `launch` subcommand or without
+/// any subcommand.
+#[derive(Args, Debug)]
+struct LaunchArgs {
     /// One or more scan IDs to launch.
     ///
-    /// If omitted, the CLI will use `DEFAULT_SCAN_IDS` from the environment.
-    #[arg(long, num_args = 1..)]
+    /// If no other selection is given, the CLI will use `DEFAULT_SCAN_IDS`
+    /// from the environment.
+    #[arg(long, num_args = 1.., conflicts_with_all = ["pattern", "folder", "tag", "interactive"])]
     scan: Option<Vec<u32>>,
+
+    /// Launch the scans listed in this plan file, each with its own targets,
+    /// retries, waiting, and export.
+    #[arg(long, conflicts_with_all = ["scan", "pattern", "folder", "tag", "interactive"])]
+    plan: Option<PathBuf>,
+
+    /// Launch every scan whose name matches this glob, e.g. `prod-*-weekly`.
+    #[arg(long = "match", value_name = "PATTERN")]
+    pattern: Option<String>,
+
+    /// Treat the `--match` pattern as a regular expression instead of a glob.
+    #[arg(long, requires = "pattern")]
+    regex: bool,
+
+    /// Launch every enabled scan in this folder, given by name or ID.
+    #[arg(long, conflicts_with_all = ["pattern", "tag"])]
+    folder: Option<String>,
+
+    /// Launch every scan targeting this tag, given as `Category:Value`, its
+    /// value, or its UUID.
+    #[arg(long, conflicts_with = "pattern")]
+    tag: Option<String>,
+
+    /// Pick the scans to launch from a searchable list of all scans.
+    #[arg(long, conflicts_with_all = ["pattern", "folder", "tag"])]
+    interactive: bool,
+
+    /// File a ServiceNow record for every scan that fails to launch.
+    #[arg(long)]
+    servicenow: bool,
+
+    /// Trigger a PagerDuty incident if any scan fails to launch
+    /// (configured via `PAGERDUTY_ROUTING_KEY`).
+    #[arg(long)]
+    pagerduty: bool,
+
+    /// Wait for the launched scans to finish, sending completion notices.
+    ///
+    /// With `--plan`, this applies to every scan, not just those with
+    /// `wait = true`.
+    #[arg(long)]
+    wait: bool,
+
+    /// Stop launching as soon as one scan fails after all retries; the
+    /// remaining scans are reported as skipped.
+    #[arg(long)]
+    fail_fast: bool,
+
+    /// Check the scan IDs against the server's scan list before launching,
+    /// failing without launching anything if one does not exist.
+    #[arg(long)]
+    verify_scans: bool,
+}
+
+/// Arguments for the `import` subcommand.
+#[derive(Args, Debug)]
+struct ImportArgs {
+    /// `.nessus` file to import.
+    file: PathBuf,
+}
+
+/// Arguments for the `sla` subcommand.
+///
+/// Finding ages come from runs recorded with `results --record`; windows
+/// from the `[sla]` section of the config file (default: 14/30/90/180 days
+/// for critical/high/medium/low).
+#[derive(Args, Debug)]
+struct SlaArgs {
+    /// Scan whose findings to check.
+    scan_id: u32,
+
+    /// Print the overdue findings as JSON.
+    #[arg(long)]
+    json: bool,
+
+    /// Exit with an error if any finding is overdue.
+    #[arg(long)]
+    fail: bool,
+}
+
+/// Arguments for the `history` subcommand.
+///
+/// Launches are recorded by `launch`, `serve`, and `consume`.
+#[derive(Args, Debug)]
+struct HistoryArgs {
+    /// Only list launches of this scan.
+    #[arg(long = "scan", value_name = "SCAN_ID")]
+    scan_id: Option<u32>,
+
+    /// Only list launches since an age (e.g. `12h`, `7d`, `2w`) or a date.
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Print the launches as JSON instead of a table.
+    #[arg(long)]
+    json: bool,
+}
+
+/// Arguments for the `trend` subcommand.
+///
+/// Runs are recorded with `results --record`.
+#[derive(Args, Debug)]
+struct TrendArgs {
+    /// Scan whose trend to show.
+    scan_id: u32,
+
+    /// Output format for the trend.
+    #[arg(long, value_enum, default_value_t = TrendFormat::Json)]
+    format: TrendFormat,
+
+    /// Write the trend to this file instead of stdout.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+/// Output formats supported by the `trend` subcommand.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum TrendFormat {
+    /// Severity counts, new/fixed counts, and MTTR as JSON.
+    Json,
+    /// One row per run.
+    Csv,
+    /// A standalone HTML page with a chart.
+    Html,
+}
+
+/// Arguments for the `defectdojo` subcommand.
+///
+/// DefectDojo is configured via `DEFECTDOJO_URL` and `DEFECTDOJO_API_KEY`.
+#[derive(Args, Debug)]
+struct DefectDojoArgs {
+    /// Scan to import.
+    scan_id: u32,
+
+    /// DefectDojo product to import into.
+    #[arg(long)]
+    product: u64,
+
+    /// Engagement name; created on the first run, reimported into afterwards.
+    #[arg(long)]
+    engagement: String,
+}
+
+/// Arguments for the `results` subcommand.
+#[derive(Args, Debug)]
+struct ResultsArgs {
+    /// Scan whose latest results to fetch.
+    scan_id: u32,
+
+    /// Only fetch findings at or above this severity.
+    #[arg(long)]
+    min_severity: Option<Severity>,
+
+    /// Only fetch findings referencing this CVE.
+    #[arg(long)]
+    cve: Option<String>,
+
+    /// Only fetch findings from this plugin.
+    #[arg(long)]
+    plugin: Option<u32>,
+
+    /// Only fetch hosts whose name contains this value.
+    #[arg(long)]
+    host: Option<String>,
+
+    /// Look up EPSS scores for the findings' CVEs.
+    #[arg(long)]
+    epss: bool,
+
+    /// Flag findings whose CVEs are in the CISA KEV catalog.
+    #[arg(long)]
+    kev: bool,
+
+    /// Add CVSS vectors and descriptions from the NVD (uses `NVD_API_KEY` if set).
+    #[arg(long)]
+    nvd: bool,
+
+    /// Also fetch each finding's plugin output (one extra request per finding).
+    #[arg(long)]
+    plugin_output: bool,
+
+    /// Ignore the scanner's plugin rules. By default excluded findings are
+    /// dropped and severities recast before reporting, recording, and gating.
+    #[arg(long)]
+    no_plugin_rules: bool,
+
+    /// Directory for cached EPSS scores, NVD details, and the KEV catalog.
+    #[arg(long, default_value = ".nessus-cache")]
+    cache_dir: PathBuf,
+
+    /// Fail if any finding is at or above this severity.
+    #[arg(long)]
+    fail_on_severity: Option<Severity>,
+
+    /// Fail if any finding has an EPSS score at or above this value (implies --epss).
+    #[arg(long)]
+    fail_on_epss: Option<f64>,
+
+    /// Fail if any finding is a known exploited vulnerability (implies --kev).
+    #[arg(long)]
+    fail_on_kev: bool,
+
+    /// Fail if any finding has been open longer than its severity's SLA
+    /// window, according to the history file (combine with --record).
+    #[arg(long)]
+    fail_on_sla: bool,
+
+    /// Create or update Jira issues for findings at or above this severity
+    /// (configured via the `JIRA_*` environment variables).
+    #[arg(long)]
+    jira: Option<Severity>,
+
+    /// File ServiceNow records for findings at or above the configured
+    /// severity (configured in the `[servicenow]` section of the config file).
+    #[arg(long)]
+    servicenow: bool,
+
+    /// Record the findings in the history file, for `trend`.
+    #[arg(long)]
+    record: bool,
+
+    /// Output format for the findings.
+    #[arg(long, value_enum, default_value_t = ResultsFormat::Text)]
+    format: ResultsFormat,
+
+    /// Write the findings to this file instead of stdout.
+    #[arg(long)]
+    out: Option<PathBuf>,
+
+    /// Custom Tera template for `--format html`.
+    #[arg(long)]
+    template: Option<PathBuf>,
+
+    /// Number of top findings listed by `--format markdown`.
+    #[arg(long, default_value_t = 10)]
+    top: usize,
+
+    /// Comma-separated columns for `--format csv` (default: all).
+    #[arg(long, value_delimiter = ',')]
+    columns: Option<Vec<CsvColumn>>,
+
+    /// Findings of a previous run (from `--format json`) to diff against in `--format markdown`.
+    #[arg(long)]
+    previous: Option<PathBuf>,
+
+    /// Upload the rendered findings to this location: `s3://bucket/prefix/`,
+    /// `az://container/prefix/`, or `gs://bucket/prefix/`, as enabled by the
+    /// `s3`, `azure`, and `gcs` features (credentials from the provider's
+    /// standard environment variables).
+    #[cfg(any(feature = "s3", feature = "azure", feature = "gcs"))]
+    #[arg(long)]
+    upload: Option<String>,
+
+    /// Object name template for `--upload`; supports `{scan_id}`, `{date}`,
+    /// `{timestamp}`, and `{ext}`.
+    #[cfg(any(feature = "s3", feature = "azure", feature = "gcs"))]
+    #[arg(long, default_value = "scan-{scan_id}-{timestamp}.{ext}")]
+    upload_name: String,
+
+    /// Server-side encryption for `--upload` to S3 (default: the bucket's default).
+    #[cfg(feature = "s3")]
+    #[arg(long, value_enum)]
+    upload_sse: Option<UploadSse>,
+
+    /// KMS key ID or ARN for `--upload-sse kms`.
+    #[cfg(feature = "s3")]
+    #[arg(long, requires = "upload_sse")]
+    upload_kms_key: Option<String>,
+}
+
+/// Server-side encryption modes for `--upload-sse`.
+#[cfg(feature = "s3")]
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum UploadSse {
+    /// SSE-S3 (`AES256`).
+    Aes256,
+    /// SSE-KMS.
+    Kms,
+}
+
+/// Output formats supported by the `results` subcommand.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ResultsFormat {
+    /// One line per finding.
+    Text,
+    /// A JSON array of findings.
+    Json,
+    /// A standalone HTML report.
+    Html,
+    /// A Markdown report for wikis and pull requests.
+    Markdown,
+    /// Normalized CSV, one row per host and plugin.
+    Csv,
+    /// One JSON object per finding.
+    Jsonl,
+    /// A GitLab DAST security report.
+    Gitlab,
+}
+
+#[cfg(any(feature = "s3", feature = "azure", feature = "gcs"))]
+impl ResultsFormat {
+    /// File extension used when uploading the rendered findings.
+    fn extension(self) -> &'static str {
+        match self {
+            ResultsFormat::Text => "txt",
+            ResultsFormat::Json | ResultsFormat::Gitlab => "json",
+            ResultsFormat::Html => "html",
+            ResultsFormat::Markdown => "md",
+            ResultsFormat::Csv => "csv",
+            ResultsFormat::Jsonl => "jsonl",
+        }
+    }
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::from(exit::SUCCESS),
+        Err(e) => {
+            error!("{}", e);
+            e.exit_code()
+        }
+    }
+}
+
+async fn run() -> CliResult<()> {
     dotenv().ok();
 
+    let mut targets = Targets::new().with_default(tracing::Level::INFO);
+    if NessusConfig::http_trace_from_env() {
+        // The redacted HTTP dump is logged at TRACE level.
+        targets = targets.with_target("nessus_launcher::http", tracing::Level::TRACE);
+    }
     let subscriber = FmtSubscriber::builder()
-        .with_max_level(tracing::Level::INFO)
-        .finish();
+        .with_max_level(tracing::Level::TRACE)
+        .finish()
+        .with(targets);
 
     tracing::subscriber::set_global_default(subscriber)
         .map_err(|e| nessus_launcher::NessusError::Other(format!("Failed to set logger: {e}")))?;
 
-    let args = Cli::parse();
+    let mut args = Cli::parse();
+    let cli_config = CliConfig::load(args.config.as_deref())?;
+
+    // Offline subcommands don't need Nessus credentials.
+    let command = match args.command.take() {
+        Some(Command::Trend(trend)) => return Ok(run_trend(&args.history, trend)?),
+        Some(Command::Sla(sla)) => return run_sla(&args.history, &cli_config, sla),
+        Some(Command::History(list)) => {
+            return Ok(history::show(
+                &args.history,
+                list.scan_id,
+                list.since.as_deref(),
+                list.json,
+            )?);
+        }
+        command => command,
+    };
 
     let config = NessusConfig::from_env()?;
     let client = NessusClient::new(config)?;
 
-    let scan_ids = match args.scan {
-        Some(ids) => ids,
-        None => NessusConfig::default_scan_ids_from_env(),
+    match command {
+        Some(Command::Results(results)) => run_results(&client, &cli_config, &args, *results).await,
+        Some(Command::Defectdojo(dojo)) => {
+            let dojo_client = DefectDojoClient::new(DefectDojoConfig::from_env()?)?;
+            let outcome = dojo_client
+                .sync_scan(&client, dojo.scan_id, dojo.product, &dojo.engagement)
+                .await?;
+            info!(
+                "Scan {} imported into DefectDojo engagement {} (test {})",
+                dojo.scan_id, outcome.engagement_id, outcome.test_id
+            );
+            Ok(())
+        }
+        Some(Command::Import(import)) => {
+            let scan = client.import_scan(&import.file).await?;
+            info!(
+                "Imported {} as scan {} ({})",
+                import.file.display(),
+                scan.id,
+                scan.name
+            );
+            println!("{}", scan.id);
+            Ok(())
+        }
+        Some(Command::Serve(serve)) => {
+            let schedules = cli_config
+                .schedules
+                .iter()
+                .map(|launch| {
+                    let profile = launch.profile.as_deref().or(args.profile.as_deref());
+                    Ok(schedules::Scheduled {
+                        notifiers: cli_config.notifiers(profile, launch.pagerduty)?,
+                        launch: launch.clone(),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(serve::serve(
+                client,
+                args.history.clone(),
+                schedules,
+                serve.listen,
+                #[cfg(feature = "grpc")]
+                serve.grpc_listen,
+            )
+            .await?)
+        }
+        #[cfg(feature = "redis")]
+        Some(Command::Consume(consume)) => {
+            let queue = consume::QueueConfig {
+                url: std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".into()),
+                queue: consume.queue,
+                results: consume.results,
+                consumer: consume.consumer,
+            };
+            Ok(consume::consume(client, queue, &args.history).await?)
+        }
+        Some(Command::Launch(launch)) => run_launch(&client, &cli_config, &args, &launch).await,
+        Some(Command::Trend(_) | Command::Sla(_) | Command::History(_)) => {
+            unreachable!("offline subcommands return early")
+        }
+        None => run_launch(&client, &cli_config, &args, &args.launch).await,
+    }
+}
+
+/// Launch scans, then report the outcome to the configured sinks and
+/// notifiers and record it in the history file.
+async fn run_launch(
+    client: &NessusClient,
+    cli_config: &CliConfig,
+    args: &Cli,
+    launch: &LaunchArgs,
+) -> CliResult<()> {
+    let requests: Vec<ScanRequest> = match &launch.plan {
+        Some(path) => plan::load(path)?.scans,
+        None => select_scans(client, launch)
+            .await?
+            .into_iter()
+            .map(ScanRequest::from)
+            .collect(),
+    };
+    let requests: Vec<ScanRequest> = requests
+        .into_iter()
+        .map(|r| if launch.wait { r.with_wait(true) } else { r })
+        .collect();
+
+    info!(
+        "Launching scans: {:?}",
+        requests.iter().map(|r| r.scan_id).collect::<Vec<_>>()
+    );
+
+    let options = client
+        .launch_options()
+        .clone()
+        .with_fail_fast(launch.fail_fast)
+        .with_verify_scan_ids(launch.verify_scans);
+    let trigger = launch_trigger(launch);
+    let user = history::user();
+    let launched_at = Utc::now();
+    let report = match client
+        .clone()
+        .with_launch_options(options)
+        .launch_requests(requests.clone())
+        .await
+    {
+        Ok(report) => report,
+        Err(e) => {
+            let records = match &e {
+                NessusError::Launch(report) => LaunchRecord::from_report(
+                    report,
+                    &trigger,
+                    user.as_deref(),
+                    launched_at,
+                    Utc::now(),
+                ),
+                e => {
+                    let scan_ids: Vec<u32> = requests.iter().map(|r| r.scan_id).collect();
+                    history::failed(
+                        &scan_ids,
+                        &trigger,
+                        user.as_deref(),
+                        launched_at,
+                        &e.to_string(),
+                    )
+                }
+            };
+            history::record(&args.history, records).await;
+            return Err(e.into());
+        }
+    };
+
+    let mut records =
+        LaunchRecord::from_report(&report, &trigger, user.as_deref(), launched_at, Utc::now());
+    let result = report_launch(
+        client,
+        cli_config,
+        args,
+        launch,
+        &requests,
+        &report,
+        &mut records,
+    )
+    .await;
+    history::record(&args.history, records).await;
+    result
+}
+
+/// What triggered `launch`, as recorded in the history file.
+fn launch_trigger(launch: &LaunchArgs) -> String {
+    if let Some(plan) = &launch.plan {
+        format!("launch --plan {}", plan.display())
+    } else if let Some(pattern) = &launch.pattern {
+        format!("launch --match {pattern}")
+    } else if let Some(folder) = &launch.folder {
+        format!("launch --folder {folder}")
+    } else if let Some(tag) = &launch.tag {
+        format!("launch --tag {tag}")
+    } else if launch.interactive {
+        "launch --interactive".into()
+    } else if launch.scan.is_some() {
+        "launch --scan".into()
+    } else {
+        "launch".into()
+    }
+}
+
+/// Report the outcome of launching `requests` to the configured sinks and
+/// notifiers, finish the scans that need it, and update their `records`.
+async fn report_launch(
+    client: &NessusClient,
+    cli_config: &CliConfig,
+    args: &Cli,
+    launch: &LaunchArgs,
+    requests: &[ScanRequest],
+    report: &LaunchReport,
+    records: &mut [LaunchRecord],
+) -> CliResult<()> {
+    if args.output == OutputMode::Github {
+        for failure in &report.failed {
+            println!("{}", Annotation::from(failure));
+        }
+        write_job_summary(&github::launch_summary(report))?;
+    }
+
+    if launch.servicenow && !report.failed.is_empty() {
+        let servicenow = ServiceNowClient::new(cli_config.servicenow()?)?;
+        servicenow.file_launch_failures(&report.failed).await?;
+    }
+
+    let notifiers = cli_config.notifiers(args.profile.as_deref(), launch.pagerduty)?;
+
+    let launch_error = CliError::from_launch(report);
+    let finishing = requests.iter().any(ScanRequest::needs_finishing);
+    notify_all(
+        &notifiers,
+        &NotifyEvent::LaunchCompleted {
+            report: report.clone(),
+        },
+    )
+    .await?;
+
+    if finishing {
+        let finished = client.finish_requests(requests, report).await;
+        let finished_at = Utc::now();
+        for scan in &finished {
+            if let Some(record) = records.iter_mut().find(|r| r.scan_id == scan.scan_id) {
+                record.completed_at = finished_at;
+                record.status = scan.status;
+                record.error.clone_from(&scan.error);
+            }
+            if let Some(status) = scan.status {
+                let event = NotifyEvent::ScanFinished {
+                    scan_id: scan.scan_id,
+                    status,
+                };
+                notify_all(&notifiers, &event).await?;
+            }
+        }
+
+        let unfinished = finished.iter().filter(|f| f.error.is_some()).count();
+        if launch_error.is_none() && unfinished > 0 {
+            return Err(NessusError::Other(format!(
+                "{unfinished} scan(s) could not be waited for or exported"
+            ))
+            .into());
+        }
+    }
+
+    launch_error.map_or(Ok(()), Err)
+}
+
+/// The IDs of the scans `launch` selects: those given with `--scan`, the
+/// enabled ones whose name matches `--match` or in `--folder`, those
+/// targeting `--tag`, those picked with `--interactive`, or `DEFAULT_SCAN_IDS`.
+async fn select_scans(client: &NessusClient, launch: &LaunchArgs) -> CliResult<Vec<u32>> {
+    if let Some(scan_ids) = &launch.scan {
+        return Ok(scan_ids.clone());
+    }
+    if launch.interactive {
+        return Ok(picker::pick_scans(client).await?);
+    }
+    if let Some(folder) = &launch.folder {
+        let scans = client.scans_in_folder(folder).await?;
+        let enabled: Vec<u32> = scans.iter().filter(|s| s.enabled).map(|s| s.id).collect();
+        if enabled.len() < scans.len() {
+            info!(
+                "Skipping {} disabled scan(s) in folder {}",
+                scans.len() - enabled.len(),
+                folder
+            );
+        }
+        return Ok(enabled);
+    }
+    if let Some(tag) = &launch.tag {
+        let scans = client.scans_with_tag(tag).await?;
+        if scans.is_empty() {
+            return Err(NessusError::Config(format!("No scans target tag {tag}")).into());
+        }
+        return Ok(scans.into_iter().map(|s| s.id).collect());
+    }
+    let Some(pattern) = &launch.pattern else {
+        return Ok(NessusConfig::default_scan_ids_from_env()?);
+    };
+
+    let pattern = if launch.regex {
+        ScanNamePattern::regex(pattern)?
+    } else {
+        ScanNamePattern::glob(pattern)
+    };
+    let scans = client.scans_matching(&pattern).await?;
+    if scans.is_empty() {
+        return Err(NessusError::Config(format!("No scans match '{pattern}'")).into());
+    }
+    let mut enabled = Vec::with_capacity(scans.len());
+    for scan in &scans {
+        if scan.enabled {
+            info!("Scan {} ({}) matches '{}'", scan.id, scan.name, pattern);
+            enabled.push(scan.id);
+        } else {
+            info!(
+                "Skipping disabled scan {} ({}) matching '{}'",
+                scan.id, scan.name, pattern
+            );
+        }
+    }
+    Ok(enabled)
+}
+
+/// Send `event` to every notifier.
+async fn notify_all(notifiers: &[Box<dyn Notifier>], event: &NotifyEvent) -> Result<()> {
+    for notifier in notifiers {
+        notifier.notify(event).await?;
+    }
+    Ok(())
+}
+
+/// Fetch, enrich, print, and gate the findings of a scan.
+async fn run_results(
+    client: &NessusClient,
+    cli_config: &CliConfig,
+    cli: &Cli,
+    args: ResultsArgs,
+) -> CliResult<()> {
+    let filter = ResultFilter {
+        min_severity: args.min_severity,
+        cve: args.cve,
+        plugin_id: args.plugin,
+        hostname: args.host,
+    };
+
+    let mut findings = if args.no_plugin_rules {
+        client.findings(args.scan_id, &filter).await?
+    } else {
+        client
+            .findings_with_plugin_rules(args.scan_id, &filter)
+            .await?
+    };
+    info!("Fetched {} findings for scan {}", findings.len(), args.scan_id);
+
+    if args.plugin_output {
+        client.load_plugin_output(&mut findings).await?;
+    }
+
+    if args.epss || args.fail_on_epss.is_some() {
+        let mut epss = EpssClient::new()?.with_cache_file(args.cache_dir.join("epss.json"))?;
+        epss.enrich(&mut findings).await?;
+    }
+
+    if args.kev || args.fail_on_kev {
+        let catalog = KevClient::new()?
+            .with_cache_file(args.cache_dir.join("kev.json"))
+            .catalog()
+            .await?;
+        catalog.enrich(&mut findings);
+    }
+
+    if args.nvd {
+        let mut nvd = NvdClient::new()?
+            .with_api_key(std::env::var("NVD_API_KEY").ok())
+            .with_cache_file(args.cache_dir.join("nvd.json"))?;
+        nvd.enrich(&mut findings).await?;
+    }
+
+    if let Some(threshold) = args.jira {
+        let jira = JiraClient::new(JiraConfig::from_env()?)?;
+        jira.sync_findings(&findings, threshold).await?;
+    }
+
+    if args.servicenow {
+        let servicenow = ServiceNowClient::new(cli_config.servicenow()?)?;
+        servicenow.file_findings(&findings).await?;
+    }
+
+    if args.record {
+        HistoryStore::update(&cli.history, |history| {
+            history.record(args.scan_id, Utc::now(), &findings)
+        })?;
+        info!("Recorded findings in {}", cli.history.display());
+    }
+
+    let rendered = match args.format {
+        ResultsFormat::Text => render_text(&findings),
+        ResultsFormat::Json => serde_json::to_string_pretty(&findings)?,
+        ResultsFormat::Html => {
+            let mut renderer = HtmlRenderer::new();
+            if let Some(path) = &args.template {
+                renderer = renderer.with_template_file(path)?;
+            }
+            let title = format!("Nessus scan {} results", args.scan_id);
+            renderer.render(&Report::new(title, findings.clone()))?
+        }
+        ResultsFormat::Markdown => {
+            let mut renderer = MarkdownRenderer::new().with_top(args.top);
+            if let Some(path) = &args.previous {
+                let previous: Vec<Finding> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+                renderer = renderer.with_previous(previous);
+            }
+            let title = format!("Nessus scan {} results", args.scan_id);
+            renderer.render(&Report::new(title, findings.clone()))
+        }
+        ResultsFormat::Csv => {
+            let mut exporter = CsvExporter::new();
+            if let Some(columns) = args.columns.clone() {
+                exporter = exporter.with_columns(columns);
+            }
+            exporter.to_string(&findings)?
+        }
+        ResultsFormat::Jsonl => {
+            let mut buf = Vec::new();
+            jsonl::write(&mut buf, &findings)?;
+            String::from_utf8_lossy(&buf).into_owned()
+        }
+        ResultsFormat::Gitlab => GitlabExporter::new().to_string(&findings)?,
+    };
+
+    #[cfg(any(feature = "s3", feature = "azure", feature = "gcs"))]
+    if let Some(url) = &args.upload {
+        #[cfg(feature = "s3")]
+        let store: Box<dyn ArtifactStore> = match args.upload_sse {
+            None => upload::open(url)?,
+            Some(sse) => {
+                let target = UploadTarget::parse(url)?;
+                if target.scheme != "s3" {
+                    return Err(NessusError::Config(
+                        "--upload-sse requires an s3:// upload URL".into(),
+                    )
+                    .into());
+                }
+                let encryption = match sse {
+                    UploadSse::Aes256 => ServerSideEncryption::S3Managed,
+                    UploadSse::Kms => ServerSideEncryption::Kms {
+                        key_id: args.upload_kms_key.clone(),
+                    },
+                };
+                Box::new(S3Store::new(target).with_encryption(encryption))
+            }
+        };
+        #[cfg(not(feature = "s3"))]
+        let store = upload::open(url)?;
+        let template = ObjectNameTemplate::new(&args.upload_name);
+        store
+            .upload(&template, args.scan_id, args.format.extension(), rendered.clone().into_bytes())
+            .await?;
+    }
+
+    match &args.out {
+        Some(path) => {
+            std::fs::write(path, rendered)?;
+            info!("Wrote results to {}", path.display());
+        }
+        None => print!("{rendered}"),
+    }
+
+    let policy = GatePolicy {
+        fail_on_severity: args.fail_on_severity,
+        fail_on_epss: args.fail_on_epss,
+        fail_on_kev: args.fail_on_kev,
+    };
+    let mut report = policy.evaluate(&findings);
+
+    if args.fail_on_sla {
+        let history = HistoryStore::open(&cli.history)?;
+        let sla = cli_config.sla.unwrap_or_default();
+        report
+            .breaches
+            .extend(sla.evaluate(&history, args.scan_id, &findings, Utc::now()));
+    }
+
+    for breach in &report.breaches {
+        error!("Gate breach: {}", breach);
+    }
+
+    if cli.output == OutputMode::Github {
+        for breach in &report.breaches {
+            println!("{}", Annotation::from(breach));
+        }
+        let title = format!("Nessus scan {} results", args.scan_id);
+        let counts = SeverityCounts::from_findings(&findings);
+        write_job_summary(&github::results_summary(&title, &counts, &report))?;
+    }
+
+    if report.passed() {
+        Ok(())
+    } else {
+        Err(CliError::GateBreached(format!(
+            "{} breach(es)",
+            report.breaches.len()
+        )))
+    }
+}
+
+/// Compute and write the trend of a scan from the history file.
+fn run_trend(history: &Path, args: TrendArgs) -> Result<()> {
+    let trend = HistoryStore::open(history)?.trend(args.scan_id);
+    if trend.points.is_empty() {
+        return Err(NessusError::Other(format!(
+            "No recorded runs of scan {} in {}",
+            args.scan_id,
+            history.display()
+        )));
+    }
+
+    let rendered = match args.format {
+        TrendFormat::Json => serde_json::to_string_pretty(&trend)?,
+        TrendFormat::Csv => {
+            let mut buf = Vec::new();
+            trend.write_csv(&mut buf)?;
+            String::from_utf8_lossy(&buf).into_owned()
+        }
+        TrendFormat::Html => render_trend(&trend)?,
     };
 
-    info!("Launching scans: {:?}", scan_ids);
+    match &args.out {
+        Some(path) => {
+            std::fs::write(path, rendered)?;
+            info!("Wrote trend to {}", path.display());
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+/// List the overdue findings of a scan from the history file.
+fn run_sla(history: &Path, cli_config: &CliConfig, args: SlaArgs) -> CliResult<()> {
+    let ages = HistoryStore::open(history)?.finding_ages(args.scan_id);
+    let now = Utc::now();
+    let sla = cli_config.sla.unwrap_or_default();
+    let overdue = sla.overdue(&ages, now);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&overdue)?);
+    } else {
+        for age in &overdue {
+            let f = &age.finding;
+            println!(
+                "{:<20} {:<8} {:>7} {} (open {} days, first seen {})",
+                f.hostname,
+                f.severity,
+                f.plugin_id,
+                f.plugin_name,
+                age.age_days(now),
+                age.first_seen.format("%Y-%m-%d")
+            );
+        }
+    }
 
-    client.launch_scans_parallel(scan_ids).await
+    if args.fail && !overdue.is_empty() {
+        return Err(CliError::GateBreached(format!(
+            "{} finding(s) exceed their SLA",
+            overdue.len()
+        )));
+    }
+    Ok(())
 }
 
+/// Append `markdown` to the GitHub Actions job summary, if running in Actions.
+fn write_job_summary(markdown: &str) -> Result<()> {
+    let Some(path) = std::env::var_os("GITHUB_STEP_SUMMARY") else {
+        info!("GITHUB_STEP_SUMMARY is not set; skipping job summary");
+        return Ok(());
+    };
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{markdown}")?;
+    Ok(())
+}
+
+/// Render findings as one line each.
+fn render_text(findings: &[Finding]) -> String {
+    let mut out = String::new();
+    for f in findings {
+        let mut line = format!(
+            "{:<20} {:<8} {:>7} {}",
+            f.hostname, f.severity, f.plugin_id, f.plugin_name
+        );
+        if !f.cves.is_empty() {
+            line.push_str(&format!(" [{}]", f.cves.join(", ")));
+        }
+        if let Some(epss) = f.epss {
+            line.push_str(&format!(" epss={epss:.3}"));
+        }
+        if let Some(cvss) = f.cve_details.iter().filter_map(|d| d.cvss_score).reduce(f64::max) {
+            line.push_str(&format!(" cvss={cvss:.1}"));
+        }
+        if f.kev {
+            line.push_str(" KEV");
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
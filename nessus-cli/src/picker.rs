@@ -0,0 +1,57 @@
+//! Interactive scan selection for `launch --interactive`.
+//!
+//! Presents the server's scan list as a fuzzy-searchable multi-select, so
+//! operators can pick scans by name instead of looking up their IDs.
+
+use inquire::{InquireError, MultiSelect};
+use nessus_launcher::{NessusClient, NessusError, Result, ScanSummary};
+use std::fmt;
+
+/// Number of scans shown at once; the rest are reached by scrolling or typing.
+const PAGE_SIZE: usize = 15;
+
+/// A scan as shown in the picker.
+struct ScanChoice(ScanSummary);
+
+impl fmt::Display for ScanChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (#{}, {})", self.0.name, self.0.id, self.0.status)
+    }
+}
+
+/// Let the user pick scans from the server's scan list and return their IDs.
+///
+/// # Errors
+///
+/// Returns an error if the scans cannot be listed, [`NessusError::Config`]
+/// if there are none or stdin is not a terminal, and [`NessusError::Other`]
+/// if the user cancels the prompt.
+pub async fn pick_scans(client: &NessusClient) -> Result<Vec<u32>> {
+    let mut scans = client.list_scans().await?;
+    if scans.is_empty() {
+        return Err(NessusError::Config("No scans to choose from".into()));
+    }
+    scans.sort_by_key(|s| s.name.to_lowercase());
+    let choices = scans.into_iter().map(ScanChoice).collect();
+
+    // The prompt blocks on terminal input, so keep it off the runtime's workers.
+    let picked = tokio::task::spawn_blocking(move || {
+        MultiSelect::new("Scans to launch:", choices)
+            .with_page_size(PAGE_SIZE)
+            .with_help_message("type to filter, space to select, enter to launch")
+            .prompt()
+    })
+    .await
+    .map_err(|e| NessusError::Other(format!("Scan picker failed: {e}")))?
+    .map_err(|e| match e {
+        InquireError::NotTTY => {
+            NessusError::Config("--interactive needs a terminal; use --scan instead".into())
+        }
+        InquireError::OperationCanceled | InquireError::OperationInterrupted => {
+            NessusError::Other("Scan selection cancelled".into())
+        }
+        e => NessusError::Other(format!("Scan picker failed: {e}")),
+    })?;
+
+    Ok(picked.into_iter().map(|choice| choice.0.id).collect())
+}
@@ -0,0 +1,39 @@
+//! Launch plan files for `launch --plan`.
+//!
+//! A plan lists the scans to launch, each with optional overrides:
+//!
+//! ```toml
+//! [[scans]]
+//! scan_id = 5
+//! alt_targets = ["10.0.0.0/24"]
+//! retries = 1
+//!
+//! [[scans]]
+//! scan_id = 8
+//! wait = true
+//! export = { format = "nessus", path = "reports/scan-8.nessus" }
+//! ```
+//!
+//! Settings a scan does not override come from the command line.
+
+use nessus_launcher::{LaunchPlan, NessusError, Result};
+use std::path::Path;
+
+/// Load the plan in `path`.
+///
+/// # Errors
+///
+/// Returns an I/O error if the file cannot be read, or
+/// [`NessusError::Config`] if it is not a valid plan or lists no scans.
+pub fn load(path: &Path) -> Result<LaunchPlan> {
+    let raw = std::fs::read_to_string(path)?;
+    let plan: LaunchPlan = toml::from_str(&raw)
+        .map_err(|e| NessusError::Config(format!("Invalid plan file {}: {e}", path.display())))?;
+    if plan.scans.is_empty() {
+        return Err(NessusError::Config(format!(
+            "Plan file {} lists no scans",
+            path.display()
+        )));
+    }
+    Ok(plan)
+}
@@ -0,0 +1,126 @@
+//! Recurring launches of the `serve` daemon, from `[[schedules]]` in the
+//! configuration file:
+//!
+//! ```toml
+//! [[schedules]]
+//! name = "emea-nightly"
+//! scan_ids = [5, 8]
+//! at = "02:30"
+//! days = ["mon", "tue", "wed", "thu", "fri"]
+//! timezone = "Europe/Berlin"
+//! on_gap = "skip"      # or "shift" (default)
+//! on_overlap = "once"  # or "twice"
+//! ```
+//!
+//! Times are local to the schedule's time zone (UTC if not given), so a
+//! window stays at the same wall-clock time across DST changes. `on_gap`
+//! decides whether a run whose time is skipped by clocks jumping forward is
+//! dropped or moved past the jump; `on_overlap` whether a time repeated by
+//! clocks falling back runs once or twice.
+//!
+//! Scheduled runs are ordinary batches: they can be followed through the
+//! API and are recorded in the history file as `schedule <name>`. Once a
+//! batch is done it is reported to the notifiers of the schedule's
+//! `profile` (or `--profile`), and to PagerDuty with `pagerduty = true`.
+
+use crate::batches::{Batch, BatchStatus, BatchStore};
+use crate::config::ScheduledLaunch;
+use chrono::Utc;
+use nessus_launcher::notify::{Notifier, NotifyEvent};
+use nessus_launcher::{LaunchFailure, LaunchReport};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Longest single sleep while waiting for a run.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// A schedule and the notifiers its batches are reported to.
+pub struct Scheduled {
+    /// What to launch, and when.
+    pub launch: ScheduledLaunch,
+    /// Where to report the outcome of each batch.
+    pub notifiers: Vec<Box<dyn Notifier>>,
+}
+
+/// Launch the scans of every schedule at its times, until the daemon stops.
+pub fn spawn(batches: &Arc<BatchStore>, schedules: Vec<Scheduled>) {
+    for scheduled in schedules {
+        tokio::spawn(run(Arc::clone(batches), scheduled));
+    }
+}
+
+async fn run(batches: Arc<BatchStore>, scheduled: Scheduled) {
+    let Scheduled {
+        launch: scheduled,
+        notifiers,
+    } = scheduled;
+    loop {
+        let Some(next) = scheduled.schedule.next_after(Utc::now()) else {
+            warn!(
+                "Schedule {} has no upcoming run; check its days",
+                scheduled.name
+            );
+            return;
+        };
+        info!(
+            "Schedule {} next launches scans {:?} at {} ({})",
+            scheduled.name,
+            scheduled.scan_ids,
+            next.with_timezone(&scheduled.schedule.timezone),
+            next
+        );
+        // Sleep in steps, so a wall clock corrected while waiting (e.g. after
+        // a host suspend) still triggers the run close to its time.
+        while let Ok(remaining) = (next - Utc::now()).to_std() {
+            tokio::time::sleep(remaining.min(MAX_SLEEP)).await;
+        }
+        let batch = batches.launch_scheduled(&scheduled.name, scheduled.scan_ids.clone());
+        info!("Schedule {} started batch {}", scheduled.name, batch.id);
+        if !notifiers.is_empty() {
+            notify(&batches, &batch.id, &notifiers).await;
+        }
+    }
+}
+
+/// Wait for the batch `id` to be done and report it to `notifiers`. A batch
+/// that failed outright is reported with every scan failed.
+async fn notify(batches: &BatchStore, id: &str, notifiers: &[Box<dyn Notifier>]) {
+    let Some(mut rx) = batches.watch(id) else {
+        return;
+    };
+    let Ok(batch) = rx
+        .wait_for(|b| b.status != BatchStatus::Running)
+        .await
+        .map(|b| b.clone())
+    else {
+        return;
+    };
+
+    let event = NotifyEvent::LaunchCompleted {
+        report: report_of(batch),
+    };
+    for notifier in notifiers {
+        if let Err(e) = notifier.notify(&event).await {
+            warn!("Could not notify about batch {}: {}", id, e);
+        }
+    }
+}
+
+/// The launch report of a done `batch`.
+fn report_of(batch: Batch) -> LaunchReport {
+    match batch.error {
+        Some(error) if batch.status == BatchStatus::Failed => LaunchReport {
+            failed: batch
+                .scan_ids
+                .into_iter()
+                .map(|scan_id| LaunchFailure {
+                    scan_id,
+                    error: error.clone(),
+                })
+                .collect(),
+            ..LaunchReport::default()
+        },
+        _ => batch.report,
+    }
+}
@@ -0,0 +1,280 @@
+//! `serve`: a small REST API over the library for other internal services.
+//!
+//! Services trigger scans through this daemon instead of each holding Nessus
+//! credentials. Every request must carry `Authorization: Bearer <token>` with
+//! the token from `NESSUS_CLI_API_TOKEN`.
+//!
+//! | Method | Path                    | Description                                    |
+//! |--------|-------------------------|------------------------------------------------|
+//! | `POST` | `/batches`              | Launch `{"scan_ids": [...]}` in the background |
+//! | `GET`  | `/batches/{id}`         | Batch status and its launch report so far      |
+//! | `GET`  | `/scans/{id}/status`    | Current status of a scan                       |
+//! | `GET`  | `/scans/{id}/findings`  | Findings of a scan (`?min_severity=high`)      |
+//!
+//! `GET /healthz` (the process is up) and `GET /readyz` (Nessus is reachable
+//! and accepts the daemon's session) need no token, for Kubernetes probes.
+//!
+//! A launch request may name who asked for it in `requested_by`, which is
+//! recorded as the user in the history file.
+//!
+//! With the `grpc` feature, the same batches are also served over gRPC (see
+//! `grpc`). Batches are kept in memory and are lost when the daemon restarts.
+//! Batches can also be launched on a schedule (see `schedules`).
+
+use crate::batches::{Batch, BatchStore};
+use crate::schedules::{self, Scheduled};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use nessus_launcher::{
+    Finding, KeepAliveOptions, NessusClient, NessusError, Result, ResultFilter, ScanStatus,
+    Severity,
+};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Environment variable holding the bearer token clients must present.
+pub const API_TOKEN_ENV: &str = "NESSUS_CLI_API_TOKEN";
+
+/// The bearer token clients must present.
+pub struct ApiToken(String);
+
+impl ApiToken {
+    /// The token from `NESSUS_CLI_API_TOKEN`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if the variable is unset or empty.
+    pub fn from_env() -> Result<Self> {
+        std::env::var(API_TOKEN_ENV)
+            .ok()
+            .filter(|t| !t.is_empty())
+            .map(Self)
+            .ok_or_else(|| NessusError::Config(format!("{API_TOKEN_ENV} must be set to serve")))
+    }
+
+    /// Whether `authorization`, the value of an `Authorization` header, is
+    /// `Bearer <token>`.
+    pub fn accepts(&self, authorization: Option<&str>) -> bool {
+        authorization
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), self.0.as_bytes()))
+    }
+}
+
+/// Compare without returning early, so timing doesn't reveal the token.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Shared state of the REST API.
+struct AppState {
+    token: Arc<ApiToken>,
+    batches: Arc<BatchStore>,
+}
+
+#[derive(Deserialize)]
+struct LaunchRequest {
+    scan_ids: Vec<u32>,
+    /// Who asked for the launch, for the history file.
+    #[serde(default)]
+    requested_by: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ScanStatusResponse {
+    scan_id: u32,
+    status: ScanStatus,
+}
+
+#[derive(Deserialize)]
+struct FindingsQuery {
+    min_severity: Option<Severity>,
+}
+
+/// An error response: `{"error": "..."}` with a fitting status code.
+struct ApiError(StatusCode, String);
+
+impl From<NessusError> for ApiError {
+    fn from(e: NessusError) -> Self {
+        let status = match e {
+            NessusError::Config(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::BAD_GATEWAY,
+        };
+        ApiError(status, e.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+/// Serve the REST API on `listen`, and the gRPC API on `grpc_listen` if
+/// given, until Ctrl-C, recording launched batches in the history file at
+/// `history`. The scans of `schedules` are launched at their times.
+///
+/// # Errors
+///
+/// Returns [`NessusError::Config`] if `NESSUS_CLI_API_TOKEN` is not set, or
+/// an error if logging in to Nessus or binding an address fails.
+pub async fn serve(
+    client: NessusClient,
+    history: PathBuf,
+    schedules: Vec<Scheduled>,
+    listen: SocketAddr,
+    #[cfg(feature = "grpc")] grpc_listen: Option<SocketAddr>,
+) -> Result<()> {
+    let token = Arc::new(ApiToken::from_env()?);
+
+    // One session for the daemon's lifetime instead of a login per request.
+    let _keep_alive = client
+        .keep_session_alive(KeepAliveOptions::default())
+        .await?;
+    let batches = Arc::new(BatchStore::new(client, history));
+    schedules::spawn(&batches, schedules);
+
+    #[cfg(feature = "grpc")]
+    if let Some(grpc_listen) = grpc_listen {
+        let grpc = crate::grpc::serve(Arc::clone(&token), Arc::clone(&batches), grpc_listen);
+        tokio::try_join!(serve_rest(token, batches, listen), grpc)?;
+        return Ok(());
+    }
+
+    serve_rest(token, batches, listen).await
+}
+
+async fn serve_rest(
+    token: Arc<ApiToken>,
+    batches: Arc<BatchStore>,
+    listen: SocketAddr,
+) -> Result<()> {
+    let app = router(Arc::new(AppState { token, batches }));
+
+    let listener = tokio::net::TcpListener::bind(listen).await?;
+    info!("Serving the REST API on {}", listen);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    Ok(())
+}
+
+/// Resolves on Ctrl-C.
+pub async fn shutdown_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+fn router(state: Arc<AppState>) -> Router {
+    let probes = Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz));
+
+    Router::new()
+        .route("/batches", post(launch_batch))
+        .route("/batches/{id}", get(batch))
+        .route("/scans/{id}/status", get(scan_status))
+        .route("/scans/{id}/findings", get(findings))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            authorize,
+        ))
+        .merge(probes)
+        .with_state(state)
+}
+
+/// Reject requests without the expected bearer token.
+async fn authorize(State(state): State<Arc<AppState>>, request: Request, next: Next) -> Response {
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    if state.token.accepts(authorization) {
+        next.run(request).await
+    } else {
+        ApiError(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid bearer token".into(),
+        )
+        .into_response()
+    }
+}
+
+/// Liveness: the daemon is up and serving requests.
+async fn healthz() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": "ok" }))
+}
+
+/// Readiness: Nessus is reachable and accepts the daemon's session.
+async fn readyz(State(state): State<Arc<AppState>>) -> Response {
+    match state.batches.client().check_session().await {
+        Ok(()) => Json(serde_json::json!({ "status": "ready" })).into_response(),
+        Err(e) => {
+            warn!("Readiness check failed: {}", e);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({ "status": "unavailable", "error": e.to_string() })),
+            )
+                .into_response()
+        }
+    }
+}
+
+async fn launch_batch(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<LaunchRequest>,
+) -> std::result::Result<(StatusCode, Json<Batch>), ApiError> {
+    if request.scan_ids.is_empty() {
+        return Err(ApiError(
+            StatusCode::BAD_REQUEST,
+            "scan_ids is empty".into(),
+        ));
+    }
+    let batch = state
+        .batches
+        .launch(request.scan_ids, request.requested_by);
+    Ok((StatusCode::ACCEPTED, Json(batch)))
+}
+
+async fn batch(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> std::result::Result<Json<Batch>, ApiError> {
+    state
+        .batches
+        .get(&id)
+        .map(Json)
+        .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("No batch {id}")))
+}
+
+async fn scan_status(
+    State(state): State<Arc<AppState>>,
+    Path(scan_id): Path<u32>,
+) -> std::result::Result<Json<ScanStatusResponse>, ApiError> {
+    let status = state.batches.client().scan_status(scan_id).await?;
+    Ok(Json(ScanStatusResponse { scan_id, status }))
+}
+
+async fn findings(
+    State(state): State<Arc<AppState>>,
+    Path(scan_id): Path<u32>,
+    Query(query): Query<FindingsQuery>,
+) -> std::result::Result<Json<Vec<Finding>>, ApiError> {
+    let mut filter = ResultFilter::new();
+    if let Some(severity) = query.min_severity {
+        filter = filter.min_severity(severity);
+    }
+    let findings = state
+        .batches
+        .client()
+        .findings_with_plugin_rules(scan_id, &filter)
+        .await?;
+    Ok(Json(findings))
+}
@@ -0,0 +1,369 @@
+//! Nessus Agent management (Nessus Manager only).
+//!
+//! This module extends [`NessusClient`] with the agent endpoints of the
+//! manager's local scanner, so stale agents can be reported and cleaned up
+//! by automation:
+//!
+//! - [`NessusClient::list_agents`]
+//! - [`NessusClient::agent_details`]
+//! - [`NessusClient::unlink_agent`]
+//! - [`NessusClient::list_agent_groups`], [`NessusClient::create_agent_group`],
+//!   and [`NessusClient::delete_agent_group`]
+//! - [`NessusClient::add_agent_to_group`] and [`NessusClient::remove_agent_from_group`]
+//! - [`NessusClient::agent_scans_for_group`] and
+//!   [`NessusClient::launch_agent_scans_for_group`]
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::{AgentFilter, NessusClient};
+//! use std::time::Duration;
+//!
+//! # async fn example(client: NessusClient) -> nessus_launcher::Result<()> {
+//! let stale = AgentFilter::new().not_seen_for(Duration::from_secs(30 * 86_400));
+//! for agent in client.list_agents(&stale).await? {
+//!     client.unlink_agent(agent.id).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{LaunchReport, NessusClient, Result, ScanSummary};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The manager's own scanner, which the agents are linked to.
+const LOCAL_SCANNER: u32 = 1;
+
+/// A Nessus Agent linked to the manager.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Agent {
+    /// Agent identifier.
+    pub id: u32,
+    /// Agent UUID.
+    #[serde(default)]
+    pub uuid: String,
+    /// Agent name, usually the host's name.
+    pub name: String,
+    /// Platform, e.g. `LINUX` or `WINDOWS`.
+    #[serde(default)]
+    pub platform: String,
+    /// Operating system distribution.
+    #[serde(default)]
+    pub distro: Option<String>,
+    /// IP address the agent last connected from.
+    #[serde(default)]
+    pub ip: Option<String>,
+    /// Connection status, e.g. `online` or `offline`.
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Agent software version.
+    #[serde(default)]
+    pub core_version: Option<String>,
+    /// Unix timestamp of the agent's last check-in.
+    #[serde(default)]
+    pub last_connect: Option<u64>,
+    /// Unix timestamp of the agent's last scan.
+    #[serde(default)]
+    pub last_scanned: Option<u64>,
+    /// Unix timestamp of when the agent was linked.
+    #[serde(default)]
+    pub linked_on: Option<u64>,
+}
+
+/// Conditions for [`NessusClient::list_agents`].
+///
+/// All configured conditions must match. They are sent to the manager as
+/// query filters and also applied to the response, so managers that ignore
+/// a filter still yield the right agents.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentFilter {
+    /// Only include agents last seen before this Unix timestamp, or never.
+    pub last_seen_before: Option<u64>,
+    /// Only include agents last seen at or after this Unix timestamp.
+    pub last_seen_after: Option<u64>,
+    /// Only include agents whose name contains this value.
+    pub name: Option<String>,
+}
+
+impl AgentFilter {
+    /// Create a filter that matches every agent.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only include agents last seen before the Unix timestamp `timestamp`.
+    pub fn last_seen_before(mut self, timestamp: u64) -> Self {
+        self.last_seen_before = Some(timestamp);
+        self
+    }
+
+    /// Only include agents last seen at or after the Unix timestamp `timestamp`.
+    pub fn last_seen_after(mut self, timestamp: u64) -> Self {
+        self.last_seen_after = Some(timestamp);
+        self
+    }
+
+    /// Only include agents that have not checked in for at least `age`.
+    pub fn not_seen_for(self, age: Duration) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        self.last_seen_before(now.saturating_sub(age).as_secs())
+    }
+
+    /// Only include agents whose name contains `name`.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Whether `agent` satisfies every configured condition.
+    pub fn matches(&self, agent: &Agent) -> bool {
+        if let Some(before) = self.last_seen_before
+            && agent.last_connect.is_some_and(|seen| seen >= before)
+        {
+            return false;
+        }
+        if let Some(after) = self.last_seen_after
+            && agent.last_connect.is_none_or(|seen| seen < after)
+        {
+            return false;
+        }
+        if let Some(name) = &self.name
+            && !agent.name.contains(name.as_str())
+        {
+            return false;
+        }
+        true
+    }
+
+    /// Render the filter as manager query parameters.
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut query = Vec::new();
+        if let Some(before) = self.last_seen_before {
+            query.push(("f".to_string(), format!("last_connect:lt:{before}")));
+        }
+        if let Some(after) = self.last_seen_after {
+            query.push(("f".to_string(), format!("last_connect:gte:{after}")));
+        }
+        if let Some(name) = &self.name {
+            query.push(("f".to_string(), format!("name:match:{name}")));
+        }
+        if !query.is_empty() {
+            query.push(("ft".to_string(), "and".to_string()));
+        }
+        query
+    }
+}
+
+/// A group of agents that agent scans can target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentGroup {
+    /// Group identifier.
+    pub id: u32,
+    /// Group UUID.
+    #[serde(default)]
+    pub uuid: String,
+    /// Group name.
+    pub name: String,
+    /// Number of agents in the group.
+    #[serde(default)]
+    pub agents_count: u32,
+}
+
+#[derive(Deserialize)]
+struct AgentGroupsResponse {
+    #[serde(default)]
+    groups: Option<Vec<AgentGroup>>,
+}
+
+#[derive(Deserialize)]
+struct AgentTargetsResponse {
+    info: AgentTargets,
+}
+
+#[derive(Deserialize)]
+struct AgentTargets {
+    #[serde(default)]
+    agent_targets: Option<Vec<AgentTarget>>,
+}
+
+#[derive(Deserialize)]
+struct AgentTarget {
+    id: u32,
+}
+
+#[derive(Deserialize)]
+struct AgentsResponse {
+    #[serde(default)]
+    agents: Option<Vec<Agent>>,
+}
+
+impl NessusClient {
+    /// List the agents linked to the manager that match `filter`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the request fails.
+    pub async fn list_agents(&self, filter: &AgentFilter) -> Result<Vec<Agent>> {
+        let session = self.authenticate().await?;
+        let resp: AgentsResponse = self
+            .get_json_with_query(
+                &session,
+                &format!("/scanners/{}/agents", LOCAL_SCANNER),
+                &filter.to_query(),
+            )
+            .await?;
+        Ok(resp
+            .agents
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|a| filter.matches(a))
+            .collect())
+    }
+
+    /// Fetch a single agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the agent cannot be fetched.
+    pub async fn agent_details(&self, agent_id: u32) -> Result<Agent> {
+        let session = self.authenticate().await?;
+        self.get_json(
+            &session,
+            &format!("/scanners/{}/agents/{}", LOCAL_SCANNER, agent_id),
+        )
+        .await
+    }
+
+    /// Unlink an agent from the manager.
+    ///
+    /// The agent stops receiving scan jobs; it has to be relinked on the host
+    /// to be used again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the agent cannot be unlinked.
+    pub async fn unlink_agent(&self, agent_id: u32) -> Result<()> {
+        let path = format!("/scanners/{}/agents/{}", LOCAL_SCANNER, agent_id);
+        self.send_without_body(Method::DELETE, &path).await
+    }
+
+    /// List all agent groups.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the request fails.
+    pub async fn list_agent_groups(&self) -> Result<Vec<AgentGroup>> {
+        let session = self.authenticate().await?;
+        let resp: AgentGroupsResponse = self
+            .get_json(
+                &session,
+                &format!("/scanners/{}/agent-groups", LOCAL_SCANNER),
+            )
+            .await?;
+        Ok(resp.groups.unwrap_or_default())
+    }
+
+    /// Create an empty agent group called `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the group cannot be created.
+    pub async fn create_agent_group(&self, name: &str) -> Result<AgentGroup> {
+        let session = self.authenticate().await?;
+        self.send_json(
+            Method::POST,
+            &session,
+            &format!("/scanners/{}/agent-groups", LOCAL_SCANNER),
+            &serde_json::json!({ "name": name }),
+        )
+        .await
+    }
+
+    /// Delete an agent group. Its agents stay linked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the group cannot be deleted.
+    pub async fn delete_agent_group(&self, group_id: u32) -> Result<()> {
+        let path = format!("/scanners/{}/agent-groups/{}", LOCAL_SCANNER, group_id);
+        self.send_without_body(Method::DELETE, &path).await
+    }
+
+    /// Add an agent to a group.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the change is rejected.
+    pub async fn add_agent_to_group(&self, group_id: u32, agent_id: u32) -> Result<()> {
+        let path = format!(
+            "/scanners/{}/agent-groups/{}/agents/{}",
+            LOCAL_SCANNER, group_id, agent_id
+        );
+        self.send_without_body(Method::PUT, &path).await
+    }
+
+    /// Remove an agent from a group.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the change is rejected.
+    pub async fn remove_agent_from_group(&self, group_id: u32, agent_id: u32) -> Result<()> {
+        let path = format!(
+            "/scanners/{}/agent-groups/{}/agents/{}",
+            LOCAL_SCANNER, group_id, agent_id
+        );
+        self.send_without_body(Method::DELETE, &path).await
+    }
+
+    /// List the agent scans targeting the group with ID `group_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or any request fails.
+    pub async fn agent_scans_for_group(&self, group_id: u32) -> Result<Vec<ScanSummary>> {
+        let session = self.authenticate().await?;
+
+        let mut scans = Vec::new();
+        for scan in self.list_scans().await? {
+            let details: AgentTargetsResponse = self
+                .get_json(&session, &format!("/scans/{}", scan.id))
+                .await?;
+            if details
+                .info
+                .agent_targets
+                .is_some_and(|targets| targets.iter().any(|t| t.id == group_id))
+            {
+                scans.push(scan);
+            }
+        }
+
+        Ok(scans)
+    }
+
+    /// Launch every agent scan targeting the group with ID `group_id`.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::agent_scans_for_group`] and
+    /// [`NessusClient::launch_scans_parallel`].
+    pub async fn launch_agent_scans_for_group(&self, group_id: u32) -> Result<LaunchReport> {
+        let scan_ids = self
+            .agent_scans_for_group(group_id)
+            .await?
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        self.launch_scans_parallel(scan_ids).await
+    }
+
+    async fn send_without_body(&self, method: Method, path: &str) -> Result<()> {
+        let session = self.authenticate().await?;
+        self.send(self.request(method, &session, path)?, path)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,339 @@
+//! Builder for [`NessusClient`].
+//!
+//! [`NessusClient::new`] covers the common case. [`NessusClient::builder`]
+//! collects everything else in one place, including the HTTP settings that
+//! cannot be changed once the client exists:
+//!
+//! - TLS: extra root certificates, or accepting self-signed certificates
+//! - An HTTP(S) proxy
+//! - Request and connect timeouts
+//! - Retries, concurrency, and the rest of [`LaunchOptions`]
+//! - The [`PollStrategy`] and [`Interceptor`]s
+//! - Caching of listings
+//!
+//! [`NessusClientBuilder::build`] checks the combination and fails with
+//! [`NessusError::Config`] instead of producing a client that cannot work.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::{NessusClient, NessusConfig, Result};
+//! use std::time::Duration;
+//!
+//! # fn example() -> Result<()> {
+//! let client = NessusClient::builder(NessusConfig::from_env()?)
+//!     .with_proxy("http://proxy.example.com:3128")
+//!     .with_timeout(Duration::from_secs(60))
+//!     .with_retries(3)
+//!     .with_concurrency(4)
+//!     .build()?;
+//! # let _ = client;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    ApiVersion, Cassette, Interceptor, LaunchOptions, NessusClient, NessusConfig, NessusError,
+    PollStrategy, Result,
+};
+use reqwest::{Client, ClientBuilder, Proxy, Url};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default time allowed for establishing a connection.
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Collects the options of a [`NessusClient`]; see [`NessusClient::builder`].
+pub struct NessusClientBuilder {
+    config: NessusConfig,
+    http_client: Option<Client>,
+    #[cfg(any(feature = "rustls", feature = "native-tls"))]
+    root_certificates: Vec<reqwest::Certificate>,
+    #[cfg(any(feature = "rustls", feature = "native-tls"))]
+    accept_invalid_certs: bool,
+    proxy: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    launch_options: LaunchOptions,
+    poll_strategy: PollStrategy,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    session_token: Option<String>,
+    api_version: Option<ApiVersion>,
+    listing_cache: Option<Duration>,
+    http_trace: bool,
+    cassette: Option<Cassette>,
+}
+
+impl NessusClient {
+    /// Start building a client for `config`.
+    ///
+    /// The defaults match [`NessusClient::new`]: the TLS backend selected by
+    /// the crate features with its default roots, no proxy, no overall
+    /// request timeout, a 30-second connect timeout, default
+    /// [`LaunchOptions`] and [`PollStrategy`], and no interceptors. HTTP
+    /// tracing is on if `NESSUS_HTTP_TRACE=1` is set.
+    pub fn builder(config: NessusConfig) -> NessusClientBuilder {
+        NessusClientBuilder {
+            config,
+            http_client: None,
+            #[cfg(any(feature = "rustls", feature = "native-tls"))]
+            root_certificates: Vec::new(),
+            #[cfg(any(feature = "rustls", feature = "native-tls"))]
+            accept_invalid_certs: false,
+            proxy: None,
+            timeout: None,
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            launch_options: LaunchOptions::default(),
+            poll_strategy: PollStrategy::default(),
+            interceptors: Vec::new(),
+            session_token: None,
+            api_version: None,
+            listing_cache: None,
+            http_trace: NessusConfig::http_trace_from_env(),
+            cassette: None,
+        }
+    }
+}
+
+impl NessusClientBuilder {
+    /// Send requests through `client` instead of building one.
+    ///
+    /// The client is used as is, so this cannot be combined with the TLS,
+    /// proxy, or timeout options.
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Trust `certificate` in addition to the default roots, e.g. the CA
+    /// that signed the Nessus server's certificate.
+    #[cfg(any(feature = "rustls", feature = "native-tls"))]
+    pub fn with_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Accept any server certificate, including Nessus' default
+    /// self-signed one.
+    ///
+    /// This disables protection against man-in-the-middle attacks; prefer
+    /// [`NessusClientBuilder::with_root_certificate`].
+    #[cfg(any(feature = "rustls", feature = "native-tls"))]
+    pub fn with_accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
+        self.accept_invalid_certs = accept_invalid_certs;
+        self
+    }
+
+    /// Send all requests through the proxy at `url`, e.g.
+    /// `http://proxy.example.com:3128`.
+    pub fn with_proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Fail requests that take longer than `timeout` in total.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fail requests whose connection takes longer than `timeout` to
+    /// establish, or wait indefinitely with `None`.
+    pub fn with_connect_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Replace the [`LaunchOptions`] used by batch launches.
+    pub fn with_launch_options(mut self, launch_options: LaunchOptions) -> Self {
+        self.launch_options = launch_options;
+        self
+    }
+
+    /// Set the number of retries after a launch attempt fails.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.launch_options.retries = retries;
+        self
+    }
+
+    /// Set the maximum number of launches in flight at once in a batch.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.launch_options.concurrency = concurrency;
+        self
+    }
+
+    /// Replace the [`PollStrategy`] used by waiting operations.
+    pub fn with_poll_strategy(mut self, poll_strategy: PollStrategy) -> Self {
+        self.poll_strategy = poll_strategy;
+        self
+    }
+
+    /// Add an [`Interceptor`] that sees every request and response.
+    ///
+    /// Interceptors run in the order they were added.
+    pub fn with_interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Use an already-obtained session token instead of logging in; see
+    /// [`NessusClient::with_session`].
+    pub fn with_session(mut self, session_token: impl Into<String>) -> Self {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    /// Use `x_api_token` instead of scraping it from `nessus6.js`.
+    pub fn with_x_api_token(mut self, x_api_token: impl Into<String>) -> Self {
+        self.config.x_api_token = Some(x_api_token.into());
+        self
+    }
+
+    /// Talk to the server as `api_version` instead of detecting it.
+    pub fn with_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = Some(api_version);
+        self
+    }
+
+    /// Cache listings for up to `ttl`; see [`NessusClient::with_listing_cache`].
+    pub fn with_listing_cache(mut self, ttl: Duration) -> Self {
+        self.listing_cache = Some(ttl);
+        self
+    }
+
+    /// Log all HTTP traffic, with secrets redacted; see
+    /// [`NessusClient::with_http_trace`].
+    pub fn with_http_trace(mut self, http_trace: bool) -> Self {
+        self.http_trace = http_trace;
+        self
+    }
+
+    /// Record or replay HTTP traffic with `cassette`; see
+    /// [`NessusClient::with_cassette`].
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Check the options and build the client.
+    ///
+    /// A trailing `/` is removed from [`NessusConfig::host`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if the host is not an `http` or
+    /// `https` URL, the proxy URL is invalid, a timeout or the concurrency
+    /// is zero, or HTTP options are combined with
+    /// [`NessusClientBuilder::with_http_client`]. Without the `rustls` and
+    /// `native-tls` features, an `https` host also needs a custom HTTP
+    /// client. Returns
+    /// [`NessusError::Other`] if the HTTP client cannot be built.
+    pub fn build(mut self) -> Result<NessusClient> {
+        self.config.host = self.config.host.trim_end_matches('/').to_string();
+        let host = Url::parse(&self.config.host).map_err(|e| {
+            NessusError::Config(format!("Invalid Nessus host '{}': {e}", self.config.host))
+        })?;
+        if !matches!(host.scheme(), "http" | "https") {
+            return Err(NessusError::Config(format!(
+                "Nessus host '{}' must be an http or https URL",
+                self.config.host
+            )));
+        }
+        #[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+        if host.scheme() == "https" && self.http_client.is_none() {
+            return Err(NessusError::Config(format!(
+                "Nessus host '{}' needs TLS; enable the `rustls` or `native-tls` feature",
+                self.config.host
+            )));
+        }
+        if self.launch_options.concurrency == 0 {
+            return Err(NessusError::Config("Concurrency must be at least 1".into()));
+        }
+        if self.timeout.is_some_and(|t| t.is_zero())
+            || self.connect_timeout.is_some_and(|t| t.is_zero())
+        {
+            return Err(NessusError::Config(
+                "Timeouts must be greater than zero".into(),
+            ));
+        }
+
+        let http_client = match self.http_client.take() {
+            Some(client) => {
+                if self.has_http_options() {
+                    return Err(NessusError::Config(
+                        "TLS, proxy, and timeout options cannot be combined with a custom HTTP client"
+                            .into(),
+                    ));
+                }
+                client
+            }
+            None => self.build_http_client()?,
+        };
+
+        let mut client = NessusClient::with_http_client(http_client, self.config)
+            .with_launch_options(self.launch_options)
+            .with_poll_strategy(self.poll_strategy)
+            .with_interceptors(self.interceptors)
+            .with_http_trace(self.http_trace);
+        if let Some(session_token) = self.session_token {
+            client = client.with_session(session_token);
+        }
+        if let Some(api_version) = self.api_version {
+            client = client.with_api_version(api_version);
+        }
+        if let Some(cassette) = self.cassette {
+            client = client.with_cassette(cassette);
+        }
+        if let Some(ttl) = self.listing_cache {
+            client = client.with_listing_cache(ttl);
+        }
+        Ok(client)
+    }
+
+    /// Whether any option that configures the HTTP client is set.
+    fn has_http_options(&self) -> bool {
+        #[cfg(any(feature = "rustls", feature = "native-tls"))]
+        if !self.root_certificates.is_empty() || self.accept_invalid_certs {
+            return true;
+        }
+        self.proxy.is_some()
+            || self.timeout.is_some()
+            || self.connect_timeout != Some(DEFAULT_CONNECT_TIMEOUT)
+    }
+
+    /// Build the HTTP client from the TLS, proxy, and timeout options.
+    fn build_http_client(&mut self) -> Result<Client> {
+        let mut builder = ClientBuilder::new();
+        #[cfg(feature = "native-tls")]
+        {
+            builder = builder.use_native_tls();
+        }
+        #[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+        {
+            builder = builder.use_rustls_tls();
+        }
+        #[cfg(any(feature = "rustls", feature = "native-tls"))]
+        {
+            for certificate in self.root_certificates.drain(..) {
+                builder = builder.add_root_certificate(certificate);
+            }
+            builder = builder.danger_accept_invalid_certs(self.accept_invalid_certs);
+        }
+
+        if let Some(url) = &self.proxy {
+            let proxy = Proxy::all(url.as_str())
+                .map_err(|e| NessusError::Config(format!("Invalid proxy URL '{url}': {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        builder
+            .build()
+            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))
+    }
+}
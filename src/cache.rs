@@ -0,0 +1,171 @@
+//! Caching of listing responses.
+//!
+//! Name resolution, tag lookups, and watch loops list the same scans and
+//! folders over and over. With [`NessusClient::with_listing_cache`], the
+//! responses of these listings are kept in memory, shared by the client and
+//! its clones:
+//!
+//! - A response younger than the cache's TTL is reused without a request.
+//! - An older one is revalidated with `If-None-Match` / `If-Modified-Since`
+//!   when the server sent an `ETag` or `Last-Modified` header, so an
+//!   unchanged listing costs a `304 Not Modified` instead of a full body.
+//!
+//! Waiting for scans always revalidates, so it never sees a status older
+//! than its last poll. Any request that changes something on the server,
+//! such as a launch or an update, empties the cache, as does
+//! [`NessusClient::clear_listing_cache`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::{NessusClient, NessusConfig, Result};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<()> {
+//! let client = NessusClient::new(NessusConfig::from_env()?)?
+//!     .with_listing_cache(Duration::from_secs(30));
+//! let scans = client.list_scans().await?;
+//! // Served from memory: no request is sent.
+//! let folders = client.list_folders().await?;
+//! # let _ = (scans, folders);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Session;
+use crate::response;
+use crate::{NessusClient, Result};
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Listing responses kept by a [`NessusClient`], keyed by path.
+#[derive(Debug)]
+pub(crate) struct ListingCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+/// A cached response body and its validators.
+#[derive(Debug, Clone)]
+struct Entry {
+    body: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched: Instant,
+}
+
+impl ListingCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    fn get(&self, path: &str) -> Option<Entry> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(path)
+            .cloned()
+    }
+
+    fn insert(&self, path: &str, entry: Entry) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(path.to_string(), entry);
+    }
+}
+
+impl NessusClient {
+    /// Forget every cached listing, so the next one is fetched in full.
+    ///
+    /// Does nothing unless [`NessusClient::with_listing_cache`] is enabled.
+    pub fn clear_listing_cache(&self) {
+        if let Some(cache) = self.listing_cache() {
+            cache.clear();
+        }
+    }
+
+    /// Perform an authenticated `GET` against `path` through the listing
+    /// cache, if enabled, and decode the JSON body.
+    ///
+    /// With `revalidate`, a cached response is never reused without asking
+    /// the server, however young it is.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::get_json`].
+    pub(crate) async fn get_listing<T: DeserializeOwned>(
+        &self,
+        session: &Session,
+        path: &str,
+        revalidate: bool,
+    ) -> Result<T> {
+        let Some(cache) = self.listing_cache() else {
+            return self.get_json(session, path).await;
+        };
+        let request = format!("GET {path}");
+
+        let cached = cache.get(path);
+        if let Some(entry) = &cached
+            && !revalidate
+            && entry.fetched.elapsed() < cache.ttl
+        {
+            debug!("Using cached {}", request);
+            return response::decode(&request, &entry.body);
+        }
+
+        let mut req = self.request(Method::GET, session, path)?;
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.etag {
+                req = req.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                req = req.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let resp = self.send(req, path).await?;
+
+        let entry = match cached {
+            Some(mut entry) if resp.status() == StatusCode::NOT_MODIFIED => {
+                debug!("{} not modified; using cached response", request);
+                entry.fetched = Instant::now();
+                entry
+            }
+            _ => {
+                let header = |name| {
+                    resp.headers()
+                        .get(name)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string)
+                };
+                let etag = header(ETAG);
+                let last_modified = header(LAST_MODIFIED);
+                Entry {
+                    body: resp.text().await?,
+                    etag,
+                    last_modified,
+                    fetched: Instant::now(),
+                }
+            }
+        };
+
+        let decoded = response::decode(&request, &entry.body)?;
+        cache.insert(path, entry);
+        Ok(decoded)
+    }
+}
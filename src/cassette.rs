@@ -0,0 +1,334 @@
+//! Recording and replaying HTTP interactions.
+//!
+//! Test suites of tools built on this crate can record a session against a
+//! real scanner once and replay it afterwards, without a scanner:
+//!
+//! - [`Cassette::record`] captures every request the client sends and the
+//!   response it gets, and saves them to a JSON file after each one.
+//! - [`Cassette::replay`] loads such a file. Requests are then answered from
+//!   it, and never reach the network.
+//!
+//! Secrets are redacted before anything is written, as in the HTTP trace:
+//! authentication headers are dropped, and passwords, tokens, and keys in
+//! bodies are replaced with `[REDACTED]`. The redacted values are valid
+//! stand-ins, so a replayed login still yields a usable session.
+//!
+//! On replay, a request is matched by method, path, and query, ignoring the
+//! cache-busting `v` parameter of `nessus6.js`, and receives the first
+//! recorded response for it not used yet. Requests may therefore arrive in
+//! a different order than recorded, e.g. from parallel launches, while
+//! repeated polls of the same path get their responses in recorded order.
+//! The server host is not part of the match, so a cassette recorded against
+//! one server replays with any configured host.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::{Cassette, NessusClient, NessusConfig, Result};
+//!
+//! # async fn example() -> Result<()> {
+//! // Once, against a real scanner:
+//! let client = NessusClient::new(NessusConfig::from_env()?)?
+//!     .with_cassette(Cassette::record("tests/cassettes/list.json"));
+//! client.list_scans().await?;
+//!
+//! // In tests, without one:
+//! let config = NessusConfig {
+//!     host: "https://nessus.invalid".into(),
+//!     username: "admin".into(),
+//!     password: "pass".into(),
+//!     x_api_token: None,
+//! };
+//! let client = NessusClient::new(config)?
+//!     .with_cassette(Cassette::replay("tests/cassettes/list.json")?);
+//! let scans = client.list_scans().await?;
+//! # let _ = scans;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::response;
+use crate::trace;
+use crate::{NessusError, Result};
+use reqwest::header::{CONTENT_LENGTH, HeaderName, HeaderValue, TRANSFER_ENCODING};
+use reqwest::{Request, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// A file of recorded HTTP interactions; see the [module docs](self).
+///
+/// Clones share the same recording.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    path: PathBuf,
+    mode: Mode,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Record,
+    Replay,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    file: CassetteFile,
+    /// Per interaction, whether it has been replayed.
+    used: Vec<bool>,
+}
+
+/// The contents of a cassette file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+/// A request as recorded; see [`Cassette::describe`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RecordedRequest {
+    method: String,
+    /// Path and query, e.g. `/scans?folder_id=3`.
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default)]
+    body: Body,
+}
+
+/// A response body: text where possible, raw bytes otherwise, e.g. for PDF
+/// exports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Body {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl Default for Body {
+    fn default() -> Self {
+        Body::Text(String::new())
+    }
+}
+
+impl Cassette {
+    /// Record into the file at `path`, replacing it.
+    ///
+    /// The file is written after every interaction, so nothing is lost if
+    /// the client is dropped or the process exits early.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self::new(path.into(), Mode::Record, CassetteFile::default())
+    }
+
+    /// Replay the interactions recorded in the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be read, or
+    /// [`NessusError::Config`] if it is not a cassette.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let raw = std::fs::read_to_string(&path)?;
+        let file: CassetteFile = serde_json::from_str(&raw).map_err(|e| {
+            NessusError::Config(format!("Invalid cassette {}: {e}", path.display()))
+        })?;
+        Ok(Self::new(path, Mode::Replay, file))
+    }
+
+    fn new(path: PathBuf, mode: Mode, file: CassetteFile) -> Self {
+        let used = vec![false; file.interactions.len()];
+        Self {
+            inner: Arc::new(Inner {
+                path,
+                mode,
+                state: Mutex::new(State { file, used }),
+            }),
+        }
+    }
+
+    /// The cassette file.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Whether this cassette records, rather than replays.
+    pub fn is_recording(&self) -> bool {
+        self.inner.mode == Mode::Record
+    }
+
+    /// The number of interactions recorded, or left to replay.
+    pub fn remaining(&self) -> usize {
+        let state = self.state();
+        match self.inner.mode {
+            Mode::Record => state.file.interactions.len(),
+            Mode::Replay => state.used.iter().filter(|used| !**used).count(),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.inner.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Answer `req` from the cassette.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if no unused interaction matches.
+    pub(crate) fn play(&self, req: &Request) -> Result<Response> {
+        let key = match_key(req.method().as_str(), &path_and_query(req));
+        let mut state = self.state();
+        let State { file, used } = &mut *state;
+
+        let index = file
+            .interactions
+            .iter()
+            .zip(used.iter())
+            .position(|(interaction, used)| {
+                !used && match_key(&interaction.request.method, &interaction.request.path) == key
+            })
+            .ok_or_else(|| {
+                NessusError::Other(format!(
+                    "No recorded response left for {} {} in cassette {}",
+                    req.method(),
+                    path_and_query(req),
+                    self.inner.path.display()
+                ))
+            })?;
+        used[index] = true;
+        debug!(
+            "Replaying {} {} from cassette",
+            req.method(),
+            path_and_query(req)
+        );
+
+        let recorded = &file.interactions[index].response;
+        let mut response = http::Response::builder().status(recorded.status);
+        for (name, value) in &recorded.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                response = response.header(name, value);
+            }
+        }
+        let body = match &recorded.body {
+            Body::Text(text) => text.clone().into_bytes(),
+            Body::Bytes(bytes) => bytes.clone(),
+        };
+        let response = response
+            .body(body)
+            .map_err(|e| NessusError::Other(format!("Invalid response in cassette: {e}")))?;
+        Ok(Response::from(response))
+    }
+
+    /// What [`Cassette::capture`] records of `req`: its method, path and
+    /// query, and redacted body.
+    pub(crate) fn describe(req: &Request) -> RecordedRequest {
+        RecordedRequest {
+            method: req.method().to_string(),
+            path: path_and_query(req),
+            body: req
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(trace::redact),
+        }
+    }
+
+    /// Record `request` and its response `resp`, returning an equivalent
+    /// response with the body still unread.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Http`] if the body cannot be read, or an I/O
+    /// error if the cassette cannot be written.
+    pub(crate) async fn capture(
+        &self,
+        request: RecordedRequest,
+        resp: Response,
+    ) -> Result<Response> {
+        let status = resp.status();
+        let version = resp.version();
+        let headers = resp.headers().clone();
+        let bytes = resp.bytes().await?;
+
+        let recorded = RecordedResponse {
+            status: status.as_u16(),
+            headers: headers
+                .iter()
+                .filter(|(name, _)| !trace::is_secret_header(name.as_str()))
+                // Redaction can change the length of the body.
+                .filter(|(name, _)| *name != CONTENT_LENGTH && *name != TRANSFER_ENCODING)
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: match std::str::from_utf8(&bytes) {
+                Ok(_) => Body::Text(trace::redact(&bytes)),
+                Err(_) => Body::Bytes(bytes.to_vec()),
+            },
+        };
+        {
+            let mut state = self.state();
+            state.file.interactions.push(Interaction {
+                request,
+                response: recorded,
+            });
+            let json = serde_json::to_string_pretty(&state.file)?;
+            if let Some(dir) = self
+                .inner
+                .path
+                .parent()
+                .filter(|d| !d.as_os_str().is_empty())
+            {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&self.inner.path, json)?;
+        }
+
+        response::rebuild(status, version, headers, bytes)
+    }
+}
+
+/// The path and query of `req`.
+fn path_and_query(req: &Request) -> String {
+    let url = req.url();
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+/// What a request is matched by on replay: its method and path and query,
+/// without the cache-busting `v` parameter of `nessus6.js`.
+fn match_key(method: &str, path_and_query: &str) -> String {
+    let (path, query) = path_and_query
+        .split_once('?')
+        .unwrap_or((path_and_query, ""));
+    let query: Vec<&str> = query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .filter(|param| !(path.ends_with("/nessus6.js") && param.starts_with("v=")))
+        .collect();
+    format!("{} {}?{}", method.to_uppercase(), path, query.join("&"))
+}
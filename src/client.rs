@@ -4,8 +4,7 @@
 //!
 //! - Fetching the X-API token from `nessus6.js`
 //! - Logging in to obtain a session token
-//! - Launching scans with retry and backoff
-//! - Parallel execution of multiple scans
+//! - Launching a single scan, or many in parallel, with retry and backoff
 //!
 //! ## Example
 //!
@@ -21,15 +20,27 @@
 //! }
 //! ```
 
-use crate::{NessusConfig, NessusError, Result};
-use futures::stream::{FuturesUnordered, StreamExt};
-use reqwest::{Client, ClientBuilder};
+use crate::cache::ListingCache;
+use crate::cassette::Cassette;
+use crate::correlation;
+use crate::response::{self, UnexpectedResponse};
+use crate::trace;
+use crate::{
+    ApiVersion, Interceptor, LaunchFailure, LaunchOptions, LaunchReport, NessusConfig, NessusError,
+    PollStrategy, Result, ScanLaunchResult, ScanRequest,
+};
+use crate::launch::dedupe_requests;
+use futures::stream::{self, Stream, StreamExt};
+use reqwest::{Client, Method, Request, RequestBuilder, Response, StatusCode};
 use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio_retry::strategy::ExponentialBackoff;
 use tokio_retry::Retry;
-use tracing::{info, error};
+use tracing::{Instrument, debug, debug_span, error, info, info_span, warn};
 
 /// A high-level asynchronous client for interacting with a Nessus server.
 ///
@@ -42,30 +53,467 @@ use tracing::{info, error};
 pub struct NessusClient {
     client: Client,
     config: NessusConfig,
+    poll_strategy: PollStrategy,
+    launch_options: LaunchOptions,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    session_token: Option<String>,
+    /// Session kept alive by a [`crate::SessionKeepAlive`], shared by clones.
+    shared_session: Arc<RwLock<Option<Session>>>,
+    /// API version of the server once detected or pinned, shared by clones.
+    api_version: Arc<RwLock<Option<ApiVersion>>>,
+    /// Cached listing responses, if enabled, shared by clones.
+    listing_cache: Option<Arc<ListingCache>>,
+    /// Whether HTTP traffic is dumped at `TRACE` level; see [`crate::trace`].
+    http_trace: bool,
+    /// Cassette that records or replays HTTP traffic, shared by clones.
+    cassette: Option<Cassette>,
+}
+
+/// Authentication material for a logged-in Nessus session.
+#[derive(Debug, Clone)]
+pub(crate) struct Session {
+    /// Value of the `X-Api-Token` header.
+    pub(crate) x_api_token: String,
+    /// Value of the `X-Cookie` header, e.g. `token=...`.
+    pub(crate) x_cookie: String,
 }
 
 impl NessusClient {
     /// Create a new [`NessusClient`] from the given configuration.
     ///
+    /// The HTTP client uses the TLS backend selected by the crate features:
+    /// `native-tls` (the OS trust store) if enabled, otherwise `rustls`.
+    /// This is [`NessusClient::builder`] with every option at its default.
+    ///
     /// # Errors
     ///
-    /// Returns [`NessusError::Other`] if the underlying HTTP client cannot be built.
+    /// Returns [`NessusError::Config`] if the host is not an `http` or
+    /// `https` URL, or is `https` without a TLS feature, or
+    /// [`NessusError::Other`] if the underlying HTTP client cannot be built.
     pub fn new(config: NessusConfig) -> Result<Self> {
-        let client = ClientBuilder::new()
-            .build()
-            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))?;
+        Self::builder(config).build()
+    }
+
+    /// Create a new [`NessusClient`] that sends its requests through `client`.
+    ///
+    /// Use this to reuse an application's tuned [`reqwest::Client`], e.g. one
+    /// with a proxy, custom TLS roots, or connection pool settings. The
+    /// client is used as is; nothing about it is reconfigured.
+    pub fn with_http_client(client: Client, config: NessusConfig) -> Self {
+        Self {
+            client,
+            config,
+            poll_strategy: PollStrategy::default(),
+            launch_options: LaunchOptions::default(),
+            interceptors: Vec::new(),
+            session_token: None,
+            shared_session: Arc::new(RwLock::new(None)),
+            api_version: Arc::new(RwLock::new(None)),
+            listing_cache: None,
+            http_trace: false,
+            cassette: None,
+        }
+    }
+
+    /// Replace the [`PollStrategy`] used by waiting operations such as
+    /// [`NessusClient::wait_for_scan`].
+    pub fn with_poll_strategy(mut self, poll_strategy: PollStrategy) -> Self {
+        self.poll_strategy = poll_strategy;
+        self
+    }
+
+    /// The [`PollStrategy`] used by waiting operations.
+    pub fn poll_strategy(&self) -> &PollStrategy {
+        &self.poll_strategy
+    }
+
+    /// Replace the [`LaunchOptions`] used by batch launches such as
+    /// [`NessusClient::launch_scans_parallel`].
+    pub fn with_launch_options(mut self, launch_options: LaunchOptions) -> Self {
+        self.launch_options = launch_options;
+        self
+    }
+
+    /// The [`LaunchOptions`] used by batch launches.
+    pub fn launch_options(&self) -> &LaunchOptions {
+        &self.launch_options
+    }
+
+    /// Add an [`Interceptor`] that sees every request and response.
+    ///
+    /// Interceptors run in the order they were added.
+    pub fn with_interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Add `interceptors` after the ones already added.
+    pub(crate) fn with_interceptors(mut self, interceptors: Vec<Arc<dyn Interceptor>>) -> Self {
+        self.interceptors.extend(interceptors);
+        self
+    }
+
+    /// Use an already-obtained session token instead of logging in.
+    ///
+    /// For services that manage Nessus authentication elsewhere. The X-API
+    /// token still comes from [`NessusConfig::x_api_token`] if set, or is
+    /// scraped otherwise; use [`NessusClient::with_x_api_token`] to skip the
+    /// scrape as well. The configured username and password are not used.
+    pub fn with_session(mut self, session_token: impl Into<String>) -> Self {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    /// Use `x_api_token` instead of scraping it from `nessus6.js`.
+    ///
+    /// This overrides [`NessusConfig::x_api_token`].
+    pub fn with_x_api_token(mut self, x_api_token: impl Into<String>) -> Self {
+        self.config.x_api_token = Some(x_api_token.into());
+        self
+    }
+
+    /// Talk to the server as `api_version` instead of detecting its version
+    /// with `GET /server/properties`.
+    pub fn with_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = Arc::new(RwLock::new(Some(api_version)));
+        self
+    }
+
+    /// Cache scan, folder, and tag listings, reusing a response for up to
+    /// `ttl` and revalidating it with the server afterwards.
+    ///
+    /// A zero `ttl` keeps only the revalidation, so every listing still
+    /// costs a request, but an unchanged one is answered with
+    /// `304 Not Modified` where the server supports it. The cache is
+    /// shared by the client's clones.
+    pub fn with_listing_cache(mut self, ttl: Duration) -> Self {
+        self.listing_cache = Some(Arc::new(ListingCache::new(ttl)));
+        self
+    }
+
+    /// Log every request and response, including headers and bodies, at
+    /// `TRACE` level under the `nessus_launcher::http` target.
+    ///
+    /// Passwords, tokens, and cookies are redacted. [`NessusClient::new`]
+    /// and [`NessusClient::builder`] enable this when `NESSUS_HTTP_TRACE=1`
+    /// is set; see [`NessusConfig::http_trace_from_env`].
+    pub fn with_http_trace(mut self, http_trace: bool) -> Self {
+        self.http_trace = http_trace;
+        self
+    }
+
+    /// Record every HTTP interaction into `cassette`, or answer every
+    /// request from it without touching the network, depending on how the
+    /// cassette was opened; see [`Cassette`].
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// The listing cache, if enabled.
+    pub(crate) fn listing_cache(&self) -> Option<&ListingCache> {
+        self.listing_cache.as_deref()
+    }
+
+    /// The server's API version, if already detected or pinned.
+    pub(crate) fn cached_api_version(&self) -> Option<ApiVersion> {
+        *self.api_version.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Remember the server's API version for this client and its clones.
+    pub(crate) fn set_api_version(&self, api_version: ApiVersion) {
+        *self.api_version.write().unwrap_or_else(|e| e.into_inner()) = Some(api_version);
+    }
+
+    /// Return the session kept alive in the background, if any, or else
+    /// authenticate afresh with [`NessusClient::new_session`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the token scrape or the login fails.
+    pub(crate) async fn authenticate(&self) -> Result<Session> {
+        if let Some(session) = self.shared_session() {
+            return Ok(session);
+        }
+        self.new_session().await
+    }
+
+    /// The session kept alive in the background, if any.
+    pub(crate) fn shared_session(&self) -> Option<Session> {
+        self.shared_session
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replace the session shared by this client and its clones.
+    pub(crate) fn set_shared_session(&self, session: Option<Session>) {
+        *self
+            .shared_session
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = session;
+    }
+
+    /// Fetch the X-API token and log in, returning the resulting [`Session`].
+    ///
+    /// A session token given to [`NessusClient::with_session`] is used
+    /// instead of logging in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the token scrape or the login fails.
+    pub(crate) async fn new_session(&self) -> Result<Session> {
+        let x_api_token = self.get_x_api_token().await?;
+        let session_token = match &self.session_token {
+            Some(token) => token.clone(),
+            None => self.login(&x_api_token).await?,
+        };
+
+        Ok(Session {
+            x_api_token,
+            x_cookie: format!("token={}", session_token),
+        })
+    }
+
+    /// Build the headers required for authenticated API calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if a token is not a valid header value.
+    pub(crate) fn auth_headers(session: &Session) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static("Mozilla/5.0"));
+        // Empty for servers that predate the X-API token; see `ApiVersion`.
+        if !session.x_api_token.is_empty() {
+            headers.insert(
+                "X-Api-Token",
+                HeaderValue::from_str(&session.x_api_token)
+                    .map_err(|e| NessusError::Other(format!("Invalid X-Api-Token header: {e}")))?,
+            );
+        }
+        headers.insert(
+            "X-Cookie",
+            HeaderValue::from_str(&session.x_cookie)
+                .map_err(|e| NessusError::Other(format!("Invalid X-Cookie header: {e}")))?,
+        );
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+        Ok(headers)
+    }
+
+    /// Perform an authenticated `GET` against `path` and decode the JSON body.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Http`] if the request fails,
+    /// [`NessusError::Other`] if the response status is not successful,
+    /// or [`NessusError::UnexpectedResponse`] if the body cannot be decoded.
+    pub(crate) async fn get_json<T: DeserializeOwned>(
+        &self,
+        session: &Session,
+        path: &str,
+    ) -> Result<T> {
+        self.get_json_with_query(session, path, &[]).await
+    }
+
+    /// Like [`NessusClient::get_json`], appending `query` to the URL.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::get_json`].
+    pub(crate) async fn get_json_with_query<T: DeserializeOwned>(
+        &self,
+        session: &Session,
+        path: &str,
+        query: &[(String, String)],
+    ) -> Result<T> {
+        let resp = self
+            .send(self.request(Method::GET, session, path)?.query(query), path)
+            .await?;
+
+        response::decode(&format!("GET {path}"), &resp.text().await?)
+    }
+
+    /// Perform an authenticated `GET` against `path` and return the raw body.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::get_json`].
+    pub(crate) async fn get_bytes(&self, session: &Session, path: &str) -> Result<Vec<u8>> {
+        let resp = self
+            .send(self.request(Method::GET, session, path)?, path)
+            .await?;
+
+        Ok(resp.bytes().await?.to_vec())
+    }
+
+    /// Send `body` as JSON with an authenticated request and decode the JSON response.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::get_json`].
+    pub(crate) async fn send_json<B, T>(
+        &self,
+        method: Method,
+        session: &Session,
+        path: &str,
+        body: &B,
+    ) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let resp = self
+            .send(self.request(method.clone(), session, path)?.json(body), path)
+            .await?;
+
+        response::decode(&format!("{method} {path}"), &resp.text().await?)
+    }
+
+    /// Perform an unauthenticated `GET` against `path`.
+    ///
+    /// Unlike [`NessusClient::send`], this does not fail on non-success
+    /// statuses.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Http`] if the request fails.
+    pub(crate) async fn get_public(&self, path: &str) -> Result<Response> {
+        let url = format!("{}{}", self.config.host, path);
+        self.execute(self.client.get(url).build()?).await
+    }
+
+    /// Build an authenticated request for `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if the session tokens are not valid header values.
+    pub(crate) fn request(
+        &self,
+        method: Method,
+        session: &Session,
+        path: &str,
+    ) -> Result<RequestBuilder> {
+        let url = format!("{}{}", self.config.host, path);
+        Ok(self
+            .client
+            .request(method, url)
+            .headers(Self::auth_headers(session)?))
+    }
+
+    /// Send `req`, failing on non-success statuses.
+    ///
+    /// `304 Not Modified`, which only answers the conditional requests of
+    /// [`NessusClient::get_listing`], is passed through.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Http`] if the request fails,
+    /// [`NessusError::Auth`] if the session is rejected, or
+    /// [`NessusError::Other`] for any other unsuccessful status.
+    pub(crate) async fn send(&self, req: RequestBuilder, path: &str) -> Result<Response> {
+        let mut req = req.build()?;
+        let method = req.method().clone();
+        let request_id = correlation::request_id(&mut req);
+        let resp = self.execute(req).await?;
+
+        if !resp.status().is_success() && resp.status() != StatusCode::NOT_MODIFIED {
+            return Err(status_error(
+                resp.status(),
+                format!(
+                    "{} {} failed with status {} (request {})",
+                    method,
+                    path,
+                    resp.status(),
+                    request_id
+                ),
+            ));
+        }
 
-        Ok(Self { client, config })
+        Ok(resp)
+    }
+
+    /// Execute `req`, running it and its response through the interceptors.
+    ///
+    /// The request gets a correlation ID in its `X-Request-Id` header, if it
+    /// has none yet, and runs in a `tracing` span recording that ID. An
+    /// authenticated request other than `GET` may change what the server
+    /// lists, so it empties the listing cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Http`] if the request fails, or the error of
+    /// the first interceptor that rejects the request or response.
+    async fn execute(&self, mut req: Request) -> Result<Response> {
+        let request_id = correlation::request_id(&mut req);
+        let span = debug_span!(
+            "nessus_request",
+            request_id = %request_id,
+            method = %req.method(),
+            path = req.url().path(),
+        );
+
+        async move {
+            for interceptor in &self.interceptors {
+                interceptor.on_request(&mut req).await?;
+            }
+            if self.http_trace {
+                trace::request(&req);
+            }
+
+            let changes_listings =
+                req.method() != Method::GET && req.headers().contains_key("X-Cookie");
+            let resp = match &self.cassette {
+                Some(cassette) if !cassette.is_recording() => cassette.play(&req),
+                Some(cassette) => {
+                    let recorded = Cassette::describe(&req);
+                    match self.client.execute(req).await {
+                        Ok(resp) => cassette.capture(recorded, resp).await,
+                        Err(e) => Err(e.into()),
+                    }
+                }
+                None => self.client.execute(req).await.map_err(Into::into),
+            };
+            if changes_listings {
+                self.clear_listing_cache();
+            }
+            let mut resp = resp?;
+            debug!("Response status {}", resp.status());
+            if self.http_trace {
+                resp = trace::response(resp).await?;
+            }
+
+            for interceptor in &self.interceptors {
+                interceptor.on_response(&resp).await?;
+            }
+
+            Ok(resp)
+        }
+        .instrument(span)
+        .await
     }
 
     /// Fetch the X-API token by requesting `nessus6.js` from the Nessus server.
     ///
-    /// This method parses the JavaScript file to extract the `getApiToken` value.
+    /// The token configured in [`NessusConfig::x_api_token`] is used instead
+    /// if set. Otherwise the script is parsed with [`extract_x_api_token`],
+    /// unless the server's [`ApiVersion`] predates the token, in which case
+    /// the token is empty.
     ///
     /// # Errors
     ///
-    /// Returns [`NessusError::Other`] if the token cannot be found or parsed.
+    /// Returns [`NessusError::UnsupportedVersion`] if the server is too old,
+    /// or [`NessusError::Auth`] if the token cannot be found.
+    ///
+    /// [`extract_x_api_token`]: crate::extract_x_api_token
     async fn get_x_api_token(&self) -> Result<String> {
+        if let Some(token) = &self.config.x_api_token {
+            return Ok(token.clone());
+        }
+        if !self.api_version().await?.requires_x_api_token() {
+            return Ok(String::new());
+        }
+
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map_err(|e| NessusError::Other(format!("Time error: {e}")))?
@@ -74,22 +522,20 @@ impl NessusClient {
 
         let url = format!("{}/nessus6.js?v={}", self.config.host, timestamp);
 
-        let body = self.client.get(&url).send().await?.text().await?;
-
-        let parts = body.split(":\"").collect::<Vec<&str>>();
-        let token_part = parts
-            .iter()
-            .find(|s| s.contains("getApiToken"))
-            .ok_or_else(|| NessusError::Other("getApiToken not found in nessus6.js".into()))?;
-
-        let vec3 = token_part.split('"').collect::<Vec<&str>>();
-        if vec3.len() < 3 {
-            return Err(NessusError::Other(
-                "Unexpected format while parsing X-API token".into(),
-            ));
-        }
+        let body = self
+            .execute(self.client.get(&url).build()?)
+            .await?
+            .text()
+            .await?;
 
-        Ok(vec3[2].to_string())
+        crate::extract_x_api_token(&body).ok_or_else(|| {
+            let hint = response::hint(&body)
+                .map(|hint| format!(" ({hint})"))
+                .unwrap_or_default();
+            NessusError::Auth(format!(
+                "X-API token not found in nessus6.js{hint}; set NESSUS_X_API_TOKEN to pin it"
+            ))
+        })
     }
 
     /// Log in to Nessus using the configured username and password.
@@ -98,8 +544,8 @@ impl NessusClient {
     ///
     /// # Errors
     ///
-    /// Returns [`NessusError::Json`] if the response cannot be parsed,
-    /// or [`NessusError::Other`] if the token field is missing.
+    /// Returns [`NessusError::UnexpectedResponse`] if the response is not
+    /// JSON, or [`NessusError::Auth`] if it holds no session token.
     async fn login(&self, x_api_token: &str) -> Result<String> {
         let url = format!("{}/session", self.config.host);
 
@@ -110,30 +556,60 @@ impl NessusClient {
 
         let mut headers = HeaderMap::new();
         headers.insert(USER_AGENT, HeaderValue::from_static("Mozilla/5.0"));
-        headers.insert("X-Api-Token", HeaderValue::from_str(x_api_token).map_err(|e| {
-            NessusError::Other(format!("Invalid X-Api-Token header value: {e}"))
-        })?);
+        if !x_api_token.is_empty() {
+            headers.insert("X-Api-Token", HeaderValue::from_str(x_api_token).map_err(|e| {
+                NessusError::Other(format!("Invalid X-Api-Token header value: {e}"))
+            })?);
+        }
         headers.insert("content-type", HeaderValue::from_static("application/json"));
 
         let resp_text = self
-            .client
-            .post(url)
-            .headers(headers)
-            .json(&body)
-            .send()
+            .execute(self.client.post(url).headers(headers).json(&body).build()?)
             .await?
             .text()
             .await?;
 
-        let v: Value = serde_json::from_str(&resp_text)?;
+        let v: Value = response::decode("POST /session", &resp_text)?;
         let token = v
             .get("token")
             .and_then(|t| t.as_str())
-            .ok_or_else(|| NessusError::Other("Missing 'token' field in session response".into()))?;
+            .ok_or_else(|| match v.get("error").and_then(|e| e.as_str()) {
+                Some(reason) => NessusError::Auth(format!("Login failed: {reason}")),
+                None => NessusError::Auth(format!(
+                    "Login failed: {}",
+                    UnexpectedResponse::new("POST /session", "no session token", &resp_text)
+                )),
+            })?;
 
         Ok(token.to_string())
     }
 
+    /// Launch a single scan, retrying with exponential backoff.
+    ///
+    /// This logs in, then launches the scan as configured by `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails, or the last launch error
+    /// once all retries are used up.
+    pub async fn launch_scan(&self, scan_id: u32, options: &LaunchOptions) -> Result<()> {
+        let session = self.authenticate().await?;
+        self.launch_scan_with_retry(scan_id, &session, options).await
+    }
+
+    /// Launch a scan with an existing session, retrying as configured by `options`.
+    async fn launch_scan_with_retry(
+        &self,
+        scan_id: u32,
+        session: &Session,
+        options: &LaunchOptions,
+    ) -> Result<()> {
+        Retry::spawn(options.retry_strategy(), || async {
+            self.launch_scan_once(scan_id, session, options).await
+        })
+        .await
+    }
+
     /// Launch a single Nessus scan once, without retry.
     ///
     /// # Errors
@@ -143,33 +619,30 @@ impl NessusClient {
     async fn launch_scan_once(
         &self,
         scan_id: u32,
-        x_api_token: &str,
-        x_cookie: &str,
+        session: &Session,
+        options: &LaunchOptions,
     ) -> Result<()> {
         let url = format!("{}/scans/{}/launch", self.config.host, scan_id);
+        let headers = Self::auth_headers(session)?;
 
-        let mut headers = HeaderMap::new();
-        headers.insert(USER_AGENT, HeaderValue::from_static("Mozilla/5.0"));
-        headers.insert(
-            "X-Api-Token",
-            HeaderValue::from_str(x_api_token)
-                .map_err(|e| NessusError::Other(format!("Invalid X-Api-Token header: {e}")))?,
-        );
-        headers.insert(
-            "X-Cookie",
-            HeaderValue::from_str(x_cookie)
-                .map_err(|e| NessusError::Other(format!("Invalid X-Cookie header: {e}")))?,
-        );
-        headers.insert("content-type", HeaderValue::from_static("application/json"));
-
-        let resp = self.client.post(url).headers(headers).send().await?;
+        let mut req = self.client.post(url).headers(headers);
+        if !options.alt_targets.is_empty() {
+            req = req.json(&serde_json::json!({ "alt_targets": options.alt_targets }));
+        }
+        let mut req = req.build()?;
+        let request_id = correlation::request_id(&mut req);
+        let resp = self.execute(req).await?;
 
         if !resp.status().is_success() {
-            return Err(NessusError::Other(format!(
-                "Scan {} launch failed with status {}",
-                scan_id,
-                resp.status()
-            )));
+            return Err(status_error(
+                resp.status(),
+                format!(
+                    "Scan {} launch failed with status {} (request {})",
+                    scan_id,
+                    resp.status(),
+                    request_id
+                ),
+            ));
         }
 
         Ok(())
@@ -177,54 +650,188 @@ impl NessusClient {
 
     /// Launch multiple scans in parallel with retry and exponential backoff.
     ///
-    /// Each scan is launched in its own task, and each task uses a retry
-    /// strategy with exponential backoff.
+    /// Up to [`LaunchOptions::concurrency`] launches run at once, each
+    /// retried according to the client's [`LaunchOptions`].
     ///
     /// # Errors
     ///
     /// Returns an error if obtaining the X-API token or session token fails.
-    /// Individual scan failures are logged but do not abort the entire operation.
-    pub async fn launch_scans_parallel(&self, scan_ids: Vec<u32>) -> Result<()> {
-        if scan_ids.is_empty() {
+    /// Individual scan failures are logged and recorded in the returned
+    /// [`LaunchReport`] but do not abort the entire operation, unless
+    /// [`LaunchOptions::fail_fast`] is set. Once the batch
+    /// is done, [`NessusError::Launch`] is returned instead of the report if
+    /// the client's [`FailurePolicy`] counts the failures as an error.
+    ///
+    /// [`FailurePolicy`]: crate::FailurePolicy
+    pub async fn launch_scans_parallel(&self, scan_ids: Vec<u32>) -> Result<LaunchReport> {
+        self.launch_requests(scan_ids.into_iter().map(ScanRequest::from).collect())
+            .await
+    }
+
+    /// Launch multiple scans in parallel, each with its own overrides of
+    /// the client's [`LaunchOptions`].
+    ///
+    /// This is [`NessusClient::launch_scans_parallel`] for batches whose
+    /// scans need different targets or retries. Only the launch is done
+    /// here; see [`NessusClient::finish_requests`] for waiting and exports.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::launch_scans_parallel`].
+    pub async fn launch_requests(&self, requests: Vec<ScanRequest>) -> Result<LaunchReport> {
+        if requests.is_empty() {
             info!("No scan IDs provided; nothing to launch.");
-            return Ok(());
+            return Ok(LaunchReport::default());
         }
 
-        let x_api_token = self.get_x_api_token().await?;
-        let session_token = self.login(&x_api_token).await?;
-        let x_cookie = format!("token={}", session_token);
+        let requests = dedupe_requests(requests);
+        let scan_ids: Vec<u32> = requests.iter().map(|r| r.scan_id).collect();
+        let fail_fast = self.launch_options.fail_fast;
+        let mut report = LaunchReport::default();
+        let mut outcomes = self.launch_requests_stream(requests).await?;
+        while let Some(outcome) = outcomes.next().await {
+            let failed = outcome.is_err();
+            report.push(outcome);
+            if failed && fail_fast {
+                break;
+            }
+        }
+        // Dropping the stream cancels whatever is still queued or in flight.
+        drop(outcomes);
 
-        let mut tasks = FuturesUnordered::new();
+        let attempted: HashSet<u32> = report
+            .succeeded
+            .iter()
+            .copied()
+            .chain(report.failed.iter().map(|f| f.scan_id))
+            .collect();
+        report.skipped = scan_ids
+            .into_iter()
+            .filter(|id| !attempted.contains(id))
+            .collect();
+        if !report.skipped.is_empty() {
+            warn!(
+                "Stopped after a failed launch; skipped {} scan(s): {:?}",
+                report.skipped.len(),
+                report.skipped
+            );
+        }
 
-        for scan_id in scan_ids {
-            let client = self.clone();
-            let x_api_token = x_api_token.clone();
-            let x_cookie = x_cookie.clone();
+        if self.launch_options.failure_policy.is_failure(&report) {
+            return Err(NessusError::Launch(report));
+        }
+        Ok(report)
+    }
 
-            tasks.push(tokio::spawn(async move {
-                let strategy = ExponentialBackoff::from_millis(500)
-                    .max_delay(Duration::from_secs(10))
-                    .take(5);
+    /// Launch multiple scans in parallel, yielding each outcome as it finishes.
+    ///
+    /// This is [`NessusClient::launch_scans_parallel`] for callers that want
+    /// to react scan by scan instead of waiting for the whole batch. Launches
+    /// are driven by polling the stream, so dropping it cancels the launches
+    /// still in flight or not yet started.
+    ///
+    /// Repeated scan IDs are launched once; see [`dedupe_scan_ids`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if obtaining the X-API token or session token fails,
+    /// or [`NessusError::Config`] if [`LaunchOptions::verify_scan_ids`] is
+    /// set and a scan ID does not exist on the server.
+    /// Individual scan failures are yielded by the stream.
+    ///
+    /// [`dedupe_scan_ids`]: crate::dedupe_scan_ids
+    pub async fn launch_scans_stream(
+        &self,
+        scan_ids: Vec<u32>,
+    ) -> Result<impl Stream<Item = ScanLaunchResult> + Send + 'static> {
+        self.launch_requests_stream(scan_ids.into_iter().map(ScanRequest::from).collect())
+            .await
+    }
 
-                let result = Retry::spawn(strategy, || async {
-                    client.launch_scan_once(scan_id, &x_api_token, &x_cookie).await
-                })
-                .await;
+    /// Like [`NessusClient::launch_scans_stream`], launching each scan with
+    /// its request's overrides of the client's [`LaunchOptions`].
+    ///
+    /// Repeated scan IDs are launched once, with the first request for them.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::launch_scans_stream`].
+    pub async fn launch_requests_stream(
+        &self,
+        requests: Vec<ScanRequest>,
+    ) -> Result<impl Stream<Item = ScanLaunchResult> + Send + 'static> {
+        let requests = dedupe_requests(requests);
+        let batch_id = correlation::new_id();
+        // One session for the whole batch, including the pre-check.
+        let session = Arc::new(
+            self.authenticate()
+                .instrument(info_span!("launch_batch", batch_id = %batch_id))
+                .await?,
+        );
+        if self.launch_options.verify_scan_ids {
+            let scan_ids: Vec<u32> = requests.iter().map(|r| r.scan_id).collect();
+            self.verify_scan_ids(&session, &scan_ids).await?;
+        }
 
-                match result {
-                    Ok(_) => info!("Scan {} launched successfully", scan_id),
-                    Err(e) => error!("Scan {} failed after retries: {}", scan_id, e),
+        // One shared handle for the whole batch rather than a clone per scan.
+        let client = Arc::new(self.clone());
+        let concurrency = self.launch_options.concurrency.max(1);
+        info!("Launching {} scan(s) in batch {}", requests.len(), batch_id);
+
+        Ok(stream::iter(requests)
+            .map(move |request| {
+                let client = Arc::clone(&client);
+                let session = Arc::clone(&session);
+                let batch_id = batch_id.clone();
+                let scan_id = request.scan_id;
+                let span = info_span!("launch_scan", batch_id = %batch_id, scan_id);
+                async move {
+                    let options = request.launch_options(&client.launch_options);
+                    let outcome = client
+                        .launch_scan_with_retry(scan_id, &session, &options)
+                        .await;
+                    match outcome {
+                        Ok(()) => {
+                            info!("Scan {} launched successfully", scan_id);
+                            Ok(scan_id)
+                        }
+                        Err(e) => {
+                            error!("Scan {} failed after retries: {}", scan_id, e);
+                            Err(LaunchFailure {
+                                scan_id,
+                                error: format!("{e} [batch {batch_id}]"),
+                            })
+                        }
+                    }
                 }
-            }));
-        }
+                .instrument(span)
+            })
+            .buffer_unordered(concurrency))
+    }
 
-        while let Some(join_result) = tasks.next().await {
-            if let Err(e) = join_result {
-                error!("Task join error: {}", e);
-            }
+    /// Fail with [`NessusError::Config`] naming every ID in `scan_ids` that
+    /// is not in [`NessusClient::list_scans`].
+    async fn verify_scan_ids(&self, session: &Session, scan_ids: &[u32]) -> Result<()> {
+        let known: HashSet<u32> = self
+            .list_scans_with(session)
+            .await?
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        let unknown: Vec<String> = scan_ids
+            .iter()
+            .filter(|id| !known.contains(id))
+            .map(u32::to_string)
+            .collect();
+
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(NessusError::Config(format!(
+                "Unknown scan ID(s): {}",
+                unknown.join(", ")
+            )))
         }
-
-        Ok(())
     }
 }
 
@@ -233,7 +840,25 @@ impl Clone for NessusClient {
         Self {
             client: self.client.clone(),
             config: self.config.clone(),
+            poll_strategy: self.poll_strategy.clone(),
+            launch_options: self.launch_options.clone(),
+            interceptors: self.interceptors.clone(),
+            session_token: self.session_token.clone(),
+            shared_session: Arc::clone(&self.shared_session),
+            api_version: Arc::clone(&self.api_version),
+            listing_cache: self.listing_cache.clone(),
+            http_trace: self.http_trace,
+            cassette: self.cassette.clone(),
         }
     }
 }
 
+/// The error for a response with a non-success `status`: [`NessusError::Auth`]
+/// for `401 Unauthorized`, [`NessusError::Other`] otherwise.
+fn status_error(status: StatusCode, message: String) -> NessusError {
+    if status == StatusCode::UNAUTHORIZED {
+        NessusError::Auth(message)
+    } else {
+        NessusError::Other(message)
+    }
+}
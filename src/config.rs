@@ -9,6 +9,10 @@
 //! - `NESSUS_USERNAME` — Nessus username
 //! - `NESSUS_PASSWORD` — Nessus password
 //! - `DEFAULT_SCAN_IDS` — Comma-separated list of scan IDs (e.g. `5,8,11`)
+//! - `NESSUS_X_API_TOKEN` — Optional X-API token to use instead of scraping it
+//!   from `nessus6.js`
+//! - `NESSUS_HTTP_TRACE` — Set to `1` to log all HTTP traffic, with secrets
+//!   redacted, at `TRACE` level
 
 use crate::{NessusError, Result};
 use dotenvy::dotenv;
@@ -23,6 +27,10 @@ pub struct NessusConfig {
     pub username: String,
     /// Nessus password.
     pub password: String,
+    /// X-API token to use instead of scraping it from `nessus6.js`.
+    ///
+    /// Pin this when the scrape fails on an unusual Nessus version.
+    pub x_api_token: Option<String>,
 }
 
 impl NessusConfig {
@@ -42,11 +50,15 @@ impl NessusConfig {
             .map_err(|_| NessusError::Config("Missing NESSUS_USERNAME".into()))?;
         let password = env::var("NESSUS_PASSWORD")
             .map_err(|_| NessusError::Config("Missing NESSUS_PASSWORD".into()))?;
+        let x_api_token = env::var("NESSUS_X_API_TOKEN")
+            .ok()
+            .filter(|t| !t.trim().is_empty());
 
         Ok(Self {
             host,
             username,
             password,
+            x_api_token,
         })
     }
 
@@ -60,14 +72,50 @@ impl NessusConfig {
     ///
     /// # Returns
     ///
-    /// A vector of scan IDs. Invalid entries are ignored.
-    pub fn default_scan_ids_from_env() -> Vec<u32> {
+    /// A vector of scan IDs, empty if the variable is not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if an entry is not a valid scan ID.
+    pub fn default_scan_ids_from_env() -> Result<Vec<u32>> {
         dotenv().ok();
 
         let ids = env::var("DEFAULT_SCAN_IDS").unwrap_or_default();
-        ids.split(',')
-            .filter_map(|s| s.trim().parse::<u32>().ok())
+        Self::parse_scan_ids(&ids).map_err(|e| match e {
+            NessusError::Config(msg) => NessusError::Config(format!("{msg} in DEFAULT_SCAN_IDS")),
+            other => other,
+        })
+    }
+
+    /// Whether `NESSUS_HTTP_TRACE` asks for HTTP traffic to be traced,
+    /// i.e. is set to `1`, `true`, `yes`, or `on`.
+    pub fn http_trace_from_env() -> bool {
+        dotenv().ok();
+
+        env::var("NESSUS_HTTP_TRACE").is_ok_and(|v| {
+            matches!(
+                v.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+    }
+
+    /// Parse a comma-separated list of scan IDs, e.g. `5, 8,11`.
+    ///
+    /// Blank entries are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] naming the first entry that is not a
+    /// valid scan ID.
+    pub fn parse_scan_ids(list: &str) -> Result<Vec<u32>> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                s.parse::<u32>()
+                    .map_err(|_| NessusError::Config(format!("Invalid scan ID '{s}'")))
+            })
             .collect()
     }
 }
-
@@ -0,0 +1,39 @@
+//! Correlation IDs for matching client activity to Nessus server logs.
+//!
+//! Every request sent to the Nessus server carries a unique ID in the
+//! [`REQUEST_ID_HEADER`] header. The ID is recorded on the request's
+//! `tracing` span and included in the error when the server rejects the
+//! request. Batch launches get a batch ID as well, recorded on the span of
+//! each launch and in each [`LaunchFailure`].
+//!
+//! [`LaunchFailure`]: crate::LaunchFailure
+
+use reqwest::Request;
+use reqwest::header::HeaderValue;
+use uuid::Uuid;
+
+/// Header carrying the ID of each request.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// A fresh random correlation ID.
+pub(crate) fn new_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// The ID of `req`, assigning a fresh one if it has none yet.
+pub(crate) fn request_id(req: &mut Request) -> String {
+    if let Some(id) = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        return id.to_string();
+    }
+
+    let id = new_id();
+    req.headers_mut().insert(
+        REQUEST_ID_HEADER,
+        HeaderValue::from_str(&id).expect("a UUID is a valid header value"),
+    );
+    id
+}
@@ -0,0 +1,262 @@
+//! EPSS (Exploit Prediction Scoring System) enrichment.
+//!
+//! Scores are fetched in bulk from the FIRST.org EPSS API and cached in
+//! memory, and optionally in a JSON file so repeated runs do not re-query
+//! CVEs whose scores are still fresh. CVEs EPSS has no score for are
+//! cached, and expire, the same way.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::enrich::epss::EpssClient;
+//! use nessus_launcher::{NessusClient, NessusConfig, ResultFilter, Result};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let client = NessusClient::new(NessusConfig::from_env()?)?;
+//!     let mut findings = client.findings(5, &ResultFilter::new()).await?;
+//!
+//!     let mut epss = EpssClient::new()?.with_cache_file(".cache/epss.json")?;
+//!     epss.enrich(&mut findings).await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{Finding, NessusError, Result};
+use reqwest::Client;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+/// Default EPSS API endpoint.
+pub const DEFAULT_EPSS_URL: &str = "https://api.first.org/data/v1/epss";
+
+/// Maximum number of CVEs sent in a single bulk request.
+const BATCH_SIZE: usize = 100;
+
+/// The EPSS score of a single CVE.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpssScore {
+    /// Probability (0.0-1.0) of exploitation activity in the next 30 days.
+    #[serde(deserialize_with = "de_f64_or_string")]
+    pub epss: f64,
+    /// Percentile of this score among all scored CVEs (0.0-1.0).
+    #[serde(deserialize_with = "de_f64_or_string")]
+    pub percentile: f64,
+    /// Date the score was published, e.g. `2024-05-01`.
+    #[serde(default)]
+    pub date: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// `None` if EPSS had no score for the CVE.
+    #[serde(default)]
+    score: Option<EpssScore>,
+    fetched_at: u64,
+}
+
+#[derive(Deserialize)]
+struct EpssResponse {
+    #[serde(default)]
+    data: Vec<EpssRecord>,
+}
+
+#[derive(Deserialize)]
+struct EpssRecord {
+    cve: String,
+    #[serde(flatten)]
+    score: EpssScore,
+}
+
+fn de_f64_or_string<'de, D: Deserializer<'de>>(d: D) -> std::result::Result<f64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Num(f64),
+        Str(String),
+    }
+
+    match Raw::deserialize(d)? {
+        Raw::Num(n) => Ok(n),
+        Raw::Str(s) => s.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Looks up and caches EPSS scores.
+pub struct EpssClient {
+    http: Client,
+    base_url: String,
+    cache: HashMap<String, CacheEntry>,
+    cache_file: Option<PathBuf>,
+    max_age: Duration,
+}
+
+impl EpssClient {
+    /// Create a client using [`DEFAULT_EPSS_URL`] and an in-memory cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if the underlying HTTP client cannot be built.
+    pub fn new() -> Result<Self> {
+        let http = Client::builder()
+            .build()
+            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self {
+            http,
+            base_url: DEFAULT_EPSS_URL.to_string(),
+            cache: HashMap::new(),
+            cache_file: None,
+            max_age: Duration::from_secs(24 * 60 * 60),
+        })
+    }
+
+    /// Use a different EPSS API endpoint, e.g. an internal mirror.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Consider cached scores stale after `max_age` (default: one day).
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Persist the cache in a JSON file, loading any existing entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Io`] or [`NessusError::Json`] if an existing
+    /// cache file cannot be read.
+    pub fn with_cache_file(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if path.exists() {
+            let contents = std::fs::read_to_string(&path)?;
+            self.cache = serde_json::from_str(&contents)?;
+        }
+        self.cache_file = Some(path);
+        Ok(self)
+    }
+
+    /// Look up scores for `cves`, querying the API only for missing or stale entries.
+    ///
+    /// CVEs unknown to EPSS are absent from the returned map. They are cached
+    /// like scores, so they are not queried again until they go stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an API request fails or the cache file cannot be
+    /// written. Scores fetched before a failed request are still cached.
+    pub async fn scores(&mut self, cves: &[String]) -> Result<HashMap<String, EpssScore>> {
+        let now = now_secs();
+        let max_age = self.max_age.as_secs();
+
+        let missing: BTreeSet<&String> = cves
+            .iter()
+            .filter(|cve| match self.cache.get(*cve) {
+                Some(entry) => now.saturating_sub(entry.fetched_at) > max_age,
+                None => true,
+            })
+            .collect();
+
+        if !missing.is_empty() {
+            let missing: Vec<&String> = missing.into_iter().collect();
+            let result = async {
+                for chunk in missing.chunks(BATCH_SIZE) {
+                    let joined = chunk
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    debug!("Fetching EPSS scores for {} CVEs", chunk.len());
+
+                    let resp: EpssResponse = self
+                        .http
+                        .get(&self.base_url)
+                        .query(&[("cve", joined)])
+                        .send()
+                        .await?
+                        .error_for_status()?
+                        .json()
+                        .await?;
+
+                    let mut scores: HashMap<String, EpssScore> = resp
+                        .data
+                        .into_iter()
+                        .map(|record| (record.cve, record.score))
+                        .collect();
+                    for cve in chunk {
+                        self.cache.insert(
+                            cve.to_string(),
+                            CacheEntry {
+                                score: scores.remove(*cve),
+                                fetched_at: now,
+                            },
+                        );
+                    }
+                }
+                Ok::<_, NessusError>(())
+            }
+            .await;
+
+            // Keep whatever was fetched before a failure for the next run.
+            self.save()?;
+            result?;
+        }
+
+        Ok(cves
+            .iter()
+            .filter_map(|cve| {
+                let score = self.cache.get(cve)?.score.clone()?;
+                Some((cve.clone(), score))
+            })
+            .collect())
+    }
+
+    /// Set [`Finding::epss`] to the highest score among each finding's CVEs.
+    ///
+    /// # Errors
+    ///
+    /// See [`EpssClient::scores`].
+    pub async fn enrich(&mut self, findings: &mut [Finding]) -> Result<()> {
+        let cves: Vec<String> = findings
+            .iter()
+            .flat_map(|f| f.cves.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let scores = self.scores(&cves).await?;
+
+        for finding in findings.iter_mut() {
+            finding.epss = finding
+                .cves
+                .iter()
+                .filter_map(|cve| scores.get(cve).map(|s| s.epss))
+                .reduce(f64::max);
+        }
+
+        Ok(())
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(path) = &self.cache_file {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, serde_json::to_string(&self.cache)?)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+//! CISA Known Exploited Vulnerabilities (KEV) enrichment.
+//!
+//! The KEV catalog is downloaded as a single JSON document and optionally
+//! cached on disk, so CI runs only re-download it once the cached copy is
+//! older than the configured maximum age.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::enrich::kev::KevClient;
+//! use nessus_launcher::{GatePolicy, NessusClient, NessusConfig, ResultFilter, Result};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let client = NessusClient::new(NessusConfig::from_env()?)?;
+//!     let mut findings = client.findings(5, &ResultFilter::new()).await?;
+//!
+//!     let catalog = KevClient::new()?.with_cache_file(".cache/kev.json").catalog().await?;
+//!     catalog.enrich(&mut findings);
+//!
+//!     let report = GatePolicy::new().fail_on_kev(true).evaluate(&findings);
+//!     assert!(report.passed());
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{Finding, NessusError, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::debug;
+
+/// Default location of the KEV catalog feed.
+pub const DEFAULT_KEV_URL: &str =
+    "https://www.cisa.gov/sites/default/files/feeds/known_exploited_vulnerabilities.json";
+
+/// A single entry of the KEV catalog.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KevEntry {
+    /// CVE identifier, e.g. `CVE-2021-44228`.
+    #[serde(rename = "cveID")]
+    pub cve_id: String,
+    /// Vendor or project of the affected product.
+    #[serde(default)]
+    pub vendor_project: String,
+    /// Affected product.
+    #[serde(default)]
+    pub product: String,
+    /// Short name of the vulnerability.
+    #[serde(default)]
+    pub vulnerability_name: String,
+    /// Date the CVE was added to the catalog.
+    #[serde(default)]
+    pub date_added: String,
+    /// Remediation due date for US federal agencies.
+    #[serde(default)]
+    pub due_date: String,
+    /// `Known` if the CVE has been used in ransomware campaigns.
+    #[serde(default)]
+    pub known_ransomware_campaign_use: String,
+}
+
+#[derive(Deserialize)]
+struct CatalogDocument {
+    #[serde(default)]
+    vulnerabilities: Vec<KevEntry>,
+}
+
+/// The KEV catalog, indexed by CVE.
+#[derive(Debug, Clone, Default)]
+pub struct KevCatalog {
+    entries: HashMap<String, KevEntry>,
+}
+
+impl KevCatalog {
+    /// Parse a catalog from the JSON feed format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Json`] if `json` is not a valid catalog.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let doc: CatalogDocument = serde_json::from_str(json)?;
+        Ok(Self {
+            entries: doc
+                .vulnerabilities
+                .into_iter()
+                .map(|e| (e.cve_id.to_ascii_uppercase(), e))
+                .collect(),
+        })
+    }
+
+    /// Number of CVEs in the catalog.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the catalog is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The catalog entry for `cve`, if it is known to be exploited.
+    pub fn get(&self, cve: &str) -> Option<&KevEntry> {
+        self.entries.get(&cve.to_ascii_uppercase())
+    }
+
+    /// Whether `cve` is known to be exploited.
+    pub fn contains(&self, cve: &str) -> bool {
+        self.get(cve).is_some()
+    }
+
+    /// Set [`Finding::kev`] on every finding referencing a cataloged CVE.
+    pub fn enrich(&self, findings: &mut [Finding]) {
+        for finding in findings.iter_mut() {
+            finding.kev = finding.cves.iter().any(|cve| self.contains(cve));
+        }
+    }
+}
+
+/// Downloads the KEV catalog, with optional on-disk caching.
+pub struct KevClient {
+    http: Client,
+    url: String,
+    cache_file: Option<PathBuf>,
+    max_age: Duration,
+}
+
+impl KevClient {
+    /// Create a client using [`DEFAULT_KEV_URL`] without on-disk caching.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if the underlying HTTP client cannot be built.
+    pub fn new() -> Result<Self> {
+        let http = Client::builder()
+            .build()
+            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self {
+            http,
+            url: DEFAULT_KEV_URL.to_string(),
+            cache_file: None,
+            max_age: Duration::from_secs(24 * 60 * 60),
+        })
+    }
+
+    /// Download the catalog from a different URL, e.g. an internal mirror.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Cache the downloaded catalog in `path`.
+    pub fn with_cache_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.cache_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Re-download the catalog once the cached copy is older than `max_age`
+    /// (default: one day).
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Load the catalog, from the cache file if it is fresh, otherwise from the network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the download fails, the catalog cannot be parsed,
+    /// or the cache file cannot be written.
+    pub async fn catalog(&self) -> Result<KevCatalog> {
+        if let Some(path) = &self.cache_file
+            && self.is_fresh(path)
+        {
+            debug!("Using cached KEV catalog at {}", path.display());
+            return KevCatalog::from_json(&std::fs::read_to_string(path)?);
+        }
+
+        debug!("Downloading KEV catalog from {}", self.url);
+        let body = self
+            .http
+            .get(&self.url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        let catalog = KevCatalog::from_json(&body)?;
+
+        if let Some(path) = &self.cache_file {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, &body)?;
+        }
+
+        Ok(catalog)
+    }
+
+    fn is_fresh(&self, path: &Path) -> bool {
+        std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age <= self.max_age)
+    }
+}
@@ -0,0 +1,37 @@
+//! Enrichment of fetched findings with third-party vulnerability data.
+//!
+//! Each enrichment source lives behind its own cargo feature so the
+//! corresponding network calls are opt-in:
+//!
+//! - `epss` — [`epss`]: exploit prediction scores from FIRST.org
+//! - `kev` — [`kev`]: the CISA Known Exploited Vulnerabilities catalog
+//! - `nvd` — [`nvd`]: CVSS vectors and descriptions from the NVD
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "epss")]
+pub mod epss;
+
+#[cfg(feature = "kev")]
+pub mod kev;
+
+#[cfg(feature = "nvd")]
+pub mod nvd;
+
+/// Details of a single CVE, as attached to [`Finding::cve_details`].
+///
+/// [`Finding::cve_details`]: crate::Finding::cve_details
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CveDetails {
+    /// CVE identifier, e.g. `CVE-2021-44228`.
+    pub id: String,
+    /// English description of the vulnerability.
+    #[serde(default)]
+    pub description: String,
+    /// CVSS vector string of the newest available CVSS version.
+    #[serde(default)]
+    pub cvss_vector: Option<String>,
+    /// CVSS base score matching `cvss_vector`.
+    #[serde(default)]
+    pub cvss_score: Option<f64>,
+}
@@ -0,0 +1,278 @@
+//! NVD CVE detail enrichment.
+//!
+//! CVSS vectors, scores, and English descriptions are fetched from the
+//! NVD CVE API 2.0, one CVE per request. Requests are spaced to stay
+//! within NVD's public rate limits (5 requests per 30 seconds without an
+//! API key, 50 with one), and results are cached in memory and optionally
+//! in a JSON file. CVEs the NVD does not know are cached too, so each is
+//! requested only once; delete the cache file to look them up again.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::enrich::nvd::NvdClient;
+//! use nessus_launcher::{NessusClient, NessusConfig, ResultFilter, Result};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let client = NessusClient::new(NessusConfig::from_env()?)?;
+//!     let mut findings = client.findings(5, &ResultFilter::new()).await?;
+//!
+//!     let mut nvd = NvdClient::new()?
+//!         .with_api_key(std::env::var("NVD_API_KEY").ok())
+//!         .with_cache_file(".cache/nvd.json")?;
+//!     nvd.enrich(&mut findings).await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::enrich::CveDetails;
+use crate::{Finding, NessusError, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::{Instant, sleep_until};
+use tracing::{debug, warn};
+
+/// Default NVD CVE API endpoint.
+pub const DEFAULT_NVD_URL: &str = "https://services.nvd.nist.gov/rest/json/cves/2.0";
+
+/// Minimum spacing between requests without an API key (5 per 30s).
+const ANONYMOUS_INTERVAL: Duration = Duration::from_secs(6);
+
+/// Minimum spacing between requests with an API key (50 per 30s).
+const KEYED_INTERVAL: Duration = Duration::from_millis(600);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdResponse {
+    #[serde(default)]
+    vulnerabilities: Vec<NvdVulnerability>,
+}
+
+#[derive(Deserialize)]
+struct NvdVulnerability {
+    cve: NvdCve,
+}
+
+#[derive(Deserialize)]
+struct NvdCve {
+    id: String,
+    #[serde(default)]
+    descriptions: Vec<NvdDescription>,
+    #[serde(default)]
+    metrics: NvdMetrics,
+}
+
+#[derive(Deserialize)]
+struct NvdDescription {
+    lang: String,
+    value: String,
+}
+
+#[derive(Deserialize, Default)]
+struct NvdMetrics {
+    #[serde(default, rename = "cvssMetricV31")]
+    v31: Vec<NvdMetric>,
+    #[serde(default, rename = "cvssMetricV30")]
+    v30: Vec<NvdMetric>,
+    #[serde(default, rename = "cvssMetricV2")]
+    v2: Vec<NvdMetric>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdMetric {
+    #[serde(default, rename = "type")]
+    kind: String,
+    cvss_data: NvdCvssData,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdCvssData {
+    vector_string: String,
+    base_score: f64,
+}
+
+impl From<NvdCve> for CveDetails {
+    fn from(cve: NvdCve) -> Self {
+        let description = cve
+            .descriptions
+            .into_iter()
+            .find(|d| d.lang == "en")
+            .map(|d| d.value)
+            .unwrap_or_default();
+
+        // Prefer the newest CVSS version, and NVD's own ("Primary") scoring within it.
+        let metric = [cve.metrics.v31, cve.metrics.v30, cve.metrics.v2]
+            .into_iter()
+            .find(|m| !m.is_empty())
+            .map(|mut metrics| {
+                let primary = metrics.iter().position(|m| m.kind == "Primary").unwrap_or(0);
+                metrics.swap_remove(primary)
+            });
+
+        CveDetails {
+            id: cve.id,
+            description,
+            cvss_vector: metric.as_ref().map(|m| m.cvss_data.vector_string.clone()),
+            cvss_score: metric.map(|m| m.cvss_data.base_score),
+        }
+    }
+}
+
+/// Looks up CVE details from the NVD, respecting its rate limits.
+pub struct NvdClient {
+    http: Client,
+    base_url: String,
+    api_key: Option<String>,
+    /// Details by CVE, or `None` for CVEs the NVD does not know.
+    cache: HashMap<String, Option<CveDetails>>,
+    cache_file: Option<PathBuf>,
+    next_request: Option<Instant>,
+}
+
+impl NvdClient {
+    /// Create an anonymous client using [`DEFAULT_NVD_URL`] and an in-memory cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if the underlying HTTP client cannot be built.
+    pub fn new() -> Result<Self> {
+        let http = Client::builder()
+            .build()
+            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self {
+            http,
+            base_url: DEFAULT_NVD_URL.to_string(),
+            api_key: None,
+            cache: HashMap::new(),
+            cache_file: None,
+            next_request: None,
+        })
+    }
+
+    /// Use a different NVD API endpoint, e.g. an internal mirror.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Authenticate with an NVD API key, which raises the rate limit tenfold.
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key.filter(|k| !k.is_empty());
+        self
+    }
+
+    /// Persist the cache in a JSON file, loading any existing entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Io`] or [`NessusError::Json`] if an existing
+    /// cache file cannot be read.
+    pub fn with_cache_file(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if path.exists() {
+            self.cache = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        }
+        self.cache_file = Some(path);
+        Ok(self)
+    }
+
+    /// Look up a single CVE, from the cache if possible.
+    ///
+    /// Returns `None` if the NVD does not know the CVE, which is cached
+    /// like any other answer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    pub async fn cve(&mut self, cve: &str) -> Result<Option<CveDetails>> {
+        if let Some(details) = self.cache.get(cve) {
+            return Ok(details.clone());
+        }
+
+        self.throttle().await;
+        debug!("Fetching NVD details for {}", cve);
+
+        let mut req = self.http.get(&self.base_url).query(&[("cveId", cve)]);
+        if let Some(key) = &self.api_key {
+            req = req.header("apiKey", key);
+        }
+
+        let resp: NvdResponse = req.send().await?.error_for_status()?.json().await?;
+
+        let details = resp.vulnerabilities.into_iter().next().map(|v| v.cve.into());
+        if details.is_none() {
+            warn!("NVD has no record of {}", cve);
+        }
+        self.cache.insert(cve.to_string(), details.clone());
+        Ok(details)
+    }
+
+    /// Fill [`Finding::cve_details`] for every finding.
+    ///
+    /// Each distinct CVE is requested at most once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an API request fails or the cache file cannot be written.
+    pub async fn enrich(&mut self, findings: &mut [Finding]) -> Result<()> {
+        let cves: BTreeSet<String> = findings
+            .iter()
+            .flat_map(|f| f.cves.iter().cloned())
+            .collect();
+
+        let mut details = HashMap::new();
+        let result = async {
+            for cve in cves {
+                if let Some(d) = self.cve(&cve).await? {
+                    details.insert(cve, d);
+                }
+            }
+            Ok::<_, NessusError>(())
+        }
+        .await;
+
+        // Keep whatever was fetched before a failure for the next run.
+        self.save()?;
+        result?;
+
+        for finding in findings.iter_mut() {
+            finding.cve_details = finding
+                .cves
+                .iter()
+                .filter_map(|cve| details.get(cve).cloned())
+                .collect();
+        }
+
+        Ok(())
+    }
+
+    async fn throttle(&mut self) {
+        if let Some(at) = self.next_request {
+            sleep_until(at).await;
+        }
+
+        let interval = if self.api_key.is_some() {
+            KEYED_INTERVAL
+        } else {
+            ANONYMOUS_INTERVAL
+        };
+        self.next_request = Some(Instant::now() + interval);
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(path) = &self.cache_file {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, serde_json::to_string(&self.cache)?)?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 //! Error types for the `nessus-launcher` library.
 
+use crate::{LaunchReport, UnexpectedResponse};
 use std::fmt;
 use std::io;
 
@@ -18,6 +19,23 @@ pub enum NessusError {
     /// Errors related to environment variables or configuration.
     Config(String),
 
+    /// Authentication failed: the X-API token could not be obtained, the
+    /// login was rejected, or the session is no longer valid.
+    Auth(String),
+
+    /// Scans failed to launch, and the client's [`FailurePolicy`] treats
+    /// that as an error. Holds the full report.
+    ///
+    /// [`FailurePolicy`]: crate::FailurePolicy
+    Launch(LaunchReport),
+
+    /// A response could not be decoded as expected. Holds the request, a
+    /// snippet of the body, and a hint at the likely cause.
+    UnexpectedResponse(UnexpectedResponse),
+
+    /// The server runs a Nessus release this library cannot talk to.
+    UnsupportedVersion(String),
+
     /// I/O related errors.
     Io(io::Error),
 
@@ -31,6 +49,15 @@ impl fmt::Display for NessusError {
             NessusError::Http(e) => write!(f, "HTTP error: {e}"),
             NessusError::Json(e) => write!(f, "JSON error: {e}"),
             NessusError::Config(msg) => write!(f, "Configuration error: {msg}"),
+            NessusError::Auth(msg) => write!(f, "Authentication error: {msg}"),
+            NessusError::Launch(report) => write!(
+                f,
+                "Launch error: {} of {} scan(s) failed to launch",
+                report.total() - report.succeeded.len(),
+                report.total()
+            ),
+            NessusError::UnexpectedResponse(e) => write!(f, "Unexpected response to {e}"),
+            NessusError::UnsupportedVersion(msg) => write!(f, "Unsupported Nessus version: {msg}"),
             NessusError::Io(e) => write!(f, "I/O error: {e}"),
             NessusError::Other(msg) => write!(f, "Error: {msg}"),
         }
@@ -0,0 +1,192 @@
+//! Normalized CSV export of findings.
+//!
+//! Unlike Nessus' own CSV export, the output has exactly one row per
+//! host and plugin, with a stable, configurable set of columns. Multiple
+//! CVEs are joined with `;` in a single cell.
+//!
+//! ## Example
+//!
+//! ```
+//! use nessus_launcher::export::csv::{CsvColumn, CsvExporter};
+//!
+//! let exporter = CsvExporter::new().with_columns(vec![
+//!     CsvColumn::Hostname,
+//!     CsvColumn::PluginId,
+//!     CsvColumn::Severity,
+//! ]);
+//! let csv = exporter.to_string(&[]).unwrap();
+//! assert_eq!(csv, "hostname,plugin_id,severity\n");
+//! ```
+
+use crate::{Finding, NessusError, Result};
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// A column of the CSV export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CsvColumn {
+    /// Scan the finding belongs to.
+    ScanId,
+    /// Nessus' host identifier within the scan.
+    HostId,
+    /// Hostname or IP address.
+    Hostname,
+    /// Plugin identifier.
+    PluginId,
+    /// Plugin name.
+    PluginName,
+    /// Plugin family.
+    PluginFamily,
+    /// Severity name, e.g. `high`.
+    Severity,
+    /// CVEs, separated by `;`.
+    Cves,
+    /// Highest EPSS score, if enriched.
+    Epss,
+    /// `true` if a CVE is in the CISA KEV catalog.
+    Kev,
+    /// Highest NVD CVSS base score, if enriched.
+    CvssScore,
+    /// CVSS vector of the highest-scoring CVE, if enriched.
+    CvssVector,
+}
+
+impl CsvColumn {
+    /// Every column, in the default order.
+    pub const ALL: [CsvColumn; 12] = [
+        CsvColumn::ScanId,
+        CsvColumn::HostId,
+        CsvColumn::Hostname,
+        CsvColumn::PluginId,
+        CsvColumn::PluginName,
+        CsvColumn::PluginFamily,
+        CsvColumn::Severity,
+        CsvColumn::Cves,
+        CsvColumn::Epss,
+        CsvColumn::Kev,
+        CsvColumn::CvssScore,
+        CsvColumn::CvssVector,
+    ];
+
+    /// The column's header name.
+    pub fn name(self) -> &'static str {
+        match self {
+            CsvColumn::ScanId => "scan_id",
+            CsvColumn::HostId => "host_id",
+            CsvColumn::Hostname => "hostname",
+            CsvColumn::PluginId => "plugin_id",
+            CsvColumn::PluginName => "plugin_name",
+            CsvColumn::PluginFamily => "plugin_family",
+            CsvColumn::Severity => "severity",
+            CsvColumn::Cves => "cves",
+            CsvColumn::Epss => "epss",
+            CsvColumn::Kev => "kev",
+            CsvColumn::CvssScore => "cvss_score",
+            CsvColumn::CvssVector => "cvss_vector",
+        }
+    }
+
+    fn value(self, f: &Finding) -> String {
+        let top_cvss = f
+            .cve_details
+            .iter()
+            .filter(|d| d.cvss_score.is_some())
+            .max_by(|a, b| a.cvss_score.partial_cmp(&b.cvss_score).unwrap_or(std::cmp::Ordering::Equal));
+
+        match self {
+            CsvColumn::ScanId => f.scan_id.to_string(),
+            CsvColumn::HostId => f.host_id.to_string(),
+            CsvColumn::Hostname => f.hostname.clone(),
+            CsvColumn::PluginId => f.plugin_id.to_string(),
+            CsvColumn::PluginName => f.plugin_name.clone(),
+            CsvColumn::PluginFamily => f.plugin_family.clone(),
+            CsvColumn::Severity => f.severity.to_string(),
+            CsvColumn::Cves => f.cves.join(";"),
+            CsvColumn::Epss => f.epss.map(|e| e.to_string()).unwrap_or_default(),
+            CsvColumn::Kev => f.kev.to_string(),
+            CsvColumn::CvssScore => top_cvss
+                .and_then(|d| d.cvss_score)
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            CsvColumn::CvssVector => top_cvss
+                .and_then(|d| d.cvss_vector.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for CsvColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for CsvColumn {
+    type Err = NessusError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        CsvColumn::ALL
+            .into_iter()
+            .find(|c| c.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| NessusError::Other(format!("Unknown CSV column '{s}'")))
+    }
+}
+
+/// Writes findings as normalized CSV.
+#[derive(Debug, Clone)]
+pub struct CsvExporter {
+    columns: Vec<CsvColumn>,
+}
+
+impl Default for CsvExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsvExporter {
+    /// Create an exporter writing [`CsvColumn::ALL`].
+    pub fn new() -> Self {
+        Self {
+            columns: CsvColumn::ALL.to_vec(),
+        }
+    }
+
+    /// Write only `columns`, in the given order.
+    pub fn with_columns(mut self, columns: Vec<CsvColumn>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Write a header row and one row per finding to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Io`] if writing fails.
+    pub fn write<W: Write>(&self, writer: W, findings: &[Finding]) -> Result<()> {
+        let mut csv = ::csv::Writer::from_writer(writer);
+
+        csv.write_record(self.columns.iter().map(|c| c.name()))
+            .map_err(io::Error::from)?;
+        for finding in findings {
+            csv.write_record(self.columns.iter().map(|c| c.value(finding)))
+                .map_err(io::Error::from)?;
+        }
+        csv.flush()?;
+
+        Ok(())
+    }
+
+    /// Render findings to a CSV string.
+    ///
+    /// # Errors
+    ///
+    /// See [`CsvExporter::write`].
+    pub fn to_string(&self, findings: &[Finding]) -> Result<String> {
+        let mut buf = Vec::new();
+        self.write(&mut buf, findings)?;
+        String::from_utf8(buf).map_err(|e| NessusError::Other(format!("Invalid UTF-8 in CSV: {e}")))
+    }
+}
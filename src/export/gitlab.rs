@@ -0,0 +1,234 @@
+//! GitLab security report export.
+//!
+//! Findings are written as a DAST security report, the JSON document
+//! GitLab ingests from the `reports:dast` artifact of a CI job to populate
+//! its vulnerability dashboard and merge request widgets.
+//!
+//! ## Example
+//!
+//! ```
+//! use nessus_launcher::export::gitlab::GitlabExporter;
+//!
+//! let json = GitlabExporter::new().to_string(&[]).unwrap();
+//! assert!(json.contains("\"vulnerabilities\": []"));
+//! ```
+//!
+//! Used from a pipeline:
+//!
+//! ```yaml
+//! nessus:
+//!   script:
+//!     - nessus-cli results 5 --format gitlab --out gl-dast-report.json
+//!   artifacts:
+//!     reports:
+//!       dast: gl-dast-report.json
+//! ```
+
+use crate::{Finding, Result, Severity};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::io::Write;
+
+/// Version of the GitLab security report schema the output conforms to.
+pub const SCHEMA_VERSION: &str = "15.0.7";
+
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Writes findings as a GitLab DAST security report.
+#[derive(Debug, Clone)]
+pub struct GitlabExporter {
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    scanner_version: String,
+}
+
+impl Default for GitlabExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitlabExporter {
+    /// Create an exporter whose scan start and end times are now.
+    pub fn new() -> Self {
+        let now = Utc::now();
+        Self {
+            start_time: now,
+            end_time: now,
+            scanner_version: "unknown".into(),
+        }
+    }
+
+    /// Set the start and end time of the scan the findings come from.
+    pub fn with_times(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.start_time = start;
+        self.end_time = end;
+        self
+    }
+
+    /// Set the Nessus version reported as the scanner version.
+    pub fn with_scanner_version(mut self, version: impl Into<String>) -> Self {
+        self.scanner_version = version.into();
+        self
+    }
+
+    /// Write the report for `findings` to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Json`] or [`NessusError::Io`] if serialization or writing fails.
+    ///
+    /// [`NessusError::Json`]: crate::NessusError::Json
+    /// [`NessusError::Io`]: crate::NessusError::Io
+    pub fn write<W: Write>(&self, mut writer: W, findings: &[Finding]) -> Result<()> {
+        serde_json::to_writer_pretty(&mut writer, &self.report(findings))?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Render the report for `findings` as a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Json`] if serialization fails.
+    ///
+    /// [`NessusError::Json`]: crate::NessusError::Json
+    pub fn to_string(&self, findings: &[Finding]) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.report(findings))?)
+    }
+
+    fn report<'a>(&'a self, findings: &'a [Finding]) -> SecurityReport<'a> {
+        SecurityReport {
+            version: SCHEMA_VERSION,
+            scan: ScanSection {
+                analyzer: Tool {
+                    id: "nessus-launcher",
+                    name: "nessus-launcher",
+                    version: env!("CARGO_PKG_VERSION"),
+                    vendor: Vendor {
+                        name: "nessus-launcher",
+                    },
+                },
+                scanner: Tool {
+                    id: "nessus",
+                    name: "Nessus",
+                    version: &self.scanner_version,
+                    vendor: Vendor { name: "Tenable" },
+                },
+                kind: "dast",
+                start_time: self.start_time.format(TIME_FORMAT).to_string(),
+                end_time: self.end_time.format(TIME_FORMAT).to_string(),
+                status: "success",
+            },
+            vulnerabilities: findings.iter().map(vulnerability).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SecurityReport<'a> {
+    version: &'static str,
+    scan: ScanSection<'a>,
+    vulnerabilities: Vec<Vulnerability<'a>>,
+}
+
+#[derive(Serialize)]
+struct ScanSection<'a> {
+    analyzer: Tool<'a>,
+    scanner: Tool<'a>,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    start_time: String,
+    end_time: String,
+    status: &'static str,
+}
+
+#[derive(Serialize)]
+struct Tool<'a> {
+    id: &'static str,
+    name: &'static str,
+    version: &'a str,
+    vendor: Vendor,
+}
+
+#[derive(Serialize)]
+struct Vendor {
+    name: &'static str,
+}
+
+#[derive(Serialize)]
+struct Vulnerability<'a> {
+    id: String,
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    severity: &'static str,
+    identifiers: Vec<Identifier>,
+    location: Location<'a>,
+}
+
+#[derive(Serialize)]
+struct Identifier {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: String,
+    value: String,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct Location<'a> {
+    hostname: &'a str,
+}
+
+fn vulnerability(finding: &Finding) -> Vulnerability<'_> {
+    let mut identifiers = vec![Identifier {
+        kind: "nessus_plugin",
+        name: format!("Nessus plugin {}", finding.plugin_id),
+        value: finding.plugin_id.to_string(),
+        url: format!(
+            "https://www.tenable.com/plugins/nessus/{}",
+            finding.plugin_id
+        ),
+    }];
+    identifiers.extend(finding.cves.iter().map(|cve| Identifier {
+        kind: "cve",
+        name: cve.clone(),
+        value: cve.clone(),
+        url: format!("https://nvd.nist.gov/vuln/detail/{cve}"),
+    }));
+
+    let description = finding.output.as_deref().or_else(|| {
+        finding
+            .cve_details
+            .iter()
+            .map(|d| d.description.as_str())
+            .find(|d| !d.is_empty())
+    });
+
+    Vulnerability {
+        id: format!(
+            "nessus-{}-{}-{}",
+            finding.scan_id, finding.host_id, finding.plugin_id
+        ),
+        name: &finding.plugin_name,
+        description,
+        severity: severity_name(finding.severity),
+        identifiers,
+        location: Location {
+            hostname: &finding.hostname,
+        },
+    }
+}
+
+/// GitLab's capitalized severity names.
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "Critical",
+        Severity::High => "High",
+        Severity::Medium => "Medium",
+        Severity::Low => "Low",
+        Severity::Info => "Info",
+    }
+}
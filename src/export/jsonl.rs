@@ -0,0 +1,56 @@
+//! JSON Lines export of findings.
+//!
+//! Each line is one [`Finding`] serialized as a JSON object, with the scan,
+//! host, plugin, severity, CVEs, and (if loaded) plugin output, plus any
+//! enrichment data.
+//!
+//! ## Example
+//!
+//! ```
+//! use nessus_launcher::export::jsonl;
+//!
+//! let mut buf = Vec::new();
+//! jsonl::write(&mut buf, &[]).unwrap();
+//! assert!(jsonl::read(buf.as_slice()).unwrap().is_empty());
+//! ```
+
+use crate::{Finding, Result};
+use std::io::{BufRead, Write};
+
+/// Write one JSON object per finding to `writer`.
+///
+/// # Errors
+///
+/// Returns [`NessusError::Json`] or [`NessusError::Io`] if serialization or writing fails.
+///
+/// [`NessusError::Json`]: crate::NessusError::Json
+/// [`NessusError::Io`]: crate::NessusError::Io
+pub fn write<W: Write>(mut writer: W, findings: &[Finding]) -> Result<()> {
+    for finding in findings {
+        serde_json::to_writer(&mut writer, finding)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read findings previously written by [`write`], skipping blank lines.
+///
+/// # Errors
+///
+/// Returns [`NessusError::Io`] if reading fails, or [`NessusError::Json`]
+/// if a line is not a valid finding.
+///
+/// [`NessusError::Json`]: crate::NessusError::Json
+/// [`NessusError::Io`]: crate::NessusError::Io
+pub fn read<R: BufRead>(reader: R) -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        findings.push(serde_json::from_str(&line)?);
+    }
+    Ok(findings)
+}
@@ -0,0 +1,11 @@
+//! Exporters writing findings in machine-readable formats.
+//!
+//! - `csv` — [`csv`]: a normalized CSV schema with configurable columns (feature `csv`)
+//! - `gitlab` — [`gitlab`]: a GitLab security report for the vulnerability dashboard (feature `gitlab`)
+//! - [`jsonl`]: one JSON object per finding, for data pipelines
+
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "gitlab")]
+pub mod gitlab;
+pub mod jsonl;
@@ -0,0 +1,118 @@
+//! File uploads and scan import.
+//!
+//! This module extends [`NessusClient`] with:
+//!
+//! - [`NessusClient::upload_file`], which stores a file on the server for
+//!   use by later requests, such as policy import, scan import, or
+//!   credential attachments
+//! - [`NessusClient::import_scan`], which uploads a `.nessus` file and
+//!   imports it as a scan. This allows round-trips between scanners (export
+//!   from one, import into another) and ingesting result files shared by
+//!   hand for diffing and reporting.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::NessusClient;
+//!
+//! # async fn example(client: NessusClient) -> nessus_launcher::Result<()> {
+//! let name = client.upload_file("ssh_key.pem").await?;
+//! println!("Uploaded as {name}");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{NessusClient, NessusError, Result};
+use reqwest::Method;
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::multipart::{Form, Part};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
+use tracing::info;
+
+/// A scan created by [`NessusClient::import_scan`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportedScan {
+    /// Identifier of the new scan.
+    pub id: u32,
+    /// UUID of the imported run.
+    #[serde(default)]
+    pub uuid: Option<String>,
+    /// Name of the new scan.
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+struct UploadResponse {
+    fileuploaded: String,
+}
+
+#[derive(Deserialize)]
+struct ImportResponse {
+    scan: ImportedScan,
+}
+
+impl NessusClient {
+    /// Upload the `.nessus` file at `path` and import it as a new scan.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Io`] if the file cannot be read, or an error if
+    /// authentication, the upload, or the import fails.
+    pub async fn import_scan(&self, path: impl AsRef<Path>) -> Result<ImportedScan> {
+        let file_name = self.upload_file(path).await?;
+
+        let session = self.authenticate().await?;
+        let resp: ImportResponse = self
+            .send_json(
+                Method::POST,
+                &session,
+                "/scans/import",
+                &json!({ "file": file_name }),
+            )
+            .await?;
+
+        info!("Imported {} as scan {}", file_name, resp.scan.id);
+        Ok(resp.scan)
+    }
+
+    /// Upload the file at `path` through `/file/upload`.
+    ///
+    /// Returns the name the server stored the file as, which is what other
+    /// endpoints expect when referring to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Io`] if the file cannot be read, or an error if
+    /// authentication or the upload fails.
+    pub async fn upload_file(&self, path: impl AsRef<Path>) -> Result<String> {
+        let path = path.as_ref();
+        let body = tokio::fs::read(path).await?;
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .ok_or_else(|| NessusError::Other(format!("Not a file: {}", path.display())))?;
+
+        let form = Form::new().part("Filedata", Part::bytes(body).file_name(file_name));
+        let content_type = HeaderValue::from_str(&format!(
+            "multipart/form-data; boundary={}",
+            form.boundary()
+        ))
+        .map_err(|e| NessusError::Other(format!("Invalid multipart boundary: {e}")))?;
+
+        let session = self.authenticate().await?;
+        let endpoint = "/file/upload";
+        // The session headers default to JSON; replace that content type.
+        let req = self
+            .request(Method::POST, &session, endpoint)?
+            .multipart(form)
+            .headers(HeaderMap::from_iter([(CONTENT_TYPE, content_type)]));
+        let resp: UploadResponse = crate::response::decode(
+            &format!("POST {endpoint}"),
+            &self.send(req, endpoint).await?.text().await?,
+        )?;
+
+        Ok(resp.fileuploaded)
+    }
+}
@@ -0,0 +1,160 @@
+//! Pass/fail gating of findings, for use in CI pipelines.
+//!
+//! A [`GatePolicy`] describes which findings are unacceptable. Evaluating
+//! it against a set of findings yields a [`GateReport`] listing every
+//! [`GateBreach`]. Evaluate findings with the scanner's plugin rules applied,
+//! e.g. from [`NessusClient::findings_with_plugin_rules`], so accepted risks
+//! and recast severities are honored.
+//!
+//! ## Example
+//!
+//! ```
+//! use nessus_launcher::{GatePolicy, Severity};
+//!
+//! let policy = GatePolicy::new()
+//!     .fail_on_severity(Severity::Critical)
+//!     .fail_on_epss(0.5)
+//!     .fail_on_kev(true);
+//! let report = policy.evaluate(&[]);
+//! assert!(report.passed());
+//! ```
+//!
+//! [`NessusClient::findings_with_plugin_rules`]: crate::NessusClient::findings_with_plugin_rules
+
+use crate::{Finding, Severity};
+use std::fmt;
+
+/// Rules deciding whether a set of findings is acceptable.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GatePolicy {
+    /// Fail on any finding at or above this severity.
+    pub fail_on_severity: Option<Severity>,
+    /// Fail on any finding whose EPSS score is at or above this value.
+    pub fail_on_epss: Option<f64>,
+    /// Fail on any finding whose CVEs appear in the CISA KEV catalog.
+    pub fail_on_kev: bool,
+}
+
+impl GatePolicy {
+    /// Create a policy that accepts everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail on any finding at or above `severity`.
+    pub fn fail_on_severity(mut self, severity: Severity) -> Self {
+        self.fail_on_severity = Some(severity);
+        self
+    }
+
+    /// Fail on any finding whose EPSS score is at or above `threshold`.
+    pub fn fail_on_epss(mut self, threshold: f64) -> Self {
+        self.fail_on_epss = Some(threshold);
+        self
+    }
+
+    /// Fail on any finding flagged as a known exploited vulnerability.
+    pub fn fail_on_kev(mut self, fail: bool) -> Self {
+        self.fail_on_kev = fail;
+        self
+    }
+
+    /// Evaluate the policy against `findings`.
+    pub fn evaluate(&self, findings: &[Finding]) -> GateReport {
+        let mut breaches = Vec::new();
+
+        for finding in findings {
+            if let Some(min) = self.fail_on_severity
+                && finding.severity >= min
+            {
+                breaches.push(GateBreach {
+                    reason: BreachReason::Severity(finding.severity),
+                    finding: finding.clone(),
+                });
+            }
+
+            if let (Some(threshold), Some(epss)) = (self.fail_on_epss, finding.epss)
+                && epss >= threshold
+            {
+                breaches.push(GateBreach {
+                    reason: BreachReason::Epss(epss),
+                    finding: finding.clone(),
+                });
+            }
+
+            if self.fail_on_kev && finding.kev {
+                breaches.push(GateBreach {
+                    reason: BreachReason::Kev,
+                    finding: finding.clone(),
+                });
+            }
+        }
+
+        GateReport { breaches }
+    }
+}
+
+/// Why a finding breached a [`GatePolicy`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum BreachReason {
+    /// The finding's severity is at or above the configured threshold.
+    Severity(Severity),
+    /// The finding's EPSS score is at or above the configured threshold.
+    Epss(f64),
+    /// The finding references a known exploited vulnerability.
+    Kev,
+    /// The finding has been open longer than its severity's SLA window.
+    Sla {
+        /// Days since the finding was first seen.
+        age_days: u32,
+        /// The SLA window for its severity, in days.
+        window_days: u32,
+    },
+}
+
+impl fmt::Display for BreachReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BreachReason::Severity(s) => write!(f, "severity {s}"),
+            BreachReason::Epss(score) => write!(f, "EPSS score {score:.3}"),
+            BreachReason::Kev => f.write_str("known exploited vulnerability"),
+            BreachReason::Sla {
+                age_days,
+                window_days,
+            } => write!(f, "open {age_days} days (SLA {window_days} days)"),
+        }
+    }
+}
+
+/// A single finding that breached the policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateBreach {
+    /// The rule that was breached.
+    pub reason: BreachReason,
+    /// The offending finding.
+    pub finding: Finding,
+}
+
+impl fmt::Display for GateBreach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} on {}: plugin {} ({})",
+            self.reason, self.finding.hostname, self.finding.plugin_id, self.finding.plugin_name
+        )
+    }
+}
+
+/// The outcome of evaluating a [`GatePolicy`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GateReport {
+    /// Every breach found, in finding order.
+    pub breaches: Vec<GateBreach>,
+}
+
+impl GateReport {
+    /// Whether no rule was breached.
+    pub fn passed(&self) -> bool {
+        self.breaches.is_empty()
+    }
+}
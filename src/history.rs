@@ -0,0 +1,610 @@
+//! Local history of scan launches and results, and trends derived from it.
+//!
+//! A [`HistoryStore`] is a JSON file holding one [`LaunchRecord`] per
+//! launched scan, saying when, by whom, and how it went, and one
+//! [`ScanRun`] snapshot per recorded fetch of a scan's findings. From
+//! consecutive snapshots of the same scan:
+//!
+//! - [`HistoryStore::trend`] computes severity counts over time, how many
+//!   findings appeared and disappeared between runs, and the mean time to
+//!   remediate
+//! - [`HistoryStore::finding_ages`] tracks when each finding was first and
+//!   last seen, which an [`SlaPolicy`] checks against severity-based
+//!   remediation windows
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use chrono::Utc;
+//! use nessus_launcher::history::HistoryStore;
+//! use nessus_launcher::{NessusClient, NessusConfig, ResultFilter, Result};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let nessus = NessusClient::new(NessusConfig::from_env()?)?;
+//!     let findings = nessus.findings(5, &ResultFilter::new()).await?;
+//!
+//!     let trend = HistoryStore::update(".nessus-history.json", |history| {
+//!         history.record(5, Utc::now(), &findings);
+//!         history.trend(5)
+//!     })?;
+//!     println!("MTTR: {:?} days", trend.mttr_days);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::report::SeverityCounts;
+use crate::{
+    BreachReason, Finding, GateBreach, LaunchReport, NessusError, Result, ScanStatus, Severity,
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A finding as stored in the history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunFinding {
+    /// Hostname or IP address.
+    pub hostname: String,
+    /// Plugin identifier.
+    pub plugin_id: u32,
+    /// Plugin name.
+    pub plugin_name: String,
+    /// Severity at the time of the run.
+    pub severity: Severity,
+}
+
+impl RunFinding {
+    /// The host and plugin identifying the finding across runs.
+    pub fn key(&self) -> (&str, u32) {
+        (&self.hostname, self.plugin_id)
+    }
+}
+
+impl From<&Finding> for RunFinding {
+    fn from(finding: &Finding) -> Self {
+        Self {
+            hostname: finding.hostname.clone(),
+            plugin_id: finding.plugin_id,
+            plugin_name: finding.plugin_name.clone(),
+            severity: finding.severity,
+        }
+    }
+}
+
+/// The findings of one scan at one point in time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanRun {
+    /// The scan the findings belong to.
+    pub scan_id: u32,
+    /// When the findings were recorded.
+    pub recorded_at: DateTime<Utc>,
+    /// The findings.
+    pub findings: Vec<RunFinding>,
+}
+
+/// How the launch of a scan went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchOutcome {
+    /// The scan was launched.
+    Launched,
+    /// The scan failed to launch after all retries.
+    Failed,
+    /// The scan was not attempted because the batch stopped early.
+    Skipped,
+}
+
+impl fmt::Display for LaunchOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LaunchOutcome::Launched => "launched",
+            LaunchOutcome::Failed => "failed",
+            LaunchOutcome::Skipped => "skipped",
+        })
+    }
+}
+
+/// One launch of one scan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchRecord {
+    /// The scan launched.
+    pub scan_id: u32,
+    /// When the batch the scan belonged to started launching.
+    pub launched_at: DateTime<Utc>,
+    /// When the launch completed or, if the scan was waited for, when it
+    /// was seen finished.
+    pub completed_at: DateTime<Utc>,
+    /// What triggered the launch, e.g. `launch`, `plan nightly.toml`, or
+    /// `serve batch 3f2a...`.
+    pub trigger: String,
+    /// Who triggered the launch, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// How the launch went.
+    pub outcome: LaunchOutcome,
+    /// The status the scan finished with, if it was waited for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ScanStatus>,
+    /// Why the launch, or waiting for the scan, failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl LaunchRecord {
+    /// One record per scan of `report`, launched at `launched_at` and
+    /// completed at `completed_at` by `trigger` on behalf of `user`.
+    pub fn from_report(
+        report: &LaunchReport,
+        trigger: &str,
+        user: Option<&str>,
+        launched_at: DateTime<Utc>,
+        completed_at: DateTime<Utc>,
+    ) -> Vec<Self> {
+        let record = |scan_id, outcome, error| Self {
+            scan_id,
+            launched_at,
+            completed_at,
+            trigger: trigger.to_string(),
+            user: user.map(str::to_string),
+            outcome,
+            status: None,
+            error,
+        };
+        let launched = report
+            .succeeded
+            .iter()
+            .map(|&id| record(id, LaunchOutcome::Launched, None));
+        let failed = report
+            .failed
+            .iter()
+            .map(|f| record(f.scan_id, LaunchOutcome::Failed, Some(f.error.clone())));
+        let skipped = report
+            .skipped
+            .iter()
+            .map(|&id| record(id, LaunchOutcome::Skipped, None));
+        launched.chain(failed).chain(skipped).collect()
+    }
+
+    /// Time from the start of the launch to its completion.
+    pub fn duration(&self) -> TimeDelta {
+        self.completed_at - self.launched_at
+    }
+}
+
+/// One point of a [`Trend`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrendPoint {
+    /// When the run was recorded.
+    pub recorded_at: DateTime<Utc>,
+    /// Number of findings per severity.
+    pub counts: SeverityCounts,
+    /// Findings not present in the previous run.
+    pub new: usize,
+    /// Findings of the previous run no longer present.
+    pub fixed: usize,
+}
+
+/// How the findings of a scan developed over its recorded runs.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Trend {
+    /// The scan the trend describes.
+    pub scan_id: u32,
+    /// One point per recorded run, oldest first.
+    pub points: Vec<TrendPoint>,
+    /// Mean days between a finding's first appearance and the first run
+    /// without it, over all fixed findings. `None` if nothing was fixed.
+    pub mttr_days: Option<f64>,
+}
+
+impl Trend {
+    /// Write the trend as CSV, one row per run.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Io`] if writing fails.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> Result<()> {
+        writeln!(
+            writer,
+            "recorded_at,critical,high,medium,low,info,total,new,fixed"
+        )?;
+        for p in &self.points {
+            let c = &p.counts;
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{}",
+                p.recorded_at.to_rfc3339(),
+                c.critical,
+                c.high,
+                c.medium,
+                c.low,
+                c.info,
+                c.total(),
+                p.new,
+                p.fixed
+            )?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// How long a finding has been open, derived from the recorded runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FindingAge {
+    /// The finding as of the last run it was seen in.
+    pub finding: RunFinding,
+    /// The first run of the current streak of runs containing the finding.
+    /// A finding that disappears and comes back starts a new streak.
+    pub first_seen: DateTime<Utc>,
+    /// The last run containing the finding.
+    pub last_seen: DateTime<Utc>,
+    /// Whether the finding is present in the latest run.
+    pub open: bool,
+}
+
+impl FindingAge {
+    /// Whole days between `first_seen` and `now`.
+    pub fn age_days(&self, now: DateTime<Utc>) -> u32 {
+        (now - self.first_seen).num_days().max(0) as u32
+    }
+}
+
+/// Remediation windows per severity, in days.
+///
+/// `None` means findings of that severity have no SLA. Implements
+/// `Deserialize`, so windows can be set in a configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlaPolicy {
+    /// Window for critical findings.
+    pub critical: Option<u32>,
+    /// Window for high findings.
+    pub high: Option<u32>,
+    /// Window for medium findings.
+    pub medium: Option<u32>,
+    /// Window for low findings.
+    pub low: Option<u32>,
+    /// Window for informational findings.
+    pub info: Option<u32>,
+}
+
+impl Default for SlaPolicy {
+    /// 14 days for critical, 30 for high, 90 for medium, 180 for low, and
+    /// none for informational findings.
+    fn default() -> Self {
+        Self {
+            critical: Some(14),
+            high: Some(30),
+            medium: Some(90),
+            low: Some(180),
+            info: None,
+        }
+    }
+}
+
+impl SlaPolicy {
+    /// Set the window for `severity`.
+    pub fn with_window(mut self, severity: Severity, days: Option<u32>) -> Self {
+        *match severity {
+            Severity::Critical => &mut self.critical,
+            Severity::High => &mut self.high,
+            Severity::Medium => &mut self.medium,
+            Severity::Low => &mut self.low,
+            Severity::Info => &mut self.info,
+        } = days;
+        self
+    }
+
+    /// The window for `severity`.
+    pub fn window(&self, severity: Severity) -> Option<u32> {
+        match severity {
+            Severity::Critical => self.critical,
+            Severity::High => self.high,
+            Severity::Medium => self.medium,
+            Severity::Low => self.low,
+            Severity::Info => self.info,
+        }
+    }
+
+    /// Open findings older than their severity's window as of `now`, oldest first.
+    pub fn overdue<'a>(&self, ages: &'a [FindingAge], now: DateTime<Utc>) -> Vec<&'a FindingAge> {
+        let mut overdue: Vec<&FindingAge> = ages
+            .iter()
+            .filter(|a| a.open)
+            .filter(|a| {
+                self.window(a.finding.severity)
+                    .is_some_and(|window| a.age_days(now) > window)
+            })
+            .collect();
+        overdue.sort_by_key(|a| a.first_seen);
+        overdue
+    }
+
+    /// Gate breaches for `findings` that have been open longer than their
+    /// window, according to the recorded runs of `scan_id` in `store`.
+    ///
+    /// Findings not in the latest recorded run are treated as new.
+    pub fn evaluate(
+        &self,
+        store: &HistoryStore,
+        scan_id: u32,
+        findings: &[Finding],
+        now: DateTime<Utc>,
+    ) -> Vec<GateBreach> {
+        let ages = store.finding_ages(scan_id);
+        let first_seen: HashMap<(&str, u32), DateTime<Utc>> = ages
+            .iter()
+            .filter(|a| a.open)
+            .map(|a| (a.finding.key(), a.first_seen))
+            .collect();
+
+        findings
+            .iter()
+            .filter_map(|finding| {
+                let window_days = self.window(finding.severity)?;
+                let seen = first_seen.get(&finding.key())?;
+                let age_days = (now - *seen).num_days().max(0) as u32;
+                (age_days > window_days).then(|| GateBreach {
+                    reason: BreachReason::Sla {
+                        age_days,
+                        window_days,
+                    },
+                    finding: finding.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryFile {
+    #[serde(default)]
+    runs: Vec<ScanRun>,
+    #[serde(default)]
+    launches: Vec<LaunchRecord>,
+}
+
+/// `path` with `suffix` appended to its file name.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    name.into()
+}
+
+/// Take the exclusive lock guarding the history file at `path`, creating
+/// its parent directories. The lock is released when the file is dropped.
+fn lock(path: &Path) -> Result<File> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    let lock = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(sibling(path, ".lock"))?;
+    lock.lock()?;
+    Ok(lock)
+}
+
+/// A JSON file of recorded scan launches and runs.
+#[derive(Debug)]
+pub struct HistoryStore {
+    path: PathBuf,
+    data: HistoryFile,
+}
+
+impl HistoryStore {
+    /// Open the store at `path`. A missing file yields an empty store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Io`] or [`NessusError::Json`] if the file
+    /// exists but cannot be read or parsed.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let data = match std::fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HistoryFile::default(),
+            Err(e) => return Err(NessusError::Io(e)),
+        };
+
+        Ok(Self { path, data })
+    }
+
+    /// The file the store is saved to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record the findings of `scan_id` as of `recorded_at`.
+    pub fn record(&mut self, scan_id: u32, recorded_at: DateTime<Utc>, findings: &[Finding]) {
+        self.data.runs.push(ScanRun {
+            scan_id,
+            recorded_at,
+            findings: findings.iter().map(RunFinding::from).collect(),
+        });
+    }
+
+    /// Record `launches`.
+    pub fn record_launches(&mut self, launches: impl IntoIterator<Item = LaunchRecord>) {
+        self.data.launches.extend(launches);
+    }
+
+    /// The recorded launches, of `scan_id` only if given, that started at
+    /// or after `since` if given, oldest first.
+    pub fn launches(
+        &self,
+        scan_id: Option<u32>,
+        since: Option<DateTime<Utc>>,
+    ) -> Vec<&LaunchRecord> {
+        let mut launches: Vec<&LaunchRecord> = self
+            .data
+            .launches
+            .iter()
+            .filter(|l| scan_id.is_none_or(|id| l.scan_id == id))
+            .filter(|l| since.is_none_or(|since| l.launched_at >= since))
+            .collect();
+        launches.sort_by_key(|l| l.launched_at);
+        launches
+    }
+
+    /// Open the store at `path`, apply `update` to it, and save it.
+    ///
+    /// An exclusive lock on `<path>.lock` is held from reading the file to
+    /// replacing it, so processes and threads updating the same file at
+    /// once each see the others' records rather than overwriting them.
+    ///
+    /// # Errors
+    ///
+    /// See [`HistoryStore::open`] and [`HistoryStore::save`].
+    pub fn update<T>(path: impl Into<PathBuf>, update: impl FnOnce(&mut Self) -> T) -> Result<T> {
+        let path = path.into();
+        let _lock = lock(&path)?;
+        let mut store = Self::open(path)?;
+        let value = update(&mut store);
+        store.write()?;
+        Ok(value)
+    }
+
+    /// Write the store back to its file, creating parent directories.
+    ///
+    /// The file is replaced atomically, under the same lock as
+    /// [`HistoryStore::update`]: the store is written to a temporary file
+    /// next to it, which is then renamed over it, so readers never see a
+    /// partial file. Records saved by others since the store was opened
+    /// are overwritten; use [`HistoryStore::update`] to keep them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Io`] or [`NessusError::Json`] if writing fails.
+    pub fn save(&self) -> Result<()> {
+        let _lock = lock(&self.path)?;
+        self.write()
+    }
+
+    /// Replace the file with the store. The caller holds the lock.
+    fn write(&self) -> Result<()> {
+        let json = serde_json::to_string(&self.data)?;
+        let tmp = sibling(&self.path, &format!(".{}.tmp", std::process::id()));
+        let written = File::create(&tmp)
+            .and_then(|mut file| {
+                file.write_all(json.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| std::fs::rename(&tmp, &self.path));
+        if written.is_err() {
+            let _ = std::fs::remove_file(&tmp);
+        }
+        Ok(written?)
+    }
+
+    /// The recorded runs of `scan_id`, oldest first.
+    pub fn runs(&self, scan_id: u32) -> Vec<&ScanRun> {
+        let mut runs: Vec<&ScanRun> = self
+            .data
+            .runs
+            .iter()
+            .filter(|r| r.scan_id == scan_id)
+            .collect();
+        runs.sort_by_key(|r| r.recorded_at);
+        runs
+    }
+
+    /// First- and last-seen times of every finding of `scan_id`.
+    ///
+    /// Findings fixed in an earlier streak and open again are reported once,
+    /// with their current streak.
+    pub fn finding_ages(&self, scan_id: u32) -> Vec<FindingAge> {
+        let mut ages: HashMap<(String, u32), FindingAge> = HashMap::new();
+
+        for run in self.runs(scan_id) {
+            let keys: HashSet<(&str, u32)> = run.findings.iter().map(RunFinding::key).collect();
+
+            for age in ages.values_mut() {
+                if !keys.contains(&age.finding.key()) {
+                    age.open = false;
+                }
+            }
+
+            for finding in &run.findings {
+                let key = (finding.hostname.clone(), finding.plugin_id);
+                let age = ages.entry(key).or_insert_with(|| FindingAge {
+                    finding: finding.clone(),
+                    first_seen: run.recorded_at,
+                    last_seen: run.recorded_at,
+                    open: true,
+                });
+                if !age.open {
+                    age.first_seen = run.recorded_at;
+                }
+                age.finding = finding.clone();
+                age.last_seen = run.recorded_at;
+                age.open = true;
+            }
+        }
+
+        let mut ages: Vec<FindingAge> = ages.into_values().collect();
+        ages.sort_by(|a, b| {
+            a.finding
+                .hostname
+                .cmp(&b.finding.hostname)
+                .then(a.finding.plugin_id.cmp(&b.finding.plugin_id))
+        });
+        ages
+    }
+
+    /// Compute the trend of `scan_id` over its recorded runs.
+    pub fn trend(&self, scan_id: u32) -> Trend {
+        let mut points = Vec::new();
+        let mut first_seen: HashMap<(&str, u32), DateTime<Utc>> = HashMap::new();
+        let mut remediation_days = Vec::new();
+        let mut previous: Option<HashSet<(&str, u32)>> = None;
+
+        for run in self.runs(scan_id) {
+            let keys: HashSet<(&str, u32)> = run.findings.iter().map(RunFinding::key).collect();
+
+            let (new, fixed) = match &previous {
+                Some(prev) => (
+                    keys.difference(prev).count(),
+                    prev.difference(&keys).count(),
+                ),
+                None => (keys.len(), 0),
+            };
+
+            first_seen.retain(|key, seen| {
+                if keys.contains(key) {
+                    return true;
+                }
+                remediation_days.push((run.recorded_at - *seen).num_seconds() as f64 / 86_400.0);
+                false
+            });
+            for key in &keys {
+                first_seen.entry(*key).or_insert(run.recorded_at);
+            }
+
+            points.push(TrendPoint {
+                recorded_at: run.recorded_at,
+                counts: SeverityCounts::from_severities(run.findings.iter().map(|f| f.severity)),
+                new,
+                fixed,
+            });
+            previous = Some(keys);
+        }
+
+        let mttr_days = (!remediation_days.is_empty())
+            .then(|| remediation_days.iter().sum::<f64>() / remediation_days.len() as f64);
+
+        Trend {
+            scan_id,
+            points,
+            mttr_days,
+        }
+    }
+}
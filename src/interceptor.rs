@@ -0,0 +1,65 @@
+//! Request and response hooks.
+//!
+//! An [`Interceptor`] sees every HTTP request the [`NessusClient`] sends to
+//! the Nessus server, including the token scrape and login, before it goes
+//! out, and every response before it is processed. Use interceptors to add
+//! custom headers, sign requests, log traffic, or inject faults without
+//! forking the client.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use async_trait::async_trait;
+//! use nessus_launcher::{Interceptor, NessusClient, NessusConfig, Result};
+//! use reqwest::header::HeaderValue;
+//!
+//! struct Tenant(&'static str);
+//!
+//! #[async_trait]
+//! impl Interceptor for Tenant {
+//!     async fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+//!         request
+//!             .headers_mut()
+//!             .insert("X-Tenant", HeaderValue::from_static(self.0));
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # fn example() -> Result<()> {
+//! let client = NessusClient::new(NessusConfig::from_env()?)?.with_interceptor(Tenant("blue"));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`NessusClient`]: crate::NessusClient
+
+use crate::Result;
+use async_trait::async_trait;
+use reqwest::{Request, Response};
+
+/// A hook around every request sent to the Nessus server.
+///
+/// Interceptors run in the order they were added. Both methods default to
+/// doing nothing.
+#[async_trait]
+pub trait Interceptor: Send + Sync {
+    /// Inspect or modify `request` before it is sent.
+    ///
+    /// # Errors
+    ///
+    /// An error aborts the request and is returned to the caller.
+    async fn on_request(&self, request: &mut Request) -> Result<()> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Inspect `response` before it is processed.
+    ///
+    /// # Errors
+    ///
+    /// An error discards the response and is returned to the caller.
+    async fn on_response(&self, response: &Response) -> Result<()> {
+        let _ = response;
+        Ok(())
+    }
+}
@@ -0,0 +1,325 @@
+//! Options for launching scans, and outcomes of launching a batch of scans.
+
+use crate::ScanExportFormat;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio_retry::strategy::ExponentialBackoff;
+use tracing::warn;
+
+/// How scans are launched.
+///
+/// Passed to [`NessusClient::launch_scan`] directly, or set on the client
+/// with [`NessusClient::with_launch_options`] for batch launches.
+///
+/// [`NessusClient::launch_scan`]: crate::NessusClient::launch_scan
+/// [`NessusClient::with_launch_options`]: crate::NessusClient::with_launch_options
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchOptions {
+    /// Number of retries after the first attempt fails.
+    pub retries: usize,
+    /// Targets to scan instead of the scan's configured ones, if non-empty.
+    pub alt_targets: Vec<String>,
+    /// Maximum number of launches in flight at once in a batch.
+    pub concurrency: usize,
+    /// When a batch launch with failed scans returns an error.
+    pub failure_policy: FailurePolicy,
+    /// Whether a batch stops at the first scan that fails after all retries.
+    pub fail_fast: bool,
+    /// Whether a batch checks its scan IDs against the server's scan list
+    /// before launching anything.
+    pub verify_scan_ids: bool,
+}
+
+/// When [`NessusClient::launch_scans_parallel`] treats failed scans as an
+/// error rather than just recording them in the [`LaunchReport`].
+///
+/// [`NessusClient::launch_scans_parallel`]: crate::NessusClient::launch_scans_parallel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Never; failures are only recorded in the report.
+    #[default]
+    ReportOnly,
+    /// When any scan fails to launch.
+    AnyFailed,
+    /// Only when every scan fails to launch.
+    AllFailed,
+}
+
+impl FailurePolicy {
+    /// Whether `report` counts as a failed batch under this policy.
+    pub fn is_failure(self, report: &LaunchReport) -> bool {
+        match self {
+            FailurePolicy::ReportOnly => false,
+            FailurePolicy::AnyFailed => !report.failed.is_empty(),
+            FailurePolicy::AllFailed => !report.failed.is_empty() && report.succeeded.is_empty(),
+        }
+    }
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        Self {
+            retries: 5,
+            alt_targets: Vec::new(),
+            concurrency: 16,
+            failure_policy: FailurePolicy::ReportOnly,
+            fail_fast: false,
+            verify_scan_ids: false,
+        }
+    }
+}
+
+impl LaunchOptions {
+    /// Options with the defaults: 5 retries, the scan's own targets, up to
+    /// 16 concurrent launches, failures only recorded in the report without
+    /// stopping the batch, and scan IDs launched without checking them first.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of launches in flight at once in a batch.
+    ///
+    /// Values below 1 are treated as 1.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Stop a batch as soon as one scan fails after all retries, e.g. because
+    /// the session was revoked mid-run.
+    ///
+    /// Launches still queued or in flight are cancelled and recorded in
+    /// [`LaunchReport::skipped`].
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Check a batch's scan IDs against [`NessusClient::list_scans`] first,
+    /// failing the whole batch if any does not exist.
+    ///
+    /// [`NessusClient::list_scans`]: crate::NessusClient::list_scans
+    pub fn with_verify_scan_ids(mut self, verify_scan_ids: bool) -> Self {
+        self.verify_scan_ids = verify_scan_ids;
+        self
+    }
+
+    /// Set when a batch launch with failed scans returns an error.
+    pub fn with_failure_policy(mut self, failure_policy: FailurePolicy) -> Self {
+        self.failure_policy = failure_policy;
+        self
+    }
+
+    /// Set the number of retries after the first attempt fails.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Scan `targets` for this launch only, instead of the configured ones.
+    pub fn with_alt_targets<I, T>(mut self, targets: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.alt_targets = targets.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Exponential backoff from 500ms, capped at 10s, for `retries` retries.
+    pub(crate) fn retry_strategy(&self) -> impl Iterator<Item = Duration> + use<> {
+        ExponentialBackoff::from_millis(500)
+            .max_delay(Duration::from_secs(10))
+            .take(self.retries)
+    }
+}
+
+/// One scan of a batch, with its own overrides of the client's
+/// [`LaunchOptions`] and what to do once it is launched.
+///
+/// This is also the shape of a scan entry in a plan file. A plain scan ID
+/// converts into a request without overrides.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScanRequest {
+    /// The scan to launch.
+    pub scan_id: u32,
+    /// Targets to scan instead of the batch's [`LaunchOptions::alt_targets`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt_targets: Option<Vec<String>>,
+    /// Retries instead of the batch's [`LaunchOptions::retries`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<usize>,
+    /// Whether to wait for the scan to finish after launching it.
+    #[serde(default)]
+    pub wait: bool,
+    /// Export to write once the scan has finished; implies waiting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export: Option<ScanExport>,
+}
+
+/// Where and in which format to export a scan's results.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScanExport {
+    /// Export file format.
+    pub format: ScanExportFormat,
+    /// File the export is written to.
+    pub path: PathBuf,
+}
+
+impl ScanRequest {
+    /// A request to launch `scan_id` with the batch's options.
+    pub fn new(scan_id: u32) -> Self {
+        Self {
+            scan_id,
+            alt_targets: None,
+            retries: None,
+            wait: false,
+            export: None,
+        }
+    }
+
+    /// Scan `targets` instead of the scan's configured ones.
+    pub fn with_alt_targets<I, T>(mut self, targets: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.alt_targets = Some(targets.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the number of retries after the first attempt fails.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = Some(retries);
+        self
+    }
+
+    /// Wait for the scan to finish after launching it.
+    pub fn with_wait(mut self, wait: bool) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    /// Export the scan's results in `format` to `path` once it has finished.
+    pub fn with_export(mut self, format: ScanExportFormat, path: impl Into<PathBuf>) -> Self {
+        self.export = Some(ScanExport {
+            format,
+            path: path.into(),
+        });
+        self
+    }
+
+    /// Whether anything remains to be done once the scan is launched.
+    pub fn needs_finishing(&self) -> bool {
+        self.wait || self.export.is_some()
+    }
+
+    /// The batch's `options` with this request's overrides applied.
+    pub fn launch_options(&self, options: &LaunchOptions) -> LaunchOptions {
+        let mut options = options.clone();
+        if let Some(alt_targets) = &self.alt_targets {
+            options.alt_targets = alt_targets.clone();
+        }
+        if let Some(retries) = self.retries {
+            options.retries = retries;
+        }
+        options
+    }
+}
+
+impl From<u32> for ScanRequest {
+    fn from(scan_id: u32) -> Self {
+        Self::new(scan_id)
+    }
+}
+
+/// Remove repeated scan IDs, keeping the first occurrence of each.
+///
+/// Launching a scan twice makes the second launch fail with `409 Conflict`
+/// and burn through its retries, so duplicates are dropped with a warning.
+pub fn dedupe_scan_ids(scan_ids: Vec<u32>) -> Vec<u32> {
+    dedupe_by_scan_id(scan_ids, |scan_id| *scan_id)
+}
+
+/// Like [`dedupe_scan_ids`], keeping the first request for each scan.
+pub(crate) fn dedupe_requests(requests: Vec<ScanRequest>) -> Vec<ScanRequest> {
+    dedupe_by_scan_id(requests, |request| request.scan_id)
+}
+
+fn dedupe_by_scan_id<T>(items: Vec<T>, scan_id: impl Fn(&T) -> u32) -> Vec<T> {
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    let mut unique = Vec::with_capacity(items.len());
+    for item in items {
+        if seen.insert(scan_id(&item)) {
+            unique.push(item);
+        } else {
+            duplicates.push(scan_id(&item));
+        }
+    }
+
+    if !duplicates.is_empty() {
+        warn!("Ignoring duplicate scan ID(s): {:?}", duplicates);
+    }
+    unique
+}
+
+/// A scan that could not be launched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchFailure {
+    /// The scan that failed to launch.
+    pub scan_id: u32,
+    /// Human-readable description of the final error.
+    pub error: String,
+}
+
+/// The outcome of launching one scan of a batch: its ID, or how it failed.
+pub type ScanLaunchResult = std::result::Result<u32, LaunchFailure>;
+
+/// The per-scan outcome of a batch launch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchReport {
+    /// Scans launched successfully, in completion order.
+    pub succeeded: Vec<u32>,
+    /// Scans that failed after all retries, in completion order.
+    pub failed: Vec<LaunchFailure>,
+    /// Scans not launched because a fail-fast batch stopped early, in the
+    /// order they were requested. A launch cancelled while in flight may
+    /// still have reached the server.
+    #[serde(default)]
+    pub skipped: Vec<u32>,
+}
+
+impl LaunchReport {
+    /// Whether every scan launched successfully.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty() && self.skipped.is_empty()
+    }
+
+    /// Record the outcome of one scan.
+    pub fn push(&mut self, result: ScanLaunchResult) {
+        match result {
+            Ok(scan_id) => self.succeeded.push(scan_id),
+            Err(failure) => self.failed.push(failure),
+        }
+    }
+
+    /// Total number of scans in the batch.
+    pub fn total(&self) -> usize {
+        self.succeeded.len() + self.failed.len() + self.skipped.len()
+    }
+}
+
+impl FromIterator<ScanLaunchResult> for LaunchReport {
+    fn from_iter<I: IntoIterator<Item = ScanLaunchResult>>(iter: I) -> Self {
+        let mut report = Self::default();
+        for result in iter {
+            report.push(result);
+        }
+        report
+    }
+}
@@ -3,9 +3,48 @@
 //! A high-level Rust library for launching Nessus scans with:
 //!
 //! - Automatic retry with exponential backoff
-//! - Parallel scan launching
-//! - Structured logging via `tracing`
+//! - Parallel scan launching, with per-scan targets, retries, waiting, and
+//!   exports from launch plans
+//! - A client builder for TLS, proxy, timeout, retry, and concurrency settings
+//! - Structured logging via `tracing`, with `X-Request-Id` correlation IDs
+//! - TLS via `rustls` (feature `rustls`, default) or the platform's native
+//!   TLS and trust store (feature `native-tls`); with neither, only `http`
+//!   hosts are supported
 //! - Configuration via environment variables / `.env`
+//! - Configurable polling when waiting for scans to finish
+//! - Optional in-memory caching of scan, folder, and tag listings, with
+//!   `ETag` / `Last-Modified` revalidation
+//! - Request and response interceptors for custom headers, signing, or logging
+//! - A redacted dump of all HTTP traffic for debugging (`NESSUS_HTTP_TRACE=1`)
+//! - Recording HTTP interactions to cassette files, redacted, and replaying
+//!   them in tests without a scanner
+//! - Server version detection, supporting Nessus 6 and later
+//! - Optional background keep-alive of a shared session for long-running services
+//! - Typed access to scan results with server-side filtering, including compliance audits
+//! - Plugin metadata lookups for enriching findings
+//! - Plugin rule management, with accepted-risk exclusions and severity recasts
+//!   applied consistently to reports and gates
+//! - Importing `.nessus` files as scans
+//! - Launching every enabled scan whose name matches a glob or regex, or
+//!   in a folder
+//! - Tag and asset management, including launching scans by tag
+//! - Scanner user management and scan permissions
+//! - Nessus Agent and agent group management for Nessus Manager deployments
+//! - Optional EPSS (feature `epss`), CISA KEV (feature `kev`), and NVD
+//!   (feature `nvd`) enrichment
+//! - CI gating of findings by severity, EPSS score, or KEV membership
+//! - Report generation, including standalone HTML (feature `html`)
+//! - Normalized CSV (feature `csv`), JSON Lines, and GitLab security report
+//!   (feature `gitlab`) export of findings
+//! - Pushing results into DefectDojo (feature `defectdojo`), Jira (feature `jira`),
+//!   and ServiceNow (feature `servicenow`)
+//! - PagerDuty alerts for failed launches (feature `pagerduty`) and Microsoft
+//!   Teams notifications (feature `teams`), plus templated webhooks for
+//!   anything else (feature `webhook`)
+//! - A local history of scan launches and results with trend reporting (feature `history`)
+//! - Recurring launch times in local time zones, with DST handling (feature `schedule`)
+//! - Uploading exported reports to S3 (feature `s3`), Azure Blob Storage
+//!   (feature `azure`), or Google Cloud Storage (feature `gcs`)
 //!
 //! ## Quick example
 //!
@@ -23,11 +62,74 @@
 //! }
 //! ```
 
+pub mod enrich;
+pub mod export;
+#[cfg(feature = "history")]
+pub mod history;
+pub mod notify;
+pub mod report;
+#[cfg(feature = "schedule")]
+pub mod schedule;
+pub mod sink;
+#[cfg(any(feature = "s3", feature = "azure", feature = "gcs"))]
+pub mod upload;
+
+mod agents;
+mod builder;
+mod cache;
+mod cassette;
 mod client;
 mod config;
+mod correlation;
 mod error;
+mod files;
+mod gate;
+mod interceptor;
+mod launch;
+mod permissions;
+mod plan;
+mod plugin_rules;
+mod plugins;
+mod poll;
+mod response;
+mod results;
+mod scans;
+mod select;
+mod server;
+mod session;
+mod tags;
+mod token;
+mod trace;
+mod users;
 
+pub use agents::{Agent, AgentFilter, AgentGroup};
+pub use builder::NessusClientBuilder;
+pub use cassette::Cassette;
 pub use client::NessusClient;
 pub use config::NessusConfig;
+pub use correlation::REQUEST_ID_HEADER;
 pub use error::{NessusError, Result};
+pub use files::ImportedScan;
+pub use gate::{BreachReason, GateBreach, GatePolicy, GateReport};
+pub use interceptor::Interceptor;
+pub use launch::{
+    FailurePolicy, LaunchFailure, LaunchOptions, LaunchReport, ScanExport, ScanLaunchResult,
+    ScanRequest, dedupe_scan_ids,
+};
+pub use permissions::{AclEntry, AclKind, ScanPermission};
+pub use plan::{FinishedScan, LaunchPlan, PlanReport};
+pub use plugin_rules::{NewPluginRule, PluginRule, PluginRuleAction, apply_plugin_rules};
+pub use plugins::{PluginAttribute, PluginDetails, PluginFamily, PluginSummary};
+pub use poll::PollStrategy;
+pub use response::UnexpectedResponse;
+pub use results::{
+    ComplianceItem, ComplianceStatus, Finding, HostCompliance, HostSummary, ResultFilter, Severity,
+};
+pub use scans::{Folder, ScanExportFormat, ScanSettingsPatch, ScanStatus, ScanSummary};
+pub use select::ScanNamePattern;
+pub use server::{ApiVersion, NessusVersion, ServerProperties};
+pub use session::{KeepAliveOptions, SessionKeepAlive};
+pub use tags::{Asset, Tag};
+pub use token::extract_x_api_token;
+pub use users::{NewUser, User, UserRole};
 
@@ -1,7 +0,0 @@
-#[derive(Debug, Clone)]
-pub struct NessusConfig {
-    pub host: String,
-    pub username: String,
-    pub password: String,
-}
-
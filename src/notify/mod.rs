@@ -0,0 +1,44 @@
+//! Notifications about launches and scans.
+//!
+//! A [`Notifier`] receives [`NotifyEvent`]s and forwards the ones it cares
+//! about to an external system. Notifiers live in submodules:
+//!
+//! - `pagerduty` — [`pagerduty`]: PagerDuty incidents for failed launches (feature `pagerduty`)
+//! - `teams` — [`teams`]: Microsoft Teams Adaptive Cards (feature `teams`)
+//! - `webhook` — [`webhook`]: any HTTP endpoint, with a templated body (feature `webhook`)
+
+use crate::{LaunchReport, Result, ScanStatus};
+use async_trait::async_trait;
+use serde::Serialize;
+
+#[cfg(feature = "pagerduty")]
+pub mod pagerduty;
+#[cfg(feature = "teams")]
+pub mod teams;
+#[cfg(feature = "webhook")]
+pub mod webhook;
+
+/// Something that happened during a run that notifiers may report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum NotifyEvent {
+    /// A batch of scans was launched, with or without failures.
+    LaunchCompleted {
+        /// Per-scan outcome of the batch.
+        report: LaunchReport,
+    },
+    /// A scan that was waited for reached a finished status.
+    ScanFinished {
+        /// The scan that finished.
+        scan_id: u32,
+        /// Its final status.
+        status: ScanStatus,
+    },
+}
+
+/// A destination for [`NotifyEvent`]s.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Report `event`. Notifiers silently ignore events they don't handle.
+    async fn notify(&self, event: &NotifyEvent) -> Result<()>;
+}
@@ -0,0 +1,185 @@
+//! PagerDuty alerting via the Events API v2.
+//!
+//! When a batch launch finishes with scans that failed after all retries,
+//! an incident is triggered so scanner outages page the on-call instead of
+//! silently skipping scans. Repeated failures of the same scans share a
+//! dedup key and therefore a single incident.
+//!
+//! ## Environment variables
+//!
+//! - `PAGERDUTY_ROUTING_KEY` — Integration key of an Events API v2 integration
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::notify::pagerduty::{PagerDutyConfig, PagerDutyNotifier};
+//! use nessus_launcher::notify::{Notifier, NotifyEvent};
+//! use nessus_launcher::{NessusClient, NessusConfig, Result};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let nessus = NessusClient::new(NessusConfig::from_env()?)?;
+//!     let report = nessus.launch_scans_parallel(vec![5, 8]).await?;
+//!
+//!     let pagerduty = PagerDutyNotifier::new(PagerDutyConfig::from_env()?)?;
+//!     pagerduty.notify(&NotifyEvent::LaunchCompleted { report }).await
+//! }
+//! ```
+
+use crate::notify::{Notifier, NotifyEvent};
+use crate::{NessusError, Result};
+use async_trait::async_trait;
+use dotenvy::dotenv;
+use reqwest::Client;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::env;
+use tracing::info;
+
+/// Default Events API v2 endpoint.
+pub const EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// Settings for triggering PagerDuty incidents.
+#[derive(Debug, Clone)]
+pub struct PagerDutyConfig {
+    /// Integration (routing) key.
+    pub routing_key: String,
+    /// Events API endpoint.
+    pub url: String,
+    /// Value of the `source` field, identifying where the alert came from.
+    pub source: String,
+}
+
+impl PagerDutyConfig {
+    /// Load configuration from `PAGERDUTY_ROUTING_KEY`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if the routing key is missing.
+    pub fn from_env() -> Result<Self> {
+        dotenv().ok();
+
+        let routing_key = env::var("PAGERDUTY_ROUTING_KEY")
+            .map_err(|_| NessusError::Config("Missing PAGERDUTY_ROUTING_KEY".into()))?;
+
+        Ok(Self {
+            routing_key,
+            url: EVENTS_URL.into(),
+            source: env::var("NESSUS_HOST").unwrap_or_else(|_| "nessus-launcher".into()),
+        })
+    }
+}
+
+/// An Events API v2 event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PagerDutyEvent {
+    /// Integration key.
+    pub routing_key: String,
+    /// Always `trigger`.
+    pub event_action: &'static str,
+    /// Key grouping repeated alerts into one incident.
+    pub dedup_key: String,
+    /// Alert details.
+    pub payload: PagerDutyPayload,
+}
+
+/// The `payload` of a [`PagerDutyEvent`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PagerDutyPayload {
+    /// One-line summary shown in the incident title.
+    pub summary: String,
+    /// Where the alert came from.
+    pub source: String,
+    /// `critical`, `error`, `warning`, or `info`.
+    pub severity: &'static str,
+    /// Affected component.
+    pub component: &'static str,
+    /// Free-form details: one entry per failed scan.
+    pub custom_details: BTreeMap<String, String>,
+}
+
+/// Triggers PagerDuty incidents for failed launches.
+pub struct PagerDutyNotifier {
+    http: Client,
+    config: PagerDutyConfig,
+}
+
+impl PagerDutyNotifier {
+    /// Create a new notifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if the HTTP client cannot be built.
+    pub fn new(config: PagerDutyConfig) -> Result<Self> {
+        let http = Client::builder()
+            .build()
+            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self { http, config })
+    }
+
+    /// The event sent for `event`, or `None` if it doesn't warrant a page.
+    pub fn event_for(&self, event: &NotifyEvent) -> Option<PagerDutyEvent> {
+        match event {
+            NotifyEvent::LaunchCompleted { report } if !report.failed.is_empty() => {
+                let mut ids: Vec<u32> = report.failed.iter().map(|f| f.scan_id).collect();
+                ids.sort_unstable();
+                let ids: Vec<String> = ids.iter().map(u32::to_string).collect();
+
+                Some(PagerDutyEvent {
+                    routing_key: self.config.routing_key.clone(),
+                    event_action: "trigger",
+                    dedup_key: format!("nessus-launch-failed-{}", ids.join("-")),
+                    payload: PagerDutyPayload {
+                        summary: format!(
+                            "{} of {} Nessus scan(s) failed to launch: {}",
+                            report.failed.len(),
+                            report.total(),
+                            ids.join(", ")
+                        ),
+                        source: self.config.source.clone(),
+                        severity: if report.succeeded.is_empty() {
+                            "critical"
+                        } else {
+                            "error"
+                        },
+                        component: "nessus",
+                        custom_details: report
+                            .failed
+                            .iter()
+                            .map(|f| (format!("scan {}", f.scan_id), f.error.clone()))
+                            .collect(),
+                    },
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for PagerDutyNotifier {
+    async fn notify(&self, event: &NotifyEvent) -> Result<()> {
+        let Some(pd_event) = self.event_for(event) else {
+            return Ok(());
+        };
+
+        let resp = self
+            .http
+            .post(&self.config.url)
+            .json(&pd_event)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(NessusError::Other(format!(
+                "PagerDuty event failed with status {status}: {body}"
+            )));
+        }
+
+        info!("Triggered PagerDuty incident {}", pd_event.dedup_key);
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+//! Microsoft Teams notifications via incoming webhooks.
+//!
+//! Launch summaries and scan completion notices are posted as Adaptive
+//! Cards, which both Teams workflow webhooks and legacy connector
+//! webhooks accept.
+//!
+//! [`TeamsConfig`] implements `Deserialize`, so it can be embedded in a
+//! configuration file.
+//!
+//! ## Environment variables
+//!
+//! - `TEAMS_WEBHOOK_URL` — Incoming webhook URL of the channel
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::notify::teams::{TeamsConfig, TeamsNotifier};
+//! use nessus_launcher::notify::{Notifier, NotifyEvent};
+//! use nessus_launcher::{NessusClient, NessusConfig, Result};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let nessus = NessusClient::new(NessusConfig::from_env()?)?;
+//!     let report = nessus.launch_scans_parallel(vec![5, 8]).await?;
+//!
+//!     let teams = TeamsNotifier::new(TeamsConfig::from_env()?)?;
+//!     teams.notify(&NotifyEvent::LaunchCompleted { report }).await
+//! }
+//! ```
+
+use crate::notify::{Notifier, NotifyEvent};
+use crate::{NessusError, Result};
+use async_trait::async_trait;
+use dotenvy::dotenv;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::env;
+use tracing::info;
+
+/// Settings for posting to a Teams channel.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TeamsConfig {
+    /// Incoming webhook URL.
+    pub webhook_url: String,
+    /// Post a summary after every batch launch.
+    #[serde(default = "default_true")]
+    pub launch_summaries: bool,
+    /// Post a notice when a waited-for scan finishes.
+    #[serde(default = "default_true")]
+    pub completion_notices: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl TeamsConfig {
+    /// Load configuration from `TEAMS_WEBHOOK_URL`, with every event enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if the webhook URL is missing.
+    pub fn from_env() -> Result<Self> {
+        dotenv().ok();
+
+        let webhook_url = env::var("TEAMS_WEBHOOK_URL")
+            .map_err(|_| NessusError::Config("Missing TEAMS_WEBHOOK_URL".into()))?;
+
+        Ok(Self {
+            webhook_url,
+            launch_summaries: true,
+            completion_notices: true,
+        })
+    }
+}
+
+/// Posts Adaptive Cards to a Teams channel.
+pub struct TeamsNotifier {
+    http: Client,
+    config: TeamsConfig,
+}
+
+impl TeamsNotifier {
+    /// Create a new notifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if the HTTP client cannot be built.
+    pub fn new(config: TeamsConfig) -> Result<Self> {
+        let http = Client::builder()
+            .build()
+            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self { http, config })
+    }
+
+    /// The webhook message posted for `event`, or `None` if the event is
+    /// disabled in the configuration.
+    pub fn message_for(&self, event: &NotifyEvent) -> Option<Value> {
+        let (title, facts) = match event {
+            NotifyEvent::LaunchCompleted { report } if self.config.launch_summaries => {
+                let title = if report.is_success() {
+                    format!("✅ Launched {} Nessus scan(s)", report.total())
+                } else {
+                    format!(
+                        "❌ {} of {} Nessus scan(s) failed to launch",
+                        report.failed.len(),
+                        report.total()
+                    )
+                };
+                let facts = report
+                    .succeeded
+                    .iter()
+                    .map(|id| fact(format!("Scan {id}"), "launched".into()))
+                    .chain(
+                        report
+                            .failed
+                            .iter()
+                            .map(|f| fact(format!("Scan {}", f.scan_id), f.error.clone())),
+                    )
+                    .collect();
+                (title, facts)
+            }
+            NotifyEvent::ScanFinished { scan_id, status } if self.config.completion_notices => (
+                format!("Nessus scan {scan_id} finished"),
+                vec![fact("Status".into(), status.to_string())],
+            ),
+            _ => return None,
+        };
+
+        Some(adaptive_card(&title, facts))
+    }
+}
+
+fn fact(title: String, value: String) -> Value {
+    json!({ "title": title, "value": value })
+}
+
+fn adaptive_card(title: &str, facts: Vec<Value>) -> Value {
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "contentUrl": null,
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": [
+                    {
+                        "type": "TextBlock",
+                        "text": title,
+                        "weight": "Bolder",
+                        "size": "Medium",
+                        "wrap": true
+                    },
+                    { "type": "FactSet", "facts": facts }
+                ]
+            }
+        }]
+    })
+}
+
+#[async_trait]
+impl Notifier for TeamsNotifier {
+    async fn notify(&self, event: &NotifyEvent) -> Result<()> {
+        let Some(message) = self.message_for(event) else {
+            return Ok(());
+        };
+
+        let resp = self
+            .http
+            .post(&self.config.webhook_url)
+            .json(&message)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(NessusError::Other(format!(
+                "Teams webhook failed with status {status}: {body}"
+            )));
+        }
+
+        info!("Posted Teams notification");
+        Ok(())
+    }
+}
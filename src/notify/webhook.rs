@@ -0,0 +1,154 @@
+//! Generic webhooks with user-supplied body templates.
+//!
+//! For systems without a dedicated integration, a [`WebhookNotifier`]
+//! renders a [Tera](https://keats.github.io/tera/) template from the event
+//! and sends the result with configurable method and headers.
+//!
+//! The template context is the event serialized as JSON: `event` holds the
+//! event name (`launch_completed` or `scan_finished`), and the remaining
+//! fields are those of the [`NotifyEvent`] variant. Templates are rendered
+//! without HTML escaping; use the `json_encode` filter to embed strings
+//! safely in a JSON body.
+//!
+//! [`WebhookConfig`] implements `Deserialize`, so it can be embedded in a
+//! configuration file, for example as TOML:
+//!
+//! ```toml
+//! [[webhooks]]
+//! url = "https://soar.example.com/api/events"
+//! events = ["launch_completed"]
+//! headers = { Authorization = "Bearer ..." }
+//! body = '''
+//! {
+//!   "source": "nessus",
+//!   "failed": {{ report.failed | length }},
+//!   "scans": {{ report.succeeded | json_encode() }}
+//! }
+//! '''
+//! ```
+
+use crate::notify::{Notifier, NotifyEvent};
+use crate::{NessusError, Result};
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tera::{Context, Tera};
+use tracing::info;
+
+/// Settings for one templated webhook.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    /// URL the rendered body is sent to.
+    pub url: String,
+    /// HTTP method (default `POST`).
+    #[serde(default = "default_method")]
+    pub method: String,
+    /// Extra request headers.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Tera template of the request body.
+    pub body: String,
+    /// Event names to send (default: every event).
+    #[serde(default)]
+    pub events: Option<Vec<String>>,
+}
+
+fn default_method() -> String {
+    "POST".into()
+}
+
+/// Sends events to a webhook, rendering the body from a template.
+pub struct WebhookNotifier {
+    http: Client,
+    config: WebhookConfig,
+    method: Method,
+}
+
+impl WebhookNotifier {
+    /// Create a new notifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if the method is invalid, or
+    /// [`NessusError::Other`] if the HTTP client cannot be built.
+    pub fn new(config: WebhookConfig) -> Result<Self> {
+        let method = config
+            .method
+            .to_uppercase()
+            .parse::<Method>()
+            .map_err(|e| {
+                NessusError::Config(format!("Invalid webhook method {}: {e}", config.method))
+            })?;
+
+        let http = Client::builder()
+            .build()
+            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self {
+            http,
+            config,
+            method,
+        })
+    }
+
+    /// Render the request body for `event`, or `None` if the event is not
+    /// selected by the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if the template fails to render.
+    pub fn render(&self, event: &NotifyEvent) -> Result<Option<String>> {
+        let value = serde_json::to_value(event)?;
+        let name = value["event"].as_str().unwrap_or_default();
+        if let Some(events) = &self.config.events
+            && !events.iter().any(|e| e == name)
+        {
+            return Ok(None);
+        }
+
+        let context = Context::from_value(value)
+            .map_err(|e| NessusError::Other(format!("Failed to build template context: {e}")))?;
+        Tera::one_off(&self.config.body, &context, false)
+            .map(Some)
+            .map_err(|e| NessusError::Other(format!("Failed to render webhook body: {e:?}")))
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, event: &NotifyEvent) -> Result<()> {
+        let Some(body) = self.render(event)? else {
+            return Ok(());
+        };
+
+        let mut request = self
+            .http
+            .request(self.method.clone(), &self.config.url)
+            .body(body);
+        if !self
+            .config
+            .headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case("content-type"))
+        {
+            request = request.header("Content-Type", "application/json");
+        }
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+
+        let resp = request.send().await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(NessusError::Other(format!(
+                "Webhook {} failed with status {status}: {body}",
+                self.config.url
+            )));
+        }
+
+        info!("Sent webhook to {}", self.config.url);
+        Ok(())
+    }
+}
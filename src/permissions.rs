@@ -0,0 +1,170 @@
+//! Scan permissions.
+//!
+//! This module extends [`NessusClient`] with read and write access to the
+//! access control list of a scan, so scans created by automation can be
+//! shared with the right users and groups straight away:
+//!
+//! - [`NessusClient::scan_permissions`]
+//! - [`NessusClient::set_scan_permissions`]
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::{AclEntry, NessusClient, ScanPermission};
+//!
+//! # async fn example(client: NessusClient) -> nessus_launcher::Result<()> {
+//! let mut acls = client.scan_permissions(5).await?;
+//! acls.push(AclEntry::group(3, ScanPermission::Control));
+//! client.set_scan_permissions(5, &acls).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{NessusClient, Result};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// The access a user or group has to a scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanPermission {
+    /// No access.
+    NoAccess,
+    /// Can view results.
+    View,
+    /// Can also launch, pause, and stop the scan.
+    Control,
+    /// Can also edit the scan's settings.
+    Configure,
+    /// Owns the scan.
+    Owner,
+}
+
+impl ScanPermission {
+    /// The numeric permission level used by the API.
+    pub fn level(self) -> u32 {
+        match self {
+            ScanPermission::NoAccess => 0,
+            ScanPermission::View => 16,
+            ScanPermission::Control => 32,
+            ScanPermission::Configure => 64,
+            ScanPermission::Owner => 128,
+        }
+    }
+
+    /// The permission for a numeric level, if it is a known one.
+    pub fn from_level(level: u32) -> Option<Self> {
+        match level {
+            0 => Some(ScanPermission::NoAccess),
+            16 => Some(ScanPermission::View),
+            32 => Some(ScanPermission::Control),
+            64 => Some(ScanPermission::Configure),
+            128 => Some(ScanPermission::Owner),
+            _ => None,
+        }
+    }
+}
+
+/// Who an [`AclEntry`] applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AclKind {
+    /// A single user.
+    User,
+    /// A user group.
+    Group,
+    /// Everyone not otherwise listed.
+    Default,
+}
+
+/// One entry of a scan's access control list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AclEntry {
+    /// Who the entry applies to.
+    #[serde(rename = "type")]
+    pub kind: AclKind,
+    /// User or group identifier; absent for the default entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    /// User or group name, as reported by the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Numeric permission level; see [`ScanPermission`].
+    pub permissions: u32,
+    /// Set to 1 on the owner's entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<u32>,
+}
+
+impl AclEntry {
+    /// An entry granting `permission` to the user with ID `user_id`.
+    pub fn user(user_id: u32, permission: ScanPermission) -> Self {
+        Self::new(AclKind::User, Some(user_id), permission)
+    }
+
+    /// An entry granting `permission` to the group with ID `group_id`.
+    pub fn group(group_id: u32, permission: ScanPermission) -> Self {
+        Self::new(AclKind::Group, Some(group_id), permission)
+    }
+
+    /// The entry for everyone not otherwise listed.
+    pub fn default_access(permission: ScanPermission) -> Self {
+        Self::new(AclKind::Default, None, permission)
+    }
+
+    /// The entry's permission, if its level is a known one.
+    pub fn permission(&self) -> Option<ScanPermission> {
+        ScanPermission::from_level(self.permissions)
+    }
+
+    fn new(kind: AclKind, id: Option<u32>, permission: ScanPermission) -> Self {
+        Self {
+            kind,
+            id,
+            name: None,
+            permissions: permission.level(),
+            owner: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PermissionsResponse {
+    #[serde(default)]
+    acls: Vec<AclEntry>,
+}
+
+impl NessusClient {
+    /// Fetch the access control list of a scan.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the scan cannot be fetched.
+    pub async fn scan_permissions(&self, scan_id: u32) -> Result<Vec<AclEntry>> {
+        let session = self.authenticate().await?;
+        let resp: PermissionsResponse = self
+            .get_json(&session, &format!("/permissions/scan/{}", scan_id))
+            .await?;
+        Ok(resp.acls)
+    }
+
+    /// Replace the access control list of a scan with `acls`.
+    ///
+    /// Entries not in `acls` lose their access, so callers usually start from
+    /// [`NessusClient::scan_permissions`] and modify the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the change is rejected.
+    pub async fn set_scan_permissions(&self, scan_id: u32, acls: &[AclEntry]) -> Result<()> {
+        let session = self.authenticate().await?;
+        let path = format!("/permissions/scan/{}", scan_id);
+        self.send(
+            self.request(Method::PUT, &session, &path)?
+                .json(&json!({ "acls": acls })),
+            &path,
+        )
+        .await?;
+        Ok(())
+    }
+}
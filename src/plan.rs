@@ -0,0 +1,201 @@
+//! Launch plans: batches whose scans each carry their own settings.
+//!
+//! A [`LaunchPlan`] lists [`ScanRequest`]s, each of which can override the
+//! targets and retries of the client's [`LaunchOptions`], and ask for the
+//! scan to be waited for and exported once launched. Plans are usually
+//! kept in files; the `nessus-cli launch --plan` file is a TOML rendering
+//! of this type.
+//!
+//! [`NessusClient::run_plan`] launches the plan's scans in parallel, then
+//! finishes the launched ones with [`NessusClient::finish_requests`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::{LaunchPlan, NessusClient, ScanExportFormat, ScanRequest};
+//!
+//! # async fn example(client: NessusClient) -> nessus_launcher::Result<()> {
+//! let plan = LaunchPlan {
+//!     scans: vec![
+//!         ScanRequest::new(5).with_alt_targets(["10.0.0.0/24"]).with_retries(1),
+//!         ScanRequest::new(8).with_export(ScanExportFormat::Nessus, "scan-8.nessus"),
+//!     ],
+//! };
+//! let report = client.run_plan(&plan).await?;
+//! println!("{} launched, {} finished", report.launch.succeeded.len(), report.finished.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`LaunchOptions`]: crate::LaunchOptions
+
+use crate::client::Session;
+use crate::launch::dedupe_requests;
+use crate::{LaunchReport, NessusClient, Result, ScanExport, ScanRequest, ScanStatus};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tracing::{error, info};
+
+/// A batch of scans, each with its own settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LaunchPlan {
+    /// The scans to launch, in order.
+    #[serde(default)]
+    pub scans: Vec<ScanRequest>,
+}
+
+/// What happened to a launched scan that was waited for or exported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinishedScan {
+    /// The scan.
+    pub scan_id: u32,
+    /// The status the scan finished with, unless waiting failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ScanStatus>,
+    /// The file the export was written to, if one was requested and written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export: Option<PathBuf>,
+    /// Why waiting or exporting failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The outcome of [`NessusClient::run_plan`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanReport {
+    /// How launching the scans went.
+    pub launch: LaunchReport,
+    /// The launched scans that were waited for or exported, in completion
+    /// order.
+    pub finished: Vec<FinishedScan>,
+}
+
+impl PlanReport {
+    /// Whether every scan launched and every wait and export succeeded.
+    pub fn is_success(&self) -> bool {
+        self.launch.is_success() && self.finished.iter().all(|f| f.error.is_none())
+    }
+}
+
+impl NessusClient {
+    /// Launch the plan's scans in parallel, then wait for and export the
+    /// launched ones as their requests ask.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::launch_scans_parallel`]. Failed waits and exports
+    /// are recorded in the report instead.
+    pub async fn run_plan(&self, plan: &LaunchPlan) -> Result<PlanReport> {
+        let requests = dedupe_requests(plan.scans.clone());
+        let launch = self.launch_requests(requests.clone()).await?;
+        let finished = self.finish_requests(&requests, &launch).await;
+        Ok(PlanReport { launch, finished })
+    }
+
+    /// Wait for, and export, the scans of `requests` that `report` shows as
+    /// launched and whose request asks for it.
+    ///
+    /// All of them share one session. They are waited for together with
+    /// [`NessusClient::wait_for_scans`], one scan listing per poll however
+    /// many scans there are, and then up to [`LaunchOptions::concurrency`]
+    /// exports run at once. A scan whose wait fails is reported on its own;
+    /// the others are still exported.
+    ///
+    /// Exports are written to their files as they are downloaded, so no
+    /// more than `concurrency` of them are held in memory at a time.
+    ///
+    /// [`LaunchOptions::concurrency`]: crate::LaunchOptions::concurrency
+    pub async fn finish_requests(
+        &self,
+        requests: &[ScanRequest],
+        report: &LaunchReport,
+    ) -> Vec<FinishedScan> {
+        let launched: HashSet<u32> = report.succeeded.iter().copied().collect();
+        let pending: Vec<&ScanRequest> = requests
+            .iter()
+            .filter(|r| r.needs_finishing() && launched.contains(&r.scan_id))
+            .collect();
+        if pending.is_empty() {
+            return Vec::new();
+        }
+
+        let session = match self.authenticate().await {
+            Ok(session) => session,
+            Err(e) => {
+                error!("Waiting for {} scan(s) failed: {}", pending.len(), e);
+                return pending
+                    .iter()
+                    .map(|r| FinishedScan {
+                        scan_id: r.scan_id,
+                        status: None,
+                        export: None,
+                        error: Some(e.to_string()),
+                    })
+                    .collect();
+            }
+        };
+        let scan_ids: Vec<u32> = pending.iter().map(|r| r.scan_id).collect();
+        let mut statuses = self.wait_for_scans_with(&session, &scan_ids).await;
+        let waited: Vec<_> = pending
+            .into_iter()
+            .filter_map(|r| Some((r, statuses.remove(&r.scan_id)?)))
+            .collect();
+
+        let session = &session;
+        stream::iter(waited)
+            .map(|(request, status)| async move {
+                let scan_id = request.scan_id;
+                let status = match status {
+                    Ok(status) => status,
+                    Err(e) => {
+                        error!("Waiting for scan {} failed: {}", scan_id, e);
+                        return FinishedScan {
+                            scan_id,
+                            status: None,
+                            export: None,
+                            error: Some(e.to_string()),
+                        };
+                    }
+                };
+                info!("Scan {} finished: {}", scan_id, status);
+                let mut finished = FinishedScan {
+                    scan_id,
+                    status: Some(status),
+                    export: None,
+                    error: None,
+                };
+                if let Some(export) = &request.export {
+                    match self.export_to_file(session, scan_id, export).await {
+                        Ok(()) => {
+                            info!("Exported scan {} to {}", scan_id, export.path.display());
+                            finished.export = Some(export.path.clone());
+                        }
+                        Err(e) => {
+                            error!("Exporting scan {} failed: {}", scan_id, e);
+                            finished.error = Some(e.to_string());
+                        }
+                    }
+                }
+                finished
+            })
+            .buffer_unordered(self.launch_options().concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    async fn export_to_file(
+        &self,
+        session: &Session,
+        scan_id: u32,
+        export: &ScanExport,
+    ) -> Result<()> {
+        let bytes = self
+            .export_scan_with(session, scan_id, export.format)
+            .await?;
+        tokio::fs::write(&export.path, bytes).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+//! Plugin rules: accepted-risk exclusions and severity recasts.
+//!
+//! This module extends [`NessusClient`] with the plugin rule endpoints, so
+//! automation can manage the overrides a security team has agreed on:
+//!
+//! - [`NessusClient::list_plugin_rules`]
+//! - [`NessusClient::create_plugin_rule`]
+//! - [`NessusClient::delete_plugin_rule`]
+//! - [`NessusClient::findings_with_plugin_rules`]
+//!
+//! A rule hides a plugin's findings ([`PluginRuleAction::Exclude`]) or
+//! changes their severity ([`PluginRuleAction::Recast`]), on one host or on
+//! all hosts, optionally until an expiry date. [`apply_plugin_rules`], or
+//! [`NessusClient::apply_plugin_rules`] with the scanner's current rules,
+//! applies them to fetched findings, so reports and [`GatePolicy`] checks
+//! see the same findings and severities as the Nessus UI.
+//! [`NessusClient::findings_with_plugin_rules`] fetches findings with the
+//! rules already applied.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::{NessusClient, NewPluginRule, PluginRuleAction, Severity};
+//!
+//! # async fn example(client: NessusClient) -> nessus_launcher::Result<()> {
+//! // Accept the risk of plugin 51192 on one host.
+//! let rule = client
+//!     .create_plugin_rule(
+//!         &NewPluginRule::new(51192, PluginRuleAction::Exclude).with_host("10.0.0.5"),
+//!     )
+//!     .await?;
+//!
+//! // Report and gate plugin 19506 as low everywhere.
+//! client
+//!     .create_plugin_rule(&NewPluginRule::new(
+//!         19506,
+//!         PluginRuleAction::Recast(Severity::Low),
+//!     ))
+//!     .await?;
+//!
+//! client.delete_plugin_rule(rule.id).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`GatePolicy`]: crate::GatePolicy
+
+use crate::{Finding, NessusClient, NessusError, Result, ResultFilter, Severity};
+use reqwest::Method;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Value, json};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What a plugin rule does to the findings it applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum PluginRuleAction {
+    /// Hide the findings, e.g. because the risk was accepted.
+    Exclude,
+    /// Report the findings with this severity instead.
+    Recast(Severity),
+}
+
+impl PluginRuleAction {
+    /// The rule type used by the API, e.g. `exclude` or `recast_high`.
+    pub fn as_type(self) -> &'static str {
+        match self {
+            PluginRuleAction::Exclude => "exclude",
+            PluginRuleAction::Recast(Severity::Info) => "recast_info",
+            PluginRuleAction::Recast(Severity::Low) => "recast_low",
+            PluginRuleAction::Recast(Severity::Medium) => "recast_medium",
+            PluginRuleAction::Recast(Severity::High) => "recast_high",
+            PluginRuleAction::Recast(Severity::Critical) => "recast_critical",
+        }
+    }
+}
+
+impl fmt::Display for PluginRuleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginRuleAction::Exclude => f.write_str("exclude"),
+            PluginRuleAction::Recast(severity) => write!(f, "recast to {severity}"),
+        }
+    }
+}
+
+impl TryFrom<String> for PluginRuleAction {
+    type Error = NessusError;
+
+    fn try_from(rule_type: String) -> Result<Self> {
+        match rule_type.strip_prefix("recast_") {
+            None if rule_type == "exclude" => Ok(PluginRuleAction::Exclude),
+            Some(severity) => Ok(PluginRuleAction::Recast(severity.parse()?)),
+            None => Err(NessusError::Other(format!(
+                "Unknown plugin rule type: {rule_type}"
+            ))),
+        }
+    }
+}
+
+impl From<PluginRuleAction> for String {
+    fn from(action: PluginRuleAction) -> Self {
+        action.as_type().to_string()
+    }
+}
+
+/// A plugin rule defined on the scanner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginRule {
+    /// Rule identifier.
+    pub id: u32,
+    /// Plugin whose findings the rule applies to.
+    #[serde(deserialize_with = "de_u32_or_string")]
+    pub plugin_id: u32,
+    /// Host the rule applies to, or `None` for every host.
+    #[serde(default, deserialize_with = "de_host")]
+    pub host: Option<String>,
+    /// What the rule does.
+    #[serde(rename = "type")]
+    pub action: PluginRuleAction,
+    /// When the rule expires, as a Unix timestamp, or `None` if it doesn't.
+    #[serde(default, rename = "date", deserialize_with = "de_timestamp")]
+    pub expires: Option<u64>,
+    /// User who created the rule.
+    #[serde(default)]
+    pub owner: Option<String>,
+}
+
+impl PluginRule {
+    /// Whether the rule applies to `finding`: it is for the finding's plugin
+    /// and for every host or the finding's host.
+    pub fn applies_to(&self, finding: &Finding) -> bool {
+        self.plugin_id == finding.plugin_id
+            && self
+                .host
+                .as_deref()
+                .is_none_or(|host| host.eq_ignore_ascii_case(&finding.hostname))
+    }
+
+    /// Whether the rule has expired at `now`.
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+}
+
+/// A plugin rule to create with [`NessusClient::create_plugin_rule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewPluginRule {
+    /// Plugin whose findings the rule applies to.
+    pub plugin_id: u32,
+    /// What the rule does.
+    pub action: PluginRuleAction,
+    /// Host the rule applies to, or `None` for every host.
+    pub host: Option<String>,
+    /// When the rule expires, as a Unix timestamp, or `None` if it doesn't.
+    pub expires: Option<u64>,
+}
+
+impl NewPluginRule {
+    /// A rule applying `action` to the findings of `plugin_id` on every
+    /// host, without expiry.
+    pub fn new(plugin_id: u32, action: PluginRuleAction) -> Self {
+        Self {
+            plugin_id,
+            action,
+            host: None,
+            expires: None,
+        }
+    }
+
+    /// Only apply the rule to `host`, a hostname or IP address.
+    pub fn with_host(mut self, host: impl Into<String>) -> Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    /// Let the rule expire at `expires`.
+    pub fn expiring_at(mut self, expires: SystemTime) -> Self {
+        self.expires = expires.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs());
+        self
+    }
+}
+
+/// Apply the unexpired `rules` to `findings` as of `now`: remove excluded
+/// findings and recast the severity of the others.
+///
+/// A rule for the finding's host takes precedence over a rule for every
+/// host; among rules equally specific, the first one listed wins.
+pub fn apply_plugin_rules(findings: &mut Vec<Finding>, rules: &[PluginRule], now: SystemTime) {
+    let active: Vec<&PluginRule> = rules.iter().filter(|r| !r.is_expired(now)).collect();
+    findings.retain_mut(|finding| {
+        let rule = active
+            .iter()
+            .filter(|r| r.applies_to(finding))
+            .min_by_key(|r| r.host.is_none());
+        match rule.map(|r| r.action) {
+            Some(PluginRuleAction::Exclude) => false,
+            Some(PluginRuleAction::Recast(severity)) => {
+                finding.severity = severity;
+                true
+            }
+            None => true,
+        }
+    });
+}
+
+#[derive(Deserialize)]
+struct PluginRulesResponse {
+    #[serde(default)]
+    plugin_rules: Option<Vec<PluginRule>>,
+}
+
+/// Accept plugin IDs sent as numbers or as strings.
+fn de_u32_or_string<'de, D: Deserializer<'de>>(d: D) -> std::result::Result<u32, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Num(u32),
+        Str(String),
+    }
+
+    match Raw::deserialize(d)? {
+        Raw::Num(n) => Ok(n),
+        Raw::Str(s) => s.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+/// Treat an empty or `*` host as every host.
+fn de_host<'de, D: Deserializer<'de>>(d: D) -> std::result::Result<Option<String>, D::Error> {
+    let host = Option::<String>::deserialize(d)?;
+    Ok(host.filter(|h| !h.is_empty() && h != "*"))
+}
+
+/// Accept expiry dates sent as numbers, numeric strings, empty strings, or
+/// `null`, where `0` and empty mean no expiry.
+fn de_timestamp<'de, D: Deserializer<'de>>(d: D) -> std::result::Result<Option<u64>, D::Error> {
+    match Option::<Value>::deserialize(d)? {
+        Some(Value::Number(n)) => Ok(n.as_u64().filter(|t| *t > 0)),
+        Some(Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(Value::String(s)) => s
+            .trim()
+            .parse::<u64>()
+            .map(|t| Some(t).filter(|t| *t > 0))
+            .map_err(serde::de::Error::custom),
+        _ => Ok(None),
+    }
+}
+
+impl NessusClient {
+    /// List the plugin rules defined on the scanner.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the request fails.
+    pub async fn list_plugin_rules(&self) -> Result<Vec<PluginRule>> {
+        let session = self.authenticate().await?;
+        let resp: PluginRulesResponse = self.get_json(&session, "/plugin-rules").await?;
+        Ok(resp.plugin_rules.unwrap_or_default())
+    }
+
+    /// Create a plugin rule and return it as stored by the scanner.
+    ///
+    /// The API does not return the new rule, so it is looked up afterwards:
+    /// the newest rule matching `rule`'s plugin, host, and action.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the rule cannot be
+    /// created, or [`NessusError::Other`] if it cannot be found afterwards.
+    pub async fn create_plugin_rule(&self, rule: &NewPluginRule) -> Result<PluginRule> {
+        let session = self.authenticate().await?;
+        let path = "/plugin-rules";
+        let body = json!({
+            "plugin_id": rule.plugin_id,
+            "type": rule.action.as_type(),
+            "host": rule.host.as_deref().unwrap_or(""),
+            "date": rule.expires,
+        });
+        self.send(
+            self.request(Method::POST, &session, path)?.json(&body),
+            path,
+        )
+        .await?;
+
+        self.list_plugin_rules()
+            .await?
+            .into_iter()
+            .filter(|r| {
+                r.plugin_id == rule.plugin_id
+                    && r.action == rule.action
+                    && r.host.as_deref().map(str::to_ascii_lowercase)
+                        == rule.host.as_deref().map(str::to_ascii_lowercase)
+            })
+            .max_by_key(|r| r.id)
+            .ok_or_else(|| {
+                NessusError::Other(format!(
+                    "Plugin rule for plugin {} was not found after creating it",
+                    rule.plugin_id
+                ))
+            })
+    }
+
+    /// Delete the plugin rule with the given ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the rule cannot be deleted.
+    pub async fn delete_plugin_rule(&self, rule_id: u32) -> Result<()> {
+        let session = self.authenticate().await?;
+        let path = format!("/plugin-rules/{}", rule_id);
+        self.send(self.request(Method::DELETE, &session, &path)?, &path)
+            .await?;
+        Ok(())
+    }
+
+    /// Apply the scanner's current plugin rules to `findings`; see
+    /// [`apply_plugin_rules`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rules cannot be listed.
+    pub async fn apply_plugin_rules(&self, findings: &mut Vec<Finding>) -> Result<()> {
+        let rules = self.list_plugin_rules().await?;
+        apply_plugin_rules(findings, &rules, SystemTime::now());
+        Ok(())
+    }
+
+    /// Fetch the findings of a scan with the scanner's current plugin rules
+    /// applied.
+    ///
+    /// `filter`'s minimum severity is applied after the rules rather than on
+    /// the server, so findings are kept or dropped by their recast severity.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::findings`] and [`NessusClient::list_plugin_rules`].
+    pub async fn findings_with_plugin_rules(
+        &self,
+        scan_id: u32,
+        filter: &ResultFilter,
+    ) -> Result<Vec<Finding>> {
+        let unfiltered = ResultFilter {
+            min_severity: None,
+            ..filter.clone()
+        };
+        let mut findings = self.findings(scan_id, &unfiltered).await?;
+        self.apply_plugin_rules(&mut findings).await?;
+        if let Some(min) = filter.min_severity {
+            findings.retain(|f| f.severity >= min);
+        }
+        Ok(findings)
+    }
+}
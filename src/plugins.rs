@@ -0,0 +1,159 @@
+//! Plugin metadata.
+//!
+//! This module extends [`NessusClient`] with read access to the plugin
+//! catalogue, so findings can be enriched with descriptions, solutions,
+//! and references:
+//!
+//! - [`NessusClient::list_plugin_families`]
+//! - [`NessusClient::plugins_in_family`]
+//! - [`NessusClient::plugin_details`]
+
+use crate::{NessusClient, Result};
+use serde::{Deserialize, Serialize};
+
+/// A plugin family, e.g. `Windows : Microsoft Bulletins`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginFamily {
+    /// Family identifier.
+    pub id: u32,
+    /// Family name.
+    pub name: String,
+    /// Number of plugins in the family.
+    #[serde(default)]
+    pub count: u32,
+}
+
+/// A plugin as listed within its family.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginSummary {
+    /// Plugin identifier.
+    pub id: u32,
+    /// Plugin name.
+    pub name: String,
+}
+
+/// A single `name = value` attribute of a plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginAttribute {
+    /// Attribute name, e.g. `description` or `cve`.
+    pub attribute_name: String,
+    /// Attribute value.
+    pub attribute_value: String,
+}
+
+/// Full metadata for one plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginDetails {
+    /// Plugin identifier.
+    pub id: u32,
+    /// Plugin name.
+    pub name: String,
+    /// Name of the family the plugin belongs to.
+    #[serde(default)]
+    pub family_name: String,
+    /// Raw plugin attributes. Some names, such as `cve`, may repeat.
+    #[serde(default)]
+    pub attributes: Vec<PluginAttribute>,
+}
+
+impl PluginDetails {
+    /// The first value of the attribute called `name`, if present.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.attribute_name == name)
+            .map(|a| a.attribute_value.as_str())
+    }
+
+    /// All values of the attribute called `name`.
+    pub fn attributes_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.attributes
+            .iter()
+            .filter(move |a| a.attribute_name == name)
+            .map(|a| a.attribute_value.as_str())
+    }
+
+    /// One-line summary of the issue.
+    pub fn synopsis(&self) -> Option<&str> {
+        self.attribute("synopsis")
+    }
+
+    /// Full description of the issue.
+    pub fn description(&self) -> Option<&str> {
+        self.attribute("description")
+    }
+
+    /// Recommended remediation.
+    pub fn solution(&self) -> Option<&str> {
+        self.attribute("solution")
+    }
+
+    /// Nessus' risk factor, e.g. `High`.
+    pub fn risk_factor(&self) -> Option<&str> {
+        self.attribute("risk_factor")
+    }
+
+    /// CVE identifiers referenced by the plugin.
+    pub fn cves(&self) -> Vec<String> {
+        self.attributes_named("cve").map(str::to_string).collect()
+    }
+
+    /// Reference URLs from the `see_also` attribute, one per line.
+    pub fn references(&self) -> Vec<String> {
+        self.attributes_named("see_also")
+            .flat_map(str::lines)
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+struct FamiliesResponse {
+    #[serde(default)]
+    families: Vec<PluginFamily>,
+}
+
+#[derive(Deserialize)]
+struct FamilyResponse {
+    #[serde(default)]
+    plugins: Vec<PluginSummary>,
+}
+
+impl NessusClient {
+    /// List all plugin families.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the request fails.
+    pub async fn list_plugin_families(&self) -> Result<Vec<PluginFamily>> {
+        let session = self.authenticate().await?;
+        let resp: FamiliesResponse = self.get_json(&session, "/plugins/families").await?;
+        Ok(resp.families)
+    }
+
+    /// List the plugins in the family with the given ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the family cannot be fetched.
+    pub async fn plugins_in_family(&self, family_id: u32) -> Result<Vec<PluginSummary>> {
+        let session = self.authenticate().await?;
+        let resp: FamilyResponse = self
+            .get_json(&session, &format!("/plugins/families/{}", family_id))
+            .await?;
+        Ok(resp.plugins)
+    }
+
+    /// Fetch full metadata for a plugin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the plugin cannot be fetched.
+    pub async fn plugin_details(&self, plugin_id: u32) -> Result<PluginDetails> {
+        let session = self.authenticate().await?;
+        self.get_json(&session, &format!("/plugins/plugin/{}", plugin_id))
+            .await
+    }
+}
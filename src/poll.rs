@@ -0,0 +1,133 @@
+//! Polling configuration shared by every waiting and watch feature.
+//!
+//! Instead of sleeping for a fixed period between status checks, waiting
+//! operations consult a [`PollStrategy`], which controls:
+//!
+//! - The initial interval between polls
+//! - A backoff factor applied after every unsuccessful poll
+//! - An upper bound on the interval between polls
+//! - An optional maximum total time to keep polling
+//!
+//! ## Example
+//!
+//! ```
+//! use nessus_launcher::PollStrategy;
+//! use std::time::Duration;
+//!
+//! // Poll every 5s, slowing down to once a minute, for at most two hours.
+//! let strategy = PollStrategy::new(Duration::from_secs(5))
+//!     .with_backoff(1.5)
+//!     .with_max_interval(Duration::from_secs(60))
+//!     .with_max_duration(Duration::from_secs(2 * 60 * 60));
+//! # let _ = strategy;
+//! ```
+
+use crate::{NessusError, Result};
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::{Instant, sleep};
+
+/// Controls how often, and for how long, a condition is polled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollStrategy {
+    /// Delay before the first re-poll.
+    pub interval: Duration,
+    /// Multiplier applied to the delay after every poll (`1.0` disables backoff).
+    pub backoff: f64,
+    /// Upper bound for the delay between two polls.
+    pub max_interval: Duration,
+    /// Give up after this much time has elapsed. `None` polls forever.
+    pub max_duration: Option<Duration>,
+}
+
+impl Default for PollStrategy {
+    /// Poll every 10 seconds, backing off up to once a minute, without a deadline.
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(10),
+            backoff: 1.5,
+            max_interval: Duration::from_secs(60),
+            max_duration: None,
+        }
+    }
+}
+
+impl PollStrategy {
+    /// Create a fixed-interval strategy with no backoff and no deadline.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            backoff: 1.0,
+            max_interval: interval,
+            max_duration: None,
+        }
+    }
+
+    /// Set the multiplier applied to the delay after every poll.
+    ///
+    /// Values below `1.0`, and NaN, are treated as `1.0`.
+    pub fn with_backoff(mut self, backoff: f64) -> Self {
+        self.backoff = backoff.max(1.0);
+        self
+    }
+
+    /// Set the upper bound for the delay between two polls.
+    pub fn with_max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// Set the maximum total time to keep polling.
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Compute the delay that follows `delay` according to the backoff settings.
+    ///
+    /// A delay too large to represent, e.g. with an infinite backoff, is
+    /// capped at the maximum interval like any other.
+    pub fn next_delay(&self, delay: Duration) -> Duration {
+        let max = self.max_interval.max(self.interval);
+        Duration::try_from_secs_f64(delay.as_secs_f64() * self.backoff.max(1.0))
+            .map_or(max, |next| next.min(max))
+    }
+
+    /// Repeatedly call `check` until it yields `Some(value)`.
+    ///
+    /// `check` is invoked immediately, then again after each delay produced by
+    /// the strategy. Errors returned by `check` abort polling.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error produced by `check`, or [`NessusError::Other`]
+    /// if `max_duration` elapses before the condition is met.
+    pub async fn poll<T, F, Fut>(&self, mut check: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Option<T>>>,
+    {
+        let started = Instant::now();
+        let mut delay = self.interval;
+
+        loop {
+            if let Some(value) = check().await? {
+                return Ok(value);
+            }
+
+            if let Some(max) = self.max_duration {
+                let elapsed = started.elapsed();
+                if elapsed >= max {
+                    return Err(NessusError::Other(format!(
+                        "Polling timed out after {}s",
+                        max.as_secs()
+                    )));
+                }
+                delay = delay.min(max - elapsed);
+            }
+
+            sleep(delay).await;
+            delay = self.next_delay(delay);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! GitHub Actions workflow commands and job summaries.
+//!
+//! [`Annotation`]s render as `::error` / `::warning` workflow commands,
+//! which GitHub shows on the run and pull request. The summary functions
+//! produce Markdown for the file named by `GITHUB_STEP_SUMMARY`.
+//!
+//! ## Example
+//!
+//! ```
+//! use nessus_launcher::report::github::{Annotation, AnnotationLevel};
+//!
+//! let annotation = Annotation::new(AnnotationLevel::Error, "Nessus gate", "severity critical");
+//! assert_eq!(annotation.to_string(), "::error title=Nessus gate::severity critical");
+//! ```
+
+use crate::report::SeverityCounts;
+use crate::{GateBreach, GateReport, LaunchFailure, LaunchReport};
+use std::fmt::{self, Write};
+
+/// Severity of a workflow annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationLevel {
+    /// `::notice`
+    Notice,
+    /// `::warning`
+    Warning,
+    /// `::error`
+    Error,
+}
+
+impl AnnotationLevel {
+    /// The workflow command name.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnnotationLevel::Notice => "notice",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Error => "error",
+        }
+    }
+}
+
+/// A single workflow annotation command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    /// Annotation level.
+    pub level: AnnotationLevel,
+    /// Short title shown above the message.
+    pub title: String,
+    /// Annotation message.
+    pub message: String,
+}
+
+impl Annotation {
+    /// Create an annotation.
+    pub fn new(
+        level: AnnotationLevel,
+        title: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            level,
+            title: title.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl From<&GateBreach> for Annotation {
+    /// Gate breaches fail the step, so they are reported as errors.
+    fn from(breach: &GateBreach) -> Self {
+        Self::new(
+            AnnotationLevel::Error,
+            "Nessus gate breach",
+            breach.to_string(),
+        )
+    }
+}
+
+impl From<&LaunchFailure> for Annotation {
+    /// Launch failures don't fail the step, so they are reported as warnings.
+    fn from(failure: &LaunchFailure) -> Self {
+        Self::new(
+            AnnotationLevel::Warning,
+            format!("Nessus scan {} failed to launch", failure.scan_id),
+            failure.error.clone(),
+        )
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "::{} title={}::{}",
+            self.level.as_str(),
+            escape_property(&self.title),
+            escape_data(&self.message)
+        )
+    }
+}
+
+/// Escape a workflow command message.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Job summary Markdown for a batch launch.
+pub fn launch_summary(report: &LaunchReport) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "## Nessus scans launched\n");
+    let _ = writeln!(out, "| Scan | Status | Details |");
+    let _ = writeln!(out, "|---:|---|---|");
+    for scan_id in &report.succeeded {
+        let _ = writeln!(out, "| {scan_id} | ✅ launched | |");
+    }
+    for failure in &report.failed {
+        let _ = writeln!(
+            out,
+            "| {} | ❌ failed | {} |",
+            failure.scan_id,
+            failure.error.replace('|', "\\|")
+        );
+    }
+    for scan_id in &report.skipped {
+        let _ = writeln!(out, "| {scan_id} | ⏭️ skipped | batch stopped early |");
+    }
+    let _ = writeln!(
+        out,
+        "\n{} of {} scan(s) launched.",
+        report.succeeded.len(),
+        report.total()
+    );
+
+    out
+}
+
+/// Job summary Markdown for the findings of a scan and their gate outcome.
+pub fn results_summary(title: &str, counts: &SeverityCounts, gate: &GateReport) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "## {title}\n");
+    let _ = writeln!(out, "| Critical | High | Medium | Low | Info | Total |");
+    let _ = writeln!(out, "|---:|---:|---:|---:|---:|---:|");
+    let _ = writeln!(
+        out,
+        "| {} | {} | {} | {} | {} | {} |\n",
+        counts.critical,
+        counts.high,
+        counts.medium,
+        counts.low,
+        counts.info,
+        counts.total()
+    );
+
+    if gate.passed() {
+        let _ = writeln!(out, "✅ Gate passed.");
+    } else {
+        let _ = writeln!(
+            out,
+            "❌ Gate failed with {} breach(es).",
+            gate.breaches.len()
+        );
+    }
+
+    out
+}
@@ -0,0 +1,147 @@
+//! Standalone HTML reports rendered with [Tera](https://keats.github.io/tera/).
+//!
+//! The built-in template produces a single self-contained page with a
+//! severity summary, a CSS bar chart, and one table per host. A custom
+//! Tera template can be supplied instead; it receives the following
+//! context:
+//!
+//! - `title` — the report title
+//! - `counts` — `{critical, high, medium, low, info}` finding counts
+//! - `total` — total number of findings
+//! - `chart` — one `{label, count, percent}` entry per severity, most severe first
+//! - `hosts` — per-host sections with `hostname`, `counts`, and `findings`
+//!
+//! With the `history` feature, [`render_trend`] renders a
+//! [`Trend`](crate::history::Trend) as a page with a stacked bar per run.
+//!
+//! ## Example
+//!
+//! ```
+//! use nessus_launcher::report::{Report, html::HtmlRenderer};
+//!
+//! let report = Report::new("Weekly scan", Vec::new());
+//! let page = HtmlRenderer::new().render(&report).unwrap();
+//! assert!(page.contains("Weekly scan"));
+//! ```
+
+use crate::report::Report;
+use crate::{NessusError, Result, Severity};
+use serde::Serialize;
+use std::path::Path;
+use tera::{Context, Tera};
+
+/// The template used when no custom template is configured.
+pub const DEFAULT_TEMPLATE: &str = include_str!("templates/report.html.tera");
+
+/// The template used by [`render_trend`].
+#[cfg(feature = "history")]
+pub const TREND_TEMPLATE: &str = include_str!("templates/trend.html.tera");
+
+#[derive(Serialize)]
+struct ChartBar {
+    label: String,
+    count: usize,
+    percent: f64,
+}
+
+/// Renders a [`Report`] as HTML.
+#[derive(Debug, Clone)]
+pub struct HtmlRenderer {
+    template: String,
+}
+
+impl Default for HtmlRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HtmlRenderer {
+    /// Create a renderer using [`DEFAULT_TEMPLATE`].
+    pub fn new() -> Self {
+        Self {
+            template: DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+
+    /// Use a custom Tera template.
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// Use a custom Tera template read from `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Io`] if the file cannot be read.
+    pub fn with_template_file(self, path: impl AsRef<Path>) -> Result<Self> {
+        let template = std::fs::read_to_string(path)?;
+        Ok(self.with_template(template))
+    }
+
+    /// Render `report`. Values are HTML-escaped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if the template is invalid or fails to render.
+    pub fn render(&self, report: &Report) -> Result<String> {
+        let total = report.counts.total();
+        let chart: Vec<ChartBar> = Severity::ALL
+            .iter()
+            .rev()
+            .map(|&severity| {
+                let count = report.counts.get(severity);
+                ChartBar {
+                    label: severity.to_string(),
+                    count,
+                    percent: if total == 0 {
+                        0.0
+                    } else {
+                        count as f64 * 100.0 / total as f64
+                    },
+                }
+            })
+            .collect();
+
+        let mut context = Context::new();
+        context.insert("title", &report.title);
+        context.insert("counts", &report.counts);
+        context.insert("total", &total);
+        context.insert("chart", &chart);
+        context.insert("hosts", &report.hosts);
+
+        Tera::one_off(&self.template, &context, true)
+            .map_err(|e| NessusError::Other(format!("Failed to render HTML report: {e:?}")))
+    }
+}
+
+/// Render `trend` as a standalone HTML page. Values are HTML-escaped.
+///
+/// # Errors
+///
+/// Returns [`NessusError::Other`] if rendering fails.
+#[cfg(feature = "history")]
+pub fn render_trend(trend: &crate::history::Trend) -> Result<String> {
+    let max_total = trend
+        .points
+        .iter()
+        .map(|p| p.counts.total())
+        .max()
+        .unwrap_or(0);
+
+    let mut context = Context::new();
+    context.insert("trend", trend);
+    // Width in rem of one finding, so the longest bar is 30rem wide.
+    context.insert(
+        "scale",
+        &if max_total == 0 {
+            0.0
+        } else {
+            30.0 / max_total as f64
+        },
+    );
+
+    Tera::one_off(TREND_TEMPLATE, &context, true)
+        .map_err(|e| NessusError::Other(format!("Failed to render HTML trend: {e:?}")))
+}
@@ -0,0 +1,164 @@
+//! Markdown reports for wikis and pull requests.
+//!
+//! The rendered document contains:
+//!
+//! - A severity summary table
+//! - The top N most severe findings
+//! - An optional diff against a previous run
+//! - One section per host
+//!
+//! ## Example
+//!
+//! ```
+//! use nessus_launcher::report::{Report, markdown::MarkdownRenderer};
+//!
+//! let report = Report::new("Weekly scan", Vec::new());
+//! let md = MarkdownRenderer::new().with_top(5).render(&report);
+//! assert!(md.starts_with("# Weekly scan"));
+//! ```
+
+use crate::Finding;
+use crate::report::{FindingsDiff, Report};
+use std::fmt::Write;
+
+/// Renders a [`Report`] as Markdown.
+#[derive(Debug, Clone)]
+pub struct MarkdownRenderer {
+    top: usize,
+    previous: Option<Vec<Finding>>,
+}
+
+impl Default for MarkdownRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownRenderer {
+    /// Create a renderer listing the top 10 findings, without a diff section.
+    pub fn new() -> Self {
+        Self {
+            top: 10,
+            previous: None,
+        }
+    }
+
+    /// List the `top` most severe findings (0 omits the section).
+    pub fn with_top(mut self, top: usize) -> Self {
+        self.top = top;
+        self
+    }
+
+    /// Add a section comparing the report against `previous` findings.
+    pub fn with_previous(mut self, previous: Vec<Finding>) -> Self {
+        self.previous = Some(previous);
+        self
+    }
+
+    /// Render `report`.
+    pub fn render(&self, report: &Report) -> String {
+        let mut out = String::new();
+        let c = &report.counts;
+
+        let _ = writeln!(out, "# {}\n", escape(&report.title));
+        let _ = writeln!(out, "## Summary\n");
+        let _ = writeln!(out, "| Critical | High | Medium | Low | Info | Total |");
+        let _ = writeln!(out, "|---:|---:|---:|---:|---:|---:|");
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} |\n",
+            c.critical,
+            c.high,
+            c.medium,
+            c.low,
+            c.info,
+            c.total()
+        );
+
+        if self.top > 0 {
+            let findings = report.findings();
+            let _ = writeln!(out, "## Top {} findings\n", self.top.min(findings.len()));
+            write_table(&mut out, findings.into_iter().take(self.top), true);
+        }
+
+        if let Some(previous) = &self.previous {
+            let current: Vec<Finding> = report.findings().into_iter().cloned().collect();
+            let diff = FindingsDiff::between(previous, &current);
+
+            let _ = writeln!(out, "## Changes since previous run\n");
+            let _ = writeln!(
+                out,
+                "{} new, {} fixed, {} unchanged.\n",
+                diff.new.len(),
+                diff.fixed.len(),
+                diff.unchanged
+            );
+            if !diff.new.is_empty() {
+                let _ = writeln!(out, "### New\n");
+                write_table(&mut out, diff.new.iter(), true);
+            }
+            if !diff.fixed.is_empty() {
+                let _ = writeln!(out, "### Fixed\n");
+                write_table(&mut out, diff.fixed.iter(), true);
+            }
+        }
+
+        let _ = writeln!(out, "## Hosts\n");
+        for host in &report.hosts {
+            let hc = &host.counts;
+            let _ = writeln!(out, "### {}\n", escape(&host.hostname));
+            let _ = writeln!(
+                out,
+                "Critical: {}, High: {}, Medium: {}, Low: {}, Info: {}\n",
+                hc.critical, hc.high, hc.medium, hc.low, hc.info
+            );
+            write_table(&mut out, host.findings.iter(), false);
+        }
+
+        out
+    }
+}
+
+fn write_table<'a>(out: &mut String, findings: impl Iterator<Item = &'a Finding>, with_host: bool) {
+    if with_host {
+        let _ = writeln!(out, "| Severity | Host | Plugin | Name | CVEs |");
+        let _ = writeln!(out, "|---|---|---:|---|---|");
+    } else {
+        let _ = writeln!(out, "| Severity | Plugin | Name | CVEs |");
+        let _ = writeln!(out, "|---|---:|---|---|");
+    }
+
+    for f in findings {
+        let mut severity = f.severity.to_string();
+        if f.kev {
+            severity.push_str(" (KEV)");
+        }
+        let cves = escape(&f.cves.join(", "));
+        if with_host {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                severity,
+                escape(&f.hostname),
+                f.plugin_id,
+                escape(&f.plugin_name),
+                cves
+            );
+        } else {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                severity,
+                f.plugin_id,
+                escape(&f.plugin_name),
+                cves
+            );
+        }
+    }
+    out.push('\n');
+}
+
+/// Escape characters that would break a Markdown table cell.
+fn escape(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
@@ -0,0 +1,192 @@
+//! Report generation from fetched or exported findings.
+//!
+//! A [`Report`] groups findings into a severity summary and per-host
+//! sections. Renderers for specific formats live in submodules:
+//!
+//! - `html` — [`html`]: a standalone HTML page (feature `html`)
+//! - [`markdown`]: Markdown for wikis and pull requests
+//! - [`github`]: GitHub Actions annotations and job summaries
+//!
+//! [`FindingsDiff`] compares two runs to show new and fixed findings.
+//!
+//! ## Example
+//!
+//! ```
+//! use nessus_launcher::report::Report;
+//!
+//! let report = Report::new("Weekly scan", Vec::new());
+//! assert_eq!(report.counts.total(), 0);
+//! ```
+
+use crate::{Finding, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+pub mod github;
+#[cfg(feature = "html")]
+pub mod html;
+pub mod markdown;
+
+/// Number of findings per severity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityCounts {
+    /// Critical findings.
+    pub critical: usize,
+    /// High findings.
+    pub high: usize,
+    /// Medium findings.
+    pub medium: usize,
+    /// Low findings.
+    pub low: usize,
+    /// Informational findings.
+    pub info: usize,
+}
+
+impl SeverityCounts {
+    /// Count `findings` by severity.
+    pub fn from_findings<'a>(findings: impl IntoIterator<Item = &'a Finding>) -> Self {
+        Self::from_severities(findings.into_iter().map(|f| f.severity))
+    }
+
+    /// Count occurrences of each severity.
+    pub fn from_severities(severities: impl IntoIterator<Item = Severity>) -> Self {
+        let mut counts = Self::default();
+        for severity in severities {
+            *counts.get_mut(severity) += 1;
+        }
+        counts
+    }
+
+    /// The count for `severity`.
+    pub fn get(&self, severity: Severity) -> usize {
+        match severity {
+            Severity::Critical => self.critical,
+            Severity::High => self.high,
+            Severity::Medium => self.medium,
+            Severity::Low => self.low,
+            Severity::Info => self.info,
+        }
+    }
+
+    fn get_mut(&mut self, severity: Severity) -> &mut usize {
+        match severity {
+            Severity::Critical => &mut self.critical,
+            Severity::High => &mut self.high,
+            Severity::Medium => &mut self.medium,
+            Severity::Low => &mut self.low,
+            Severity::Info => &mut self.info,
+        }
+    }
+
+    /// Total number of findings.
+    pub fn total(&self) -> usize {
+        self.critical + self.high + self.medium + self.low + self.info
+    }
+}
+
+/// Findings of a single host, most severe first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostSection {
+    /// Hostname or IP address.
+    pub hostname: String,
+    /// Number of findings per severity on this host.
+    pub counts: SeverityCounts,
+    /// The host's findings, most severe first.
+    pub findings: Vec<Finding>,
+}
+
+/// Findings prepared for rendering.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    /// Report title.
+    pub title: String,
+    /// Number of findings per severity across all hosts.
+    pub counts: SeverityCounts,
+    /// Per-host sections, ordered by hostname.
+    pub hosts: Vec<HostSection>,
+}
+
+impl Report {
+    /// Build a report from `findings`.
+    pub fn new(title: impl Into<String>, findings: Vec<Finding>) -> Self {
+        let counts = SeverityCounts::from_findings(&findings);
+
+        let mut by_host: BTreeMap<String, Vec<Finding>> = BTreeMap::new();
+        for finding in findings {
+            by_host
+                .entry(finding.hostname.clone())
+                .or_default()
+                .push(finding);
+        }
+
+        let hosts = by_host
+            .into_iter()
+            .map(|(hostname, mut findings)| {
+                sort_by_severity(&mut findings);
+                HostSection {
+                    hostname,
+                    counts: SeverityCounts::from_findings(&findings),
+                    findings,
+                }
+            })
+            .collect();
+
+        Self {
+            title: title.into(),
+            counts,
+            hosts,
+        }
+    }
+
+    /// All findings, most severe first.
+    pub fn findings(&self) -> Vec<&Finding> {
+        let mut all: Vec<&Finding> = self.hosts.iter().flat_map(|h| &h.findings).collect();
+        all.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.plugin_id.cmp(&b.plugin_id)));
+        all
+    }
+}
+
+/// Differences between the findings of two runs.
+///
+/// Findings are matched by host and plugin.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FindingsDiff {
+    /// Findings present now but not in the previous run.
+    pub new: Vec<Finding>,
+    /// Findings present in the previous run but not now.
+    pub fixed: Vec<Finding>,
+    /// Number of findings present in both runs.
+    pub unchanged: usize,
+}
+
+impl FindingsDiff {
+    /// Compare `current` findings against `previous` ones.
+    pub fn between(previous: &[Finding], current: &[Finding]) -> Self {
+        let previous_keys: HashSet<(&str, u32)> = previous.iter().map(Finding::key).collect();
+        let current_keys: HashSet<(&str, u32)> = current.iter().map(Finding::key).collect();
+
+        let mut new: Vec<Finding> = current
+            .iter()
+            .filter(|f| !previous_keys.contains(&f.key()))
+            .cloned()
+            .collect();
+        let mut fixed: Vec<Finding> = previous
+            .iter()
+            .filter(|f| !current_keys.contains(&f.key()))
+            .cloned()
+            .collect();
+        sort_by_severity(&mut new);
+        sort_by_severity(&mut fixed);
+
+        Self {
+            unchanged: current.len() - new.len(),
+            new,
+            fixed,
+        }
+    }
+}
+
+/// Sort findings most severe first, then by plugin ID.
+fn sort_by_severity(findings: &mut [Finding]) {
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.plugin_id.cmp(&b.plugin_id)));
+}
@@ -0,0 +1,141 @@
+//! Decoding of API responses.
+//!
+//! A bare `serde_json` error such as "missing field `token`" says nothing
+//! about what the server actually sent. Responses are therefore decoded
+//! with [`decode`], which on failure returns
+//! [`NessusError::UnexpectedResponse`] holding the request, a snippet of
+//! the body with secrets redacted, and, where the body is recognizable, a hint at the likely
+//! cause, e.g. an HTML login page served by a proxy in front of Nessus.
+//!
+//! Decoding is lenient where that is harmless: a leading byte order mark
+//! is ignored, and an empty body decodes like `null`, so endpoints that
+//! answer with no content can be decoded as `()` or `Option<T>`.
+
+use crate::{NessusError, Result, trace};
+use reqwest::header::HeaderMap;
+use reqwest::{Body, Response, StatusCode, Version};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fmt;
+
+/// Maximum number of characters of the body kept in an error.
+const SNIPPET_LEN: usize = 200;
+
+/// A response that could not be decoded as expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnexpectedResponse {
+    /// The request, e.g. `GET /scans`.
+    pub request: String,
+    /// Why decoding failed.
+    pub error: String,
+    /// The start of the body, with secrets redacted as in HTTP traces and
+    /// whitespace collapsed.
+    pub snippet: String,
+    /// The likely cause, if the body is recognizable.
+    pub hint: Option<String>,
+}
+
+impl UnexpectedResponse {
+    /// Describe `body`, the response to `request`, which failed to decode
+    /// with `error`.
+    pub fn new(request: impl Into<String>, error: impl Into<String>, body: &str) -> Self {
+        Self {
+            request: request.into(),
+            error: error.into(),
+            snippet: snippet(&trace::redact(body.as_bytes())),
+            hint: hint(body),
+        }
+    }
+}
+
+impl fmt::Display for UnexpectedResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.request, self.error)?;
+        if let Some(hint) = &self.hint {
+            write!(f, "; {hint}")?;
+        }
+        if self.snippet.is_empty() {
+            write!(f, " (empty body)")
+        } else {
+            write!(f, " (body: {})", self.snippet)
+        }
+    }
+}
+
+/// Decode `body`, the response to `request` (e.g. `GET /scans`), as JSON.
+///
+/// # Errors
+///
+/// Returns [`NessusError::UnexpectedResponse`] if the body does not decode
+/// as `T`.
+pub(crate) fn decode<T: DeserializeOwned>(request: &str, body: &str) -> Result<T> {
+    let trimmed = body.trim_start_matches('\u{feff}').trim();
+    let json = if trimmed.is_empty() { "null" } else { trimmed };
+
+    serde_json::from_str(json).map_err(|e| {
+        NessusError::UnexpectedResponse(UnexpectedResponse::new(request, e.to_string(), body))
+    })
+}
+
+/// A response with `status`, `version`, `headers`, and `body`, for handing
+/// on a response whose body has already been read.
+///
+/// # Errors
+///
+/// Returns [`NessusError::Other`] if the parts do not form a response.
+pub(crate) fn rebuild(
+    status: StatusCode,
+    version: Version,
+    headers: HeaderMap,
+    body: impl Into<Body>,
+) -> Result<Response> {
+    let mut rebuilt = http::Response::builder().status(status).version(version);
+    if let Some(rebuilt_headers) = rebuilt.headers_mut() {
+        *rebuilt_headers = headers;
+    }
+    let rebuilt = rebuilt
+        .body(body.into())
+        .map_err(|e| NessusError::Other(format!("Failed to rebuild response: {e}")))?;
+    Ok(Response::from(rebuilt))
+}
+
+/// The likely cause of `body` not being the expected JSON, if recognizable.
+pub(crate) fn hint(body: &str) -> Option<String> {
+    let body = body.trim_start_matches('\u{feff}').trim();
+    let lower = body.to_lowercase();
+
+    if body.is_empty() {
+        return Some("the server sent an empty response".into());
+    }
+    if lower.starts_with('<') {
+        let hint = if ["login", "log in", "sign in", "password"]
+            .iter()
+            .any(|word| lower.contains(word))
+        {
+            "response looks like an HTML login page; check NESSUS_HOST and any proxy or \
+             single sign-on in front of Nessus"
+        } else {
+            "response is HTML, not JSON; check that NESSUS_HOST points at the Nessus \
+             API (usually port 8834) rather than a proxy or web page"
+        };
+        return Some(hint.into());
+    }
+
+    let value: Value = serde_json::from_str(body).ok()?;
+    if let Some(error) = value.get("error").and_then(Value::as_str) {
+        return Some(format!("the server reported: {error}"));
+    }
+    Some(
+        "the response is JSON of an unexpected shape; the Nessus version may not be supported"
+            .into(),
+    )
+}
+
+/// The start of `body`, with whitespace collapsed.
+fn snippet(body: &str) -> String {
+    let collapsed = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    match collapsed.char_indices().nth(SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &collapsed[..end]),
+        None => collapsed,
+    }
+}
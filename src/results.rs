@@ -0,0 +1,534 @@
+//! Typed access to scan results.
+//!
+//! This module extends [`NessusClient`] with:
+//!
+//! - Listing the hosts of a scan with per-severity counts
+//! - Fetching individual findings, filtered server-side with a [`ResultFilter`]
+//! - Retrieving configuration-audit (compliance) results per host
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::{NessusClient, NessusConfig, ResultFilter, Result, Severity};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let client = NessusClient::new(NessusConfig::from_env()?)?;
+//!
+//!     // Only download high and critical findings.
+//!     let filter = ResultFilter::new().min_severity(Severity::High);
+//!     for finding in client.findings(5, &filter).await? {
+//!         println!("{} {} {}", finding.hostname, finding.severity, finding.plugin_name);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::client::Session;
+use crate::enrich::CveDetails;
+use crate::{NessusClient, NessusError, PluginDetails, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Severity of a finding, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Informational, severity index 0.
+    Info,
+    /// Low, severity index 1.
+    Low,
+    /// Medium, severity index 2.
+    Medium,
+    /// High, severity index 3.
+    High,
+    /// Critical, severity index 4.
+    Critical,
+}
+
+impl Severity {
+    /// All severities, from least to most severe.
+    pub const ALL: [Severity; 5] = [
+        Severity::Info,
+        Severity::Low,
+        Severity::Medium,
+        Severity::High,
+        Severity::Critical,
+    ];
+
+    /// Map a Nessus severity index (0-4) to a severity.
+    ///
+    /// Indexes above 4 are treated as critical.
+    pub fn from_index(index: u8) -> Self {
+        match index {
+            0 => Severity::Info,
+            1 => Severity::Low,
+            2 => Severity::Medium,
+            3 => Severity::High,
+            _ => Severity::Critical,
+        }
+    }
+
+    /// The Nessus severity index (0-4) of this severity.
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for Severity {
+    type Err = NessusError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "info" | "informational" | "none" | "0" => Ok(Severity::Info),
+            "low" | "1" => Ok(Severity::Low),
+            "medium" | "2" => Ok(Severity::Medium),
+            "high" | "3" => Ok(Severity::High),
+            "critical" | "4" => Ok(Severity::Critical),
+            other => Err(NessusError::Other(format!("Unknown severity '{other}'"))),
+        }
+    }
+}
+
+/// Server-side filters applied when fetching findings.
+///
+/// All configured conditions must match. Filters are translated into the
+/// `filter.N.*` query parameters understood by Nessus, so unmatched
+/// findings are never downloaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResultFilter {
+    /// Only include findings at or above this severity.
+    pub min_severity: Option<Severity>,
+    /// Only include findings referencing this CVE (substring match).
+    pub cve: Option<String>,
+    /// Only include findings from this plugin.
+    pub plugin_id: Option<u32>,
+    /// Only include hosts whose name contains this value.
+    pub hostname: Option<String>,
+}
+
+impl ResultFilter {
+    /// Create a filter that matches everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only include findings at or above `severity`.
+    pub fn min_severity(mut self, severity: Severity) -> Self {
+        self.min_severity = Some(severity);
+        self
+    }
+
+    /// Only include findings referencing `cve`.
+    pub fn cve(mut self, cve: impl Into<String>) -> Self {
+        self.cve = Some(cve.into());
+        self
+    }
+
+    /// Only include findings from `plugin_id`.
+    pub fn plugin_id(mut self, plugin_id: u32) -> Self {
+        self.plugin_id = Some(plugin_id);
+        self
+    }
+
+    /// Only include hosts whose name contains `hostname`.
+    pub fn hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.hostname = Some(hostname.into());
+        self
+    }
+
+    /// Whether no conditions are configured.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Render the filter as Nessus query parameters.
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut conditions: Vec<(&str, &str, String)> = Vec::new();
+
+        if let Some(severity) = self.min_severity.filter(|s| *s > Severity::Info) {
+            conditions.push(("severity", "gt", (severity.index() - 1).to_string()));
+        }
+        if let Some(cve) = &self.cve {
+            conditions.push(("cve", "match", cve.clone()));
+        }
+        if let Some(plugin_id) = self.plugin_id {
+            conditions.push(("plugin_id", "eq", plugin_id.to_string()));
+        }
+        if let Some(hostname) = &self.hostname {
+            conditions.push(("hostname", "match", hostname.clone()));
+        }
+
+        if conditions.is_empty() {
+            return Vec::new();
+        }
+
+        let mut query = vec![("filter.search_type".to_string(), "and".to_string())];
+        for (i, (filter, quality, value)) in conditions.into_iter().enumerate() {
+            query.push((format!("filter.{i}.filter"), filter.to_string()));
+            query.push((format!("filter.{i}.quality"), quality.to_string()));
+            query.push((format!("filter.{i}.value"), value));
+        }
+        query
+    }
+}
+
+/// A single vulnerability finding: one plugin reported against one host.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    /// Scan the finding belongs to.
+    pub scan_id: u32,
+    /// Nessus' identifier for the host within the scan.
+    pub host_id: u32,
+    /// Hostname or IP address of the host.
+    pub hostname: String,
+    /// Plugin that reported the finding.
+    pub plugin_id: u32,
+    /// Name of the plugin.
+    pub plugin_name: String,
+    /// Family of the plugin.
+    #[serde(default)]
+    pub plugin_family: String,
+    /// Severity of the finding.
+    pub severity: Severity,
+    /// CVE identifiers referenced by the plugin.
+    #[serde(default)]
+    pub cves: Vec<String>,
+    /// Highest EPSS score among the finding's CVEs, if enriched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epss: Option<f64>,
+    /// Whether any of the finding's CVEs is in the CISA KEV catalog, if enriched.
+    #[serde(default)]
+    pub kev: bool,
+    /// CVSS and description details for the finding's CVEs, if enriched.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cve_details: Vec<CveDetails>,
+    /// Plugin output reported for the host, if loaded with
+    /// [`NessusClient::load_plugin_output`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+}
+
+impl Finding {
+    /// Identity of the finding across runs: its hostname and plugin.
+    pub fn key(&self) -> (&str, u32) {
+        (self.hostname.as_str(), self.plugin_id)
+    }
+}
+
+/// A host in a scan's results, with its finding counts per severity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostSummary {
+    /// Nessus' identifier for the host within this scan.
+    pub host_id: u32,
+    /// Hostname or IP address of the host.
+    pub hostname: String,
+    /// Number of critical findings.
+    #[serde(default)]
+    pub critical: u32,
+    /// Number of high findings.
+    #[serde(default)]
+    pub high: u32,
+    /// Number of medium findings.
+    #[serde(default)]
+    pub medium: u32,
+    /// Number of low findings.
+    #[serde(default)]
+    pub low: u32,
+    /// Number of informational findings.
+    #[serde(default)]
+    pub info: u32,
+}
+
+/// The outcome of a single compliance check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComplianceStatus {
+    /// The host satisfies the check.
+    Passed,
+    /// The check could not be fully evaluated and needs manual review.
+    Warning,
+    /// The host does not satisfy the check.
+    Failed,
+}
+
+impl ComplianceStatus {
+    /// Map a Nessus compliance severity index to a status.
+    ///
+    /// Nessus reports passed checks as informational, warnings as low or
+    /// medium, and failures as high or above.
+    pub fn from_severity(severity: u8) -> Self {
+        match severity {
+            0 => ComplianceStatus::Passed,
+            1 | 2 => ComplianceStatus::Warning,
+            _ => ComplianceStatus::Failed,
+        }
+    }
+}
+
+impl fmt::Display for ComplianceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ComplianceStatus::Passed => "PASSED",
+            ComplianceStatus::Warning => "WARNING",
+            ComplianceStatus::Failed => "FAILED",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A single compliance check evaluated against a host.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceItem {
+    /// Plugin that implements the check.
+    pub plugin_id: u32,
+    /// Name of the check, usually the audit item's description.
+    pub plugin_name: String,
+    /// Family of the implementing plugin.
+    pub plugin_family: String,
+    /// Outcome of the check.
+    pub status: ComplianceStatus,
+}
+
+/// All compliance checks evaluated against one host.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostCompliance {
+    /// Nessus' identifier for the host within the scan.
+    pub host_id: u32,
+    /// Hostname or IP address of the host.
+    pub hostname: String,
+    /// Checks evaluated against the host.
+    pub items: Vec<ComplianceItem>,
+}
+
+impl HostCompliance {
+    /// Number of items with the given status.
+    pub fn count(&self, status: ComplianceStatus) -> usize {
+        self.items.iter().filter(|i| i.status == status).count()
+    }
+}
+
+#[derive(Deserialize)]
+struct ScanHostsResponse {
+    #[serde(default)]
+    hosts: Vec<HostSummary>,
+}
+
+#[derive(Deserialize)]
+struct HostDetailsResponse {
+    #[serde(default)]
+    vulnerabilities: Vec<RawVulnerability>,
+    #[serde(default)]
+    compliance: Vec<RawComplianceItem>,
+}
+
+#[derive(Deserialize)]
+struct RawVulnerability {
+    plugin_id: u32,
+    plugin_name: String,
+    #[serde(default)]
+    plugin_family: String,
+    severity: u8,
+}
+
+#[derive(Deserialize)]
+struct PluginOutputResponse {
+    #[serde(default)]
+    outputs: Vec<RawPluginOutput>,
+}
+
+#[derive(Deserialize)]
+struct RawPluginOutput {
+    #[serde(default)]
+    plugin_output: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawComplianceItem {
+    plugin_id: u32,
+    plugin_name: String,
+    #[serde(default)]
+    plugin_family: String,
+    severity: u8,
+}
+
+impl NessusClient {
+    /// List the hosts in a scan's latest results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the scan cannot be fetched.
+    pub async fn scan_hosts(&self, scan_id: u32) -> Result<Vec<HostSummary>> {
+        let session = self.authenticate().await?;
+        self.scan_hosts_with(&session, scan_id).await
+    }
+
+    /// Fetch the findings of a scan, applying `filter` on the server.
+    ///
+    /// CVE references are looked up once per distinct plugin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or any host or plugin
+    /// cannot be fetched.
+    pub async fn findings(&self, scan_id: u32, filter: &ResultFilter) -> Result<Vec<Finding>> {
+        let session = self.authenticate().await?;
+        let query = filter.to_query();
+
+        let hosts: ScanHostsResponse = self
+            .get_json_with_query(&session, &format!("/scans/{}", scan_id), &query)
+            .await?;
+
+        let mut cves_by_plugin: HashMap<u32, Vec<String>> = HashMap::new();
+        let mut findings = Vec::new();
+
+        for host in hosts.hosts {
+            let details: HostDetailsResponse = self
+                .get_json_with_query(
+                    &session,
+                    &format!("/scans/{}/hosts/{}", scan_id, host.host_id),
+                    &query,
+                )
+                .await?;
+
+            for vuln in details.vulnerabilities {
+                let cves = match cves_by_plugin.get(&vuln.plugin_id) {
+                    Some(cves) => cves.clone(),
+                    None => {
+                        let plugin: PluginDetails = self
+                            .get_json(&session, &format!("/plugins/plugin/{}", vuln.plugin_id))
+                            .await?;
+                        let cves = plugin.cves();
+                        cves_by_plugin.insert(vuln.plugin_id, cves.clone());
+                        cves
+                    }
+                };
+
+                findings.push(Finding {
+                    scan_id,
+                    host_id: host.host_id,
+                    hostname: host.hostname.clone(),
+                    plugin_id: vuln.plugin_id,
+                    plugin_name: vuln.plugin_name,
+                    plugin_family: vuln.plugin_family,
+                    severity: Severity::from_index(vuln.severity),
+                    cves,
+                    epss: None,
+                    kev: false,
+                    cve_details: Vec::new(),
+                    output: None,
+                });
+            }
+        }
+
+        Ok(findings)
+    }
+
+    /// Fill [`Finding::output`] with the plugin output reported for each finding's host.
+    ///
+    /// This costs one request per finding, so filter findings first where possible.
+    /// Multiple outputs (e.g. one per port) are joined with blank lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or any output cannot be fetched.
+    pub async fn load_plugin_output(&self, findings: &mut [Finding]) -> Result<()> {
+        let session = self.authenticate().await?;
+
+        for finding in findings.iter_mut() {
+            let resp: PluginOutputResponse = self
+                .get_json(
+                    &session,
+                    &format!(
+                        "/scans/{}/hosts/{}/plugins/{}",
+                        finding.scan_id, finding.host_id, finding.plugin_id
+                    ),
+                )
+                .await?;
+
+            let outputs: Vec<String> = resp
+                .outputs
+                .into_iter()
+                .filter_map(|o| o.plugin_output)
+                .map(|o| o.trim().to_string())
+                .filter(|o| !o.is_empty())
+                .collect();
+
+            finding.output = (!outputs.is_empty()).then(|| outputs.join("\n\n"));
+        }
+
+        Ok(())
+    }
+
+    /// Fetch the compliance (configuration audit) results of a scan, per host.
+    ///
+    /// Hosts without any compliance items are omitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or any host's details
+    /// cannot be fetched.
+    pub async fn compliance_results(&self, scan_id: u32) -> Result<Vec<HostCompliance>> {
+        let session = self.authenticate().await?;
+        let hosts = self.scan_hosts_with(&session, scan_id).await?;
+
+        let mut results = Vec::with_capacity(hosts.len());
+        for host in hosts {
+            let details: HostDetailsResponse = self
+                .get_json(
+                    &session,
+                    &format!("/scans/{}/hosts/{}", scan_id, host.host_id),
+                )
+                .await?;
+
+            if details.compliance.is_empty() {
+                continue;
+            }
+
+            let items = details
+                .compliance
+                .into_iter()
+                .map(|raw| ComplianceItem {
+                    plugin_id: raw.plugin_id,
+                    plugin_name: raw.plugin_name,
+                    plugin_family: raw.plugin_family,
+                    status: ComplianceStatus::from_severity(raw.severity),
+                })
+                .collect();
+
+            results.push(HostCompliance {
+                host_id: host.host_id,
+                hostname: host.hostname,
+                items,
+            });
+        }
+
+        Ok(results)
+    }
+
+    pub(crate) async fn scan_hosts_with(
+        &self,
+        session: &Session,
+        scan_id: u32,
+    ) -> Result<Vec<HostSummary>> {
+        let resp: ScanHostsResponse = self
+            .get_json(session, &format!("/scans/{}", scan_id))
+            .await?;
+        Ok(resp.hosts)
+    }
+}
@@ -0,0 +1,561 @@
+//! Scan status queries and waiting.
+//!
+//! This module extends [`NessusClient`] with:
+//!
+//! - Listing scans and folders
+//! - Updating a scan's name, description, targets, policy, or schedule
+//! - Moving scans between folders, including to and from the trash
+//! - Querying the current [`ScanStatus`] of a scan
+//! - Waiting for a scan, or a whole batch with one listing per poll, to
+//!   finish, driven by the client's [`PollStrategy`]
+//! - Downloading Nessus-generated exports of a scan's results
+//!
+//! [`PollStrategy`]: crate::PollStrategy
+
+use crate::client::Session;
+use crate::{NessusClient, NessusError, Result};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use tracing::{debug, info};
+
+/// The lifecycle state of a Nessus scan, as reported by `GET /scans/{id}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanStatus {
+    /// The scan has never been run.
+    Empty,
+    /// The scan is queued or starting up.
+    Pending,
+    /// The scan is currently running.
+    Running,
+    /// The scan has been paused.
+    Paused,
+    /// The scan is being stopped.
+    Stopping,
+    /// The scan finished normally.
+    Completed,
+    /// The scan was stopped by a user.
+    Canceled,
+    /// The scan was aborted by the server.
+    Aborted,
+    /// The scan results were imported from a file.
+    Imported,
+    /// Any status not known to this crate.
+    #[serde(other)]
+    Unknown,
+}
+
+impl ScanStatus {
+    /// Whether the scan has stopped running and will not change on its own.
+    pub fn is_finished(self) -> bool {
+        matches!(
+            self,
+            ScanStatus::Completed
+                | ScanStatus::Canceled
+                | ScanStatus::Aborted
+                | ScanStatus::Imported
+        )
+    }
+}
+
+impl fmt::Display for ScanStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ScanStatus::Empty => "empty",
+            ScanStatus::Pending => "pending",
+            ScanStatus::Running => "running",
+            ScanStatus::Paused => "paused",
+            ScanStatus::Stopping => "stopping",
+            ScanStatus::Completed => "completed",
+            ScanStatus::Canceled => "canceled",
+            ScanStatus::Aborted => "aborted",
+            ScanStatus::Imported => "imported",
+            ScanStatus::Unknown => "unknown",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A scan as listed by `GET /scans`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanSummary {
+    /// Scan identifier.
+    pub id: u32,
+    /// Scan UUID of the latest run, if the scan has run.
+    #[serde(default)]
+    pub uuid: Option<String>,
+    /// Scan name.
+    pub name: String,
+    /// Folder the scan lives in.
+    #[serde(default)]
+    pub folder_id: Option<u32>,
+    /// Status of the latest run.
+    pub status: ScanStatus,
+    /// Whether the scan's schedule is enabled.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// A scan folder, e.g. `My Scans` or `Trash`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Folder {
+    /// Folder identifier.
+    pub id: u32,
+    /// Folder name.
+    pub name: String,
+    /// Folder type: `main`, `trash`, or `custom`.
+    #[serde(rename = "type", default)]
+    pub kind: String,
+}
+
+impl Folder {
+    /// Whether `reference` names this folder: its ID, or its name
+    /// (case-insensitive).
+    pub fn matches(&self, reference: &str) -> bool {
+        reference.parse() == Ok(self.id) || self.name.eq_ignore_ascii_case(reference)
+    }
+}
+
+/// Changes to apply to a scan with [`NessusClient::update_scan`].
+///
+/// Only the fields that are set are sent; everything else keeps its
+/// current value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ScanSettingsPatch {
+    /// New scan name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// New description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// New targets, comma-separated.
+    #[serde(rename = "text_targets", skip_serializing_if = "Option::is_none")]
+    pub targets: Option<String>,
+    /// New policy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_id: Option<u32>,
+    /// Whether the scan's schedule is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+impl ScanSettingsPatch {
+    /// Create a patch that changes nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rename the scan.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Change the scan's description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Replace the scan's targets (hostnames, addresses, or ranges).
+    pub fn with_targets<I, T>(mut self, targets: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let targets: Vec<String> = targets
+            .into_iter()
+            .map(|t| t.as_ref().trim().to_string())
+            .collect();
+        self.targets = Some(targets.join(","));
+        self
+    }
+
+    /// Switch the scan to another policy.
+    pub fn with_policy(mut self, policy_id: u32) -> Self {
+        self.policy_id = Some(policy_id);
+        self
+    }
+
+    /// Enable or disable the scan's schedule.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    /// Whether the patch changes nothing.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// File formats Nessus can export scan results in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanExportFormat {
+    /// The `.nessus` XML format, accepted by most vulnerability management tools.
+    Nessus,
+    /// Nessus' own CSV format.
+    Csv,
+    /// Nessus' own HTML report.
+    Html,
+}
+
+impl ScanExportFormat {
+    /// The format name understood by the export API.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ScanExportFormat::Nessus => "nessus",
+            ScanExportFormat::Csv => "csv",
+            ScanExportFormat::Html => "html",
+        }
+    }
+
+    /// The conventional file extension for the format.
+    pub fn extension(self) -> &'static str {
+        self.as_str()
+    }
+}
+
+#[derive(Deserialize)]
+struct ScanListResponse {
+    #[serde(default)]
+    folders: Option<Vec<Folder>>,
+    #[serde(default)]
+    scans: Option<Vec<ScanSummary>>,
+}
+
+#[derive(Deserialize)]
+struct ExportResponse {
+    file: u64,
+}
+
+#[derive(Deserialize)]
+struct ExportStatusResponse {
+    status: String,
+}
+
+#[derive(Deserialize)]
+struct ScanInfoResponse {
+    info: ScanInfo,
+}
+
+#[derive(Deserialize)]
+struct ScanInfo {
+    status: ScanStatus,
+}
+
+impl NessusClient {
+    /// List all scans visible to the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the request fails.
+    pub async fn list_scans(&self) -> Result<Vec<ScanSummary>> {
+        let session = self.authenticate().await?;
+        self.list_scans_with(&session).await
+    }
+
+    /// List all scan folders.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the request fails.
+    pub async fn list_folders(&self) -> Result<Vec<Folder>> {
+        let session = self.authenticate().await?;
+        let resp: ScanListResponse = self.get_listing(&session, "/scans", false).await?;
+        Ok(resp.folders.unwrap_or_default())
+    }
+
+    /// Apply `patch` to the settings of a scan.
+    ///
+    /// An empty patch is not sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the update is rejected.
+    pub async fn update_scan(&self, scan_id: u32, patch: &ScanSettingsPatch) -> Result<()> {
+        if patch.is_empty() {
+            return Ok(());
+        }
+
+        let session = self.authenticate().await?;
+        let path = format!("/scans/{}", scan_id);
+        self.send(
+            self.request(Method::PUT, &session, &path)?
+                .json(&serde_json::json!({ "settings": patch })),
+            &path,
+        )
+        .await?;
+        debug!("Updated scan {}", scan_id);
+        Ok(())
+    }
+
+    /// Move a scan into the folder with ID `folder_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the move is rejected.
+    pub async fn move_scan(&self, scan_id: u32, folder_id: u32) -> Result<()> {
+        let session = self.authenticate().await?;
+        let path = format!("/scans/{}/folder", scan_id);
+        self.send(
+            self.request(Method::PUT, &session, &path)?
+                .json(&serde_json::json!({ "folder_id": folder_id })),
+            &path,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Move a scan to the trash. Unlike deleting, this can be undone with
+    /// [`NessusClient::restore_scan`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trash folder cannot be found or the move fails.
+    pub async fn trash_scan(&self, scan_id: u32) -> Result<()> {
+        let trash = self.folder_of_kind("trash").await?;
+        self.move_scan(scan_id, trash.id).await?;
+        info!("Moved scan {} to the trash", scan_id);
+        Ok(())
+    }
+
+    /// Move a scan out of the trash into the main `My Scans` folder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the main folder cannot be found or the move fails.
+    pub async fn restore_scan(&self, scan_id: u32) -> Result<()> {
+        let main = self.folder_of_kind("main").await?;
+        self.move_scan(scan_id, main.id).await?;
+        info!("Restored scan {} to {}", scan_id, main.name);
+        Ok(())
+    }
+
+    /// Permanently delete every scan in the trash, returning their IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trash folder cannot be found or a scan cannot
+    /// be deleted. Scans deleted before the failure stay deleted.
+    pub async fn empty_trash(&self) -> Result<Vec<u32>> {
+        let trash = self.folder_of_kind("trash").await?;
+        let session = self.authenticate().await?;
+
+        let mut deleted = Vec::new();
+        for scan in self.list_scans().await? {
+            if scan.folder_id != Some(trash.id) {
+                continue;
+            }
+            let path = format!("/scans/{}", scan.id);
+            self.send(self.request(Method::DELETE, &session, &path)?, &path)
+                .await?;
+            deleted.push(scan.id);
+        }
+
+        info!("Emptied the trash: deleted {} scan(s)", deleted.len());
+        Ok(deleted)
+    }
+
+    /// Fetch the current status of a scan.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the scan cannot be fetched.
+    pub async fn scan_status(&self, scan_id: u32) -> Result<ScanStatus> {
+        let session = self.authenticate().await?;
+        self.scan_status_with(&session, scan_id).await
+    }
+
+    /// Wait until a scan reaches a finished [`ScanStatus`].
+    ///
+    /// Status checks are spaced according to the client's [`PollStrategy`],
+    /// and the final status is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication or a status check fails, or if the
+    /// strategy's maximum poll duration elapses first.
+    ///
+    /// [`PollStrategy`]: crate::PollStrategy
+    pub async fn wait_for_scan(&self, scan_id: u32) -> Result<ScanStatus> {
+        let session = self.authenticate().await?;
+
+        self.poll_strategy()
+            .poll(|| async {
+                let status = self.scan_status_with(&session, scan_id).await?;
+                debug!("Scan {} status: {}", scan_id, status);
+                Ok(status.is_finished().then_some(status))
+            })
+            .await
+    }
+
+    /// Export a scan's latest results in `format` and download the file.
+    ///
+    /// Nessus prepares exports asynchronously; readiness is polled according
+    /// to the client's [`PollStrategy`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails, the export cannot be
+    /// requested or downloaded, or polling times out.
+    ///
+    /// [`PollStrategy`]: crate::PollStrategy
+    pub async fn export_scan(&self, scan_id: u32, format: ScanExportFormat) -> Result<Vec<u8>> {
+        let session = self.authenticate().await?;
+        self.export_scan_with(&session, scan_id, format).await
+    }
+
+    /// Wait until every scan in `scan_ids` reaches a finished [`ScanStatus`],
+    /// returning how waiting went for each.
+    ///
+    /// Unlike calling [`NessusClient::wait_for_scan`] per scan, each poll
+    /// checks all scans at once with a single `GET /scans`, so large batches
+    /// cost one request per poll rather than one per scan. Polls are spaced
+    /// according to the client's [`PollStrategy`].
+    ///
+    /// A scan that is no longer listed fails on its own while the others are
+    /// still waited for. If a listing fails or the strategy's maximum poll
+    /// duration elapses, the scans not finished by then fail with that error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails.
+    ///
+    /// [`PollStrategy`]: crate::PollStrategy
+    pub async fn wait_for_scans(
+        &self,
+        scan_ids: &[u32],
+    ) -> Result<HashMap<u32, Result<ScanStatus>>> {
+        let session = self.authenticate().await?;
+        Ok(self.wait_for_scans_with(&session, scan_ids).await)
+    }
+
+    pub(crate) async fn list_scans_with(&self, session: &Session) -> Result<Vec<ScanSummary>> {
+        let resp: ScanListResponse = self.get_listing(session, "/scans", false).await?;
+        Ok(resp.scans.unwrap_or_default())
+    }
+
+    /// List the scans outside the trash folder.
+    pub(crate) async fn untrashed_scans_with(&self, session: &Session) -> Result<Vec<ScanSummary>> {
+        let resp: ScanListResponse = self.get_listing(session, "/scans", false).await?;
+        let trash: Vec<u32> = resp
+            .folders
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|f| f.kind == "trash")
+            .map(|f| f.id)
+            .collect();
+        Ok(resp
+            .scans
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|s| s.folder_id.is_none_or(|id| !trash.contains(&id)))
+            .collect())
+    }
+
+    pub(crate) async fn wait_for_scans_with(
+        &self,
+        session: &Session,
+        scan_ids: &[u32],
+    ) -> HashMap<u32, Result<ScanStatus>> {
+        let waited = Mutex::new(HashMap::with_capacity(scan_ids.len()));
+        let polled = self
+            .poll_strategy()
+            .poll(|| async {
+                let resp: ScanListResponse = self.get_listing(session, "/scans", true).await?;
+                let listed: HashMap<u32, ScanStatus> = resp
+                    .scans
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|s| (s.id, s.status))
+                    .collect();
+
+                let mut waited = waited.lock().unwrap();
+                for &scan_id in scan_ids {
+                    if waited.contains_key(&scan_id) {
+                        continue;
+                    }
+                    match listed.get(&scan_id) {
+                        Some(&status) if status.is_finished() => {
+                            waited.insert(scan_id, Ok(status));
+                        }
+                        Some(status) => debug!("Scan {} status: {}", scan_id, status),
+                        None => {
+                            let error = format!("Scan {scan_id} is no longer listed");
+                            waited.insert(scan_id, Err(NessusError::Other(error)));
+                        }
+                    }
+                }
+                Ok(scan_ids
+                    .iter()
+                    .all(|id| waited.contains_key(id))
+                    .then_some(()))
+            })
+            .await;
+
+        let mut waited = waited.into_inner().unwrap();
+        if let Err(e) = polled {
+            let message = match e {
+                NessusError::Other(message) => message,
+                e => e.to_string(),
+            };
+            for &scan_id in scan_ids {
+                waited
+                    .entry(scan_id)
+                    .or_insert_with(|| Err(NessusError::Other(message.clone())));
+            }
+        }
+        waited
+    }
+
+    pub(crate) async fn export_scan_with(
+        &self,
+        session: &Session,
+        scan_id: u32,
+        format: ScanExportFormat,
+    ) -> Result<Vec<u8>> {
+        let export: ExportResponse = self
+            .send_json(
+                Method::POST,
+                session,
+                &format!("/scans/{}/export", scan_id),
+                &serde_json::json!({ "format": format.as_str() }),
+            )
+            .await?;
+
+        let status_path = format!("/scans/{}/export/{}/status", scan_id, export.file);
+        self.poll_strategy()
+            .poll(|| async {
+                let status: ExportStatusResponse = self.get_json(session, &status_path).await?;
+                debug!("Export {} of scan {}: {}", export.file, scan_id, status.status);
+                Ok((status.status == "ready").then_some(()))
+            })
+            .await?;
+
+        self.get_bytes(
+            session,
+            &format!("/scans/{}/export/{}/download", scan_id, export.file),
+        )
+        .await
+    }
+
+    async fn folder_of_kind(&self, kind: &str) -> Result<Folder> {
+        self.list_folders()
+            .await?
+            .into_iter()
+            .find(|f| f.kind == kind)
+            .ok_or_else(|| NessusError::Other(format!("No {kind} folder found")))
+    }
+
+    async fn scan_status_with(&self, session: &Session, scan_id: u32) -> Result<ScanStatus> {
+        let resp: ScanInfoResponse = self
+            .get_json(session, &format!("/scans/{}", scan_id))
+            .await?;
+        Ok(resp.info.status)
+    }
+}
@@ -0,0 +1,185 @@
+//! Recurring launch times in a site's local time zone.
+//!
+//! A [`Schedule`] is a wall-clock time, optionally limited to some weekdays,
+//! in an IANA time zone such as `Europe/Berlin`. [`Schedule::next_after`]
+//! turns it into the next instant to launch at, following the zone's
+//! daylight saving time rules.
+//!
+//! Around DST transitions a local time may not exist (clocks jump forward
+//! over it) or may occur twice (clocks fall back over it). What happens then
+//! is chosen per schedule:
+//!
+//! - [`GapPolicy::Skip`] drops a run whose time does not exist that day;
+//!   [`GapPolicy::Shift`] runs it as far after the jump as the time was into
+//!   the gap, e.g. 02:30 becomes 03:30 when clocks jump from 02:00 to 03:00.
+//! - [`OverlapPolicy::Once`] runs at the first of two occurrences of a time;
+//!   [`OverlapPolicy::Twice`] runs at both.
+//!
+//! Schedules implement `Deserialize`, so they can be set in a configuration
+//! file:
+//!
+//! ```toml
+//! at = "02:30"
+//! days = ["sat", "sun"]
+//! timezone = "America/New_York"
+//! on_gap = "shift"
+//! on_overlap = "once"
+//! ```
+//!
+//! ## Example
+//!
+//! ```
+//! use chrono::{TimeZone, Utc};
+//! use nessus_launcher::schedule::Schedule;
+//!
+//! let schedule = Schedule::daily("02:30".parse().unwrap(), chrono_tz::Europe::Berlin);
+//! let now = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+//! // 02:30 CET is 01:30 UTC.
+//! assert_eq!(
+//!     schedule.next_after(now),
+//!     Some(Utc.with_ymd_and_hms(2024, 3, 2, 1, 30, 0).unwrap())
+//! );
+//! ```
+
+use chrono::offset::LocalResult;
+use chrono::{
+    DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// How far ahead [`Schedule::next_after`] looks for a run, in days.
+///
+/// A week covers every weekday; the rest allows for a run skipped by
+/// [`GapPolicy::Skip`] on the only scheduled weekday.
+const LOOKAHEAD_DAYS: usize = 15;
+
+/// What to do when the scheduled time does not exist on a day, because
+/// clocks jump forward over it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GapPolicy {
+    /// Don't run that day.
+    Skip,
+    /// Run as far after the jump as the time was into the gap.
+    #[default]
+    Shift,
+}
+
+/// What to do when the scheduled time occurs twice on a day, because clocks
+/// fall back over it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlapPolicy {
+    /// Run at the first occurrence only.
+    #[default]
+    Once,
+    /// Run at both occurrences.
+    Twice,
+}
+
+/// A recurring local time; see the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Local time of day to run at, e.g. `02:30`.
+    #[serde(deserialize_with = "time_of_day")]
+    pub at: NaiveTime,
+    /// Weekdays to run on; every day if empty.
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    /// Time zone `at` is in.
+    #[serde(default = "utc")]
+    pub timezone: Tz,
+    /// What to do when `at` does not exist on a day.
+    #[serde(default)]
+    pub on_gap: GapPolicy,
+    /// What to do when `at` occurs twice on a day.
+    #[serde(default)]
+    pub on_overlap: OverlapPolicy,
+}
+
+fn utc() -> Tz {
+    Tz::UTC
+}
+
+/// Parse a time of day with or without seconds, e.g. `02:30` or `02:30:15`.
+fn time_of_day<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    NaiveTime::parse_from_str(&raw, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(&raw, "%H:%M:%S"))
+        .map_err(|e| serde::de::Error::custom(format!("invalid time of day '{raw}': {e}")))
+}
+
+impl Schedule {
+    /// Every day at `at` in `timezone`, with the default DST policies.
+    pub fn daily(at: NaiveTime, timezone: Tz) -> Self {
+        Self {
+            at,
+            days: Vec::new(),
+            timezone,
+            on_gap: GapPolicy::default(),
+            on_overlap: OverlapPolicy::default(),
+        }
+    }
+
+    /// Only run on `days`.
+    pub fn with_days(mut self, days: Vec<Weekday>) -> Self {
+        self.days = days;
+        self
+    }
+
+    /// Set what to do when the time does not exist on a day.
+    pub fn with_gap_policy(mut self, policy: GapPolicy) -> Self {
+        self.on_gap = policy;
+        self
+    }
+
+    /// Set what to do when the time occurs twice on a day.
+    pub fn with_overlap_policy(mut self, policy: OverlapPolicy) -> Self {
+        self.on_overlap = policy;
+        self
+    }
+
+    /// The first run strictly after `after`, or `None` if no day within
+    /// the next two weeks has one.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        // Start a day early: a run on the previous local date can still be
+        // ahead of `after` in zones west of UTC.
+        let today = after.with_timezone(&self.timezone).date_naive();
+        today
+            .pred_opt()?
+            .iter_days()
+            .take(LOOKAHEAD_DAYS)
+            .filter(|date| self.days.is_empty() || self.days.contains(&date.weekday()))
+            .flat_map(|date| self.runs_on(date))
+            .find(|run| *run > after)
+    }
+
+    /// The instants of the runs on the local `date`, earliest first.
+    fn runs_on(&self, date: NaiveDate) -> Vec<DateTime<Utc>> {
+        let local = date.and_time(self.at);
+        match self.timezone.from_local_datetime(&local) {
+            LocalResult::Single(run) => vec![run.to_utc()],
+            LocalResult::Ambiguous(first, second) => match self.on_overlap {
+                OverlapPolicy::Once => vec![first.to_utc()],
+                OverlapPolicy::Twice => vec![first.to_utc(), second.to_utc()],
+            },
+            LocalResult::None => match self.on_gap {
+                GapPolicy::Skip => Vec::new(),
+                GapPolicy::Shift => self.shift_over_gap(local).into_iter().collect(),
+            },
+        }
+    }
+
+    /// The instant `local`, which falls into a gap, would be at with the
+    /// UTC offset from before the gap.
+    fn shift_over_gap(&self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        let before = self
+            .timezone
+            .from_local_datetime(&(local - TimeDelta::days(1)))
+            .earliest()?;
+        let offset = TimeDelta::seconds(before.offset().fix().local_minus_utc().into());
+        Some(Utc.from_utc_datetime(&(local - offset)))
+    }
+}
@@ -0,0 +1,167 @@
+//! Selecting scans to launch by name or folder.
+//!
+//! This module extends [`NessusClient`] with launching scans organized by
+//! naming convention (e.g. `prod-web-weekly`, `prod-db-weekly`) or by folder
+//! rather than by ID:
+//!
+//! - [`NessusClient::scans_matching`]
+//! - [`NessusClient::launch_matching`]
+//! - [`NessusClient::scans_in_folder`]
+//! - [`NessusClient::launch_folder`]
+
+use crate::{LaunchReport, NessusClient, NessusError, Result, ScanSummary};
+use regex::Regex;
+use std::fmt;
+use tracing::info;
+
+/// A pattern that scan names are matched against.
+#[derive(Debug, Clone)]
+pub struct ScanNamePattern {
+    source: String,
+    regex: Regex,
+}
+
+impl ScanNamePattern {
+    /// A shell-style glob matched against the whole name: `*` matches any
+    /// run of characters, `?` any single character, and everything else
+    /// matches itself.
+    pub fn glob(pattern: &str) -> Self {
+        let mut expr = String::from("^");
+        for c in pattern.chars() {
+            match c {
+                '*' => expr.push_str(".*"),
+                '?' => expr.push('.'),
+                c => expr.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+            }
+        }
+        expr.push('$');
+
+        Self {
+            source: pattern.to_string(),
+            regex: Regex::new(&expr).expect("escaped glob is a valid regex"),
+        }
+    }
+
+    /// A regular expression, matched anywhere in the name unless anchored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if `pattern` is not a valid regex.
+    pub fn regex(pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| {
+            NessusError::Config(format!("Invalid scan name pattern '{pattern}': {e}"))
+        })?;
+        Ok(Self {
+            source: pattern.to_string(),
+            regex,
+        })
+    }
+
+    /// Whether `name` matches the pattern.
+    pub fn matches(&self, name: &str) -> bool {
+        self.regex.is_match(name)
+    }
+}
+
+impl fmt::Display for ScanNamePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl NessusClient {
+    /// List the scans whose name matches `pattern`. Scans in the trash are
+    /// never matched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the scans cannot be listed.
+    pub async fn scans_matching(&self, pattern: &ScanNamePattern) -> Result<Vec<ScanSummary>> {
+        let session = self.authenticate().await?;
+        let scans = self.untrashed_scans_with(&session).await?;
+        Ok(scans
+            .into_iter()
+            .filter(|scan| pattern.matches(&scan.name))
+            .collect())
+    }
+
+    /// Launch every enabled scan whose name matches `pattern`. Disabled
+    /// scans are skipped.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::scans_matching`] and
+    /// [`NessusClient::launch_scans_parallel`].
+    pub async fn launch_matching(&self, pattern: &ScanNamePattern) -> Result<LaunchReport> {
+        let (enabled, disabled): (Vec<_>, Vec<_>) = self
+            .scans_matching(pattern)
+            .await?
+            .into_iter()
+            .partition(|s| s.enabled);
+        if !disabled.is_empty() {
+            info!(
+                "Skipping {} disabled scan(s) matching '{}': {:?}",
+                disabled.len(),
+                pattern,
+                disabled.iter().map(|s| s.id).collect::<Vec<_>>()
+            );
+        }
+
+        let scan_ids = enabled.into_iter().map(|s| s.id).collect();
+        self.launch_scans_parallel(scan_ids).await
+    }
+
+    /// List the scans in the folder named by `reference`, its ID or name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if no folder or more than one folder
+    /// matches `reference`, or an error if any request fails.
+    pub async fn scans_in_folder(&self, reference: &str) -> Result<Vec<ScanSummary>> {
+        let folders = self.list_folders().await?;
+        let mut matching = folders.iter().filter(|f| f.matches(reference));
+        let folder = match (matching.next(), matching.next()) {
+            (Some(folder), None) => folder,
+            (None, _) => {
+                return Err(NessusError::Config(format!("No folder matches {reference}")));
+            }
+            (Some(_), Some(_)) => {
+                return Err(NessusError::Config(format!(
+                    "Folder reference {reference} is ambiguous; use the folder ID"
+                )));
+            }
+        };
+
+        let scans = self.list_scans().await?;
+        Ok(scans
+            .into_iter()
+            .filter(|scan| scan.folder_id == Some(folder.id))
+            .collect())
+    }
+
+    /// Launch every enabled scan in the folder named by `reference`, its ID
+    /// or name. Disabled scans are skipped.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::scans_in_folder`] and
+    /// [`NessusClient::launch_scans_parallel`].
+    pub async fn launch_folder(&self, reference: &str) -> Result<LaunchReport> {
+        let (enabled, disabled): (Vec<_>, Vec<_>) = self
+            .scans_in_folder(reference)
+            .await?
+            .into_iter()
+            .partition(|s| s.enabled);
+        if !disabled.is_empty() {
+            info!(
+                "Skipping {} disabled scan(s) in folder {}: {:?}",
+                disabled.len(),
+                reference,
+                disabled.iter().map(|s| s.id).collect::<Vec<_>>()
+            );
+        }
+
+        let scan_ids = enabled.into_iter().map(|s| s.id).collect();
+        self.launch_scans_parallel(scan_ids).await
+    }
+}
@@ -0,0 +1,191 @@
+//! Server version detection.
+//!
+//! Nessus releases differ in how API calls are authenticated: Nessus 6
+//! accepts the session token alone, while Nessus 7 and later also require
+//! the `X-Api-Token` embedded in `nessus6.js`. Before scraping that token,
+//! the client reads the server version from `GET /server/properties` and
+//! picks the matching [`ApiVersion`]. Releases older than Nessus 6 have no
+//! JSON API and fail with [`NessusError::UnsupportedVersion`] up front,
+//! rather than with a missing token or an unparseable login response.
+//!
+//! The detected version is cached and shared by the client's clones. Use
+//! [`NessusClient::with_api_version`] to pin it and skip detection.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::NessusClient;
+//!
+//! # async fn example(client: NessusClient) -> nessus_launcher::Result<()> {
+//! let properties = client.server_properties().await?;
+//! if let Some(version) = properties.version() {
+//!     println!("Nessus {version}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{NessusClient, NessusError, Result};
+use serde::Deserialize;
+use std::fmt;
+use tracing::{debug, info};
+
+/// Server information from `GET /server/properties`.
+///
+/// Which fields are present depends on the Nessus release and on whether
+/// the request is authenticated, so all of them are optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ServerProperties {
+    /// Version of the scanner engine, e.g. `10.6.1`.
+    #[serde(default)]
+    pub server_version: Option<String>,
+    /// Version of the web UI, e.g. `10.6.1`.
+    #[serde(default)]
+    pub nessus_ui_version: Option<String>,
+    /// Product, e.g. `Nessus Professional` or `Nessus Manager`.
+    #[serde(default)]
+    pub nessus_type: Option<String>,
+}
+
+impl ServerProperties {
+    /// The server's version, taken from `server_version` or, failing that,
+    /// `nessus_ui_version`.
+    pub fn version(&self) -> Option<NessusVersion> {
+        [&self.server_version, &self.nessus_ui_version]
+            .into_iter()
+            .flatten()
+            .find_map(|v| NessusVersion::parse(v))
+    }
+}
+
+/// A Nessus release number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NessusVersion {
+    /// Major version.
+    pub major: u32,
+    /// Minor version.
+    pub minor: u32,
+    /// Patch version.
+    pub patch: u32,
+}
+
+impl NessusVersion {
+    /// Parse a version as reported by Nessus, e.g. `10.6.1` or
+    /// `8.15.2 (#10) LINUX`. Missing minor and patch numbers count as 0.
+    ///
+    /// Returns `None` if the string does not start with a version number.
+    pub fn parse(s: &str) -> Option<Self> {
+        let number = s.split_whitespace().next()?;
+        let mut parts = number.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+        let patch = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+        Some(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl fmt::Display for NessusVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// How the client talks to a given Nessus release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiVersion {
+    /// Nessus 6: the session token alone authenticates API calls.
+    V6,
+    /// Nessus 7 and later: API calls also carry the X-API token from
+    /// `nessus6.js`.
+    V7,
+}
+
+impl ApiVersion {
+    /// The API version for a Nessus release.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::UnsupportedVersion`] for releases before
+    /// Nessus 6, which have no JSON API.
+    pub fn for_version(version: NessusVersion) -> Result<Self> {
+        match version.major {
+            0..=5 => Err(NessusError::UnsupportedVersion(format!(
+                "Nessus {version} has no JSON API; Nessus 6 or later is required"
+            ))),
+            6 => Ok(ApiVersion::V6),
+            _ => Ok(ApiVersion::V7),
+        }
+    }
+
+    /// Whether API calls must carry the X-API token.
+    pub fn requires_x_api_token(self) -> bool {
+        match self {
+            ApiVersion::V6 => false,
+            ApiVersion::V7 => true,
+        }
+    }
+}
+
+impl NessusClient {
+    /// Fetch the server's properties. No login is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Http`] if the request fails,
+    /// [`NessusError::Other`] if the response status is not successful,
+    /// or [`NessusError::UnexpectedResponse`] if the body cannot be decoded.
+    pub async fn server_properties(&self) -> Result<ServerProperties> {
+        let resp = self.get_public("/server/properties").await?;
+        if !resp.status().is_success() {
+            return Err(NessusError::Other(format!(
+                "GET /server/properties failed with status {}",
+                resp.status()
+            )));
+        }
+        crate::response::decode("GET /server/properties", &resp.text().await?)
+    }
+
+    /// The server's [`ApiVersion`], detected on first use and cached.
+    ///
+    /// Servers that do not reveal their version, e.g. because they require
+    /// a login for `/server/properties`, are assumed to be Nessus 7 or later.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Http`] if the server cannot be reached, or
+    /// [`NessusError::UnsupportedVersion`] if it runs a release before
+    /// Nessus 6.
+    pub async fn api_version(&self) -> Result<ApiVersion> {
+        if let Some(api_version) = self.cached_api_version() {
+            return Ok(api_version);
+        }
+
+        let api_version = match self.detect_version().await? {
+            Some(version) => {
+                info!("Nessus server version {}", version);
+                ApiVersion::for_version(version)?
+            }
+            None => {
+                debug!("Nessus server version unknown; assuming Nessus 7 or later");
+                ApiVersion::V7
+            }
+        };
+        self.set_api_version(api_version);
+        Ok(api_version)
+    }
+
+    /// The server's version, or `None` if it does not reveal one.
+    async fn detect_version(&self) -> Result<Option<NessusVersion>> {
+        let resp = self.get_public("/server/properties").await?;
+        if !resp.status().is_success() {
+            return Ok(None);
+        }
+        let properties: ServerProperties =
+            serde_json::from_str(&resp.text().await?).unwrap_or_default();
+        Ok(properties.version())
+    }
+}
@@ -0,0 +1,174 @@
+//! Background session keep-alive.
+//!
+//! By default every operation of [`NessusClient`] logs in afresh. Daemons
+//! and watch loops can instead keep one session alive in the background
+//! with [`NessusClient::keep_session_alive`]. The session is then shared by
+//! the client and all its clones, and is:
+//!
+//! - touched with `GET /session` every [`KeepAliveOptions::interval`], so it
+//!   does not time out while idle
+//! - replaced by a new login once it is [`KeepAliveOptions::refresh_after`]
+//!   old, or as soon as a touch fails
+//!
+//! [`NessusClient::check_session`] checks the session on demand, e.g. for a
+//! readiness probe.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::{KeepAliveOptions, NessusClient, NessusConfig, Result};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let client = NessusClient::new(NessusConfig::from_env()?)?;
+//!     let _keep_alive = client.keep_session_alive(KeepAliveOptions::default()).await?;
+//!     // ... long-running work using `client` or its clones ...
+//!     Ok(())
+//! }
+//! ```
+
+use crate::client::Session;
+use crate::{NessusClient, Result};
+use reqwest::Method;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// How often [`NessusClient::keep_session_alive`] touches and renews the session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepAliveOptions {
+    /// Time between touches of the session.
+    pub interval: Duration,
+    /// Age at which the session is replaced by a new login.
+    pub refresh_after: Duration,
+}
+
+impl Default for KeepAliveOptions {
+    /// Touch every 5 minutes and renew every 20, well within Nessus' default
+    /// 30-minute session timeout.
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5 * 60),
+            refresh_after: Duration::from_secs(20 * 60),
+        }
+    }
+}
+
+impl KeepAliveOptions {
+    /// Set the time between touches of the session.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the age at which the session is replaced by a new login.
+    pub fn with_refresh_after(mut self, refresh_after: Duration) -> Self {
+        self.refresh_after = refresh_after;
+        self
+    }
+}
+
+/// Handle to a running keep-alive task.
+///
+/// Dropping the handle stops the task, and the client goes back to logging
+/// in for every operation.
+#[must_use = "the keep-alive task stops when the handle is dropped"]
+pub struct SessionKeepAlive {
+    client: NessusClient,
+    task: JoinHandle<()>,
+}
+
+impl SessionKeepAlive {
+    /// Stop the keep-alive task.
+    pub fn stop(self) {}
+}
+
+impl Drop for SessionKeepAlive {
+    fn drop(&mut self) {
+        self.task.abort();
+        self.client.set_shared_session(None);
+    }
+}
+
+impl NessusClient {
+    /// Log in once and keep the session alive in a background task.
+    ///
+    /// Until the returned handle is dropped, this client and its clones use
+    /// the shared session instead of logging in for every operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the initial login fails. Failures of later
+    /// touches and renewals are logged and retried at the next interval.
+    pub async fn keep_session_alive(&self, options: KeepAliveOptions) -> Result<SessionKeepAlive> {
+        let session = self.new_session().await?;
+        self.set_shared_session(Some(session));
+        info!(
+            "Keeping the Nessus session alive (touch every {:?}, renew after {:?})",
+            options.interval, options.refresh_after
+        );
+
+        let client = self.clone();
+        let task = tokio::spawn(async move { client.run_keep_alive(options).await });
+
+        Ok(SessionKeepAlive {
+            client: self.clone(),
+            task,
+        })
+    }
+
+    /// Check that the server is reachable and accepts this client's session:
+    /// the shared one while it is kept alive, otherwise a fresh login.
+    ///
+    /// Suited to readiness probes of long-running services.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Auth`] if the credentials or the session are
+    /// rejected, or another error if the server cannot be reached.
+    ///
+    /// [`NessusError::Auth`]: crate::NessusError::Auth
+    pub async fn check_session(&self) -> Result<()> {
+        let session = self.authenticate().await?;
+        self.touch_session(&session).await
+    }
+
+    async fn run_keep_alive(&self, options: KeepAliveOptions) {
+        let mut obtained_at = Instant::now();
+
+        loop {
+            tokio::time::sleep(options.interval).await;
+
+            let stale = obtained_at.elapsed() >= options.refresh_after;
+            let alive = match self.shared_session() {
+                Some(session) if !stale => match self.touch_session(&session).await {
+                    Ok(()) => true,
+                    Err(e) => {
+                        debug!("Session touch failed: {}", e);
+                        false
+                    }
+                },
+                _ => false,
+            };
+            if alive {
+                continue;
+            }
+
+            match self.new_session().await {
+                Ok(session) => {
+                    self.set_shared_session(Some(session));
+                    obtained_at = Instant::now();
+                    debug!("Renewed the Nessus session");
+                }
+                Err(e) => warn!("Failed to renew the Nessus session: {}", e),
+            }
+        }
+    }
+
+    async fn touch_session(&self, session: &Session) -> Result<()> {
+        let path = "/session";
+        self.send(self.request(Method::GET, session, path)?, path)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+//! DefectDojo integration.
+//!
+//! Completed scans are exported from Nessus in the `.nessus` format and
+//! imported into a DefectDojo engagement using the `Tenable Scan` parser.
+//! The first run creates the engagement and a test; later runs reimport
+//! into the same test so DefectDojo can track closed findings.
+//!
+//! ## Environment variables
+//!
+//! - `DEFECTDOJO_URL` — Base URL of DefectDojo (e.g. `https://dojo.example.com`)
+//! - `DEFECTDOJO_API_KEY` — API v2 key of the importing user
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::sink::defectdojo::{DefectDojoClient, DefectDojoConfig};
+//! use nessus_launcher::{NessusClient, NessusConfig, Result};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let nessus = NessusClient::new(NessusConfig::from_env()?)?;
+//!     let dojo = DefectDojoClient::new(DefectDojoConfig::from_env()?)?;
+//!
+//!     let outcome = dojo.sync_scan(&nessus, 5, 1, "Weekly Nessus").await?;
+//!     println!("Imported into test {}", outcome.test_id);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{NessusClient, NessusError, Result, ScanExportFormat};
+use chrono::{Duration as ChronoDuration, Utc};
+use dotenvy::dotenv;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, Response};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::env;
+use tracing::info;
+
+/// DefectDojo parser used for `.nessus` files.
+pub const SCAN_TYPE: &str = "Tenable Scan";
+
+/// Connection settings for DefectDojo.
+#[derive(Debug, Clone)]
+pub struct DefectDojoConfig {
+    /// Base URL of DefectDojo, e.g. `https://dojo.example.com`.
+    pub url: String,
+    /// API v2 key.
+    pub api_key: String,
+}
+
+impl DefectDojoConfig {
+    /// Load configuration from `DEFECTDOJO_URL` and `DEFECTDOJO_API_KEY`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if either variable is missing.
+    pub fn from_env() -> Result<Self> {
+        dotenv().ok();
+
+        let url = env::var("DEFECTDOJO_URL")
+            .map_err(|_| NessusError::Config("Missing DEFECTDOJO_URL".into()))?;
+        let api_key = env::var("DEFECTDOJO_API_KEY")
+            .map_err(|_| NessusError::Config("Missing DEFECTDOJO_API_KEY".into()))?;
+
+        Ok(Self {
+            url: url.trim_end_matches('/').to_string(),
+            api_key,
+        })
+    }
+}
+
+/// The result of pushing a scan into DefectDojo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportOutcome {
+    /// Engagement the scan was imported into.
+    pub engagement_id: u64,
+    /// Test holding the imported findings.
+    pub test_id: u64,
+    /// `true` if an existing test was reimported rather than a new one created.
+    pub reimported: bool,
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    results: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct IdOnly {
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct ImportResponse {
+    test: u64,
+}
+
+/// A client for the DefectDojo API v2.
+pub struct DefectDojoClient {
+    http: Client,
+    config: DefectDojoConfig,
+}
+
+impl DefectDojoClient {
+    /// Create a new client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if the API key is not a valid header
+    /// value or the HTTP client cannot be built.
+    pub fn new(config: DefectDojoConfig) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Token {}", config.api_key))
+                .map_err(|e| NessusError::Other(format!("Invalid DefectDojo API key: {e}")))?,
+        );
+
+        let http = Client::builder()
+            .default_headers(headers)
+            .build()
+            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self { http, config })
+    }
+
+    /// Wait for a Nessus scan to finish, export it, and import it into the
+    /// engagement called `engagement_name` of product `product_id`.
+    ///
+    /// The engagement is created if it does not exist. If it already holds a
+    /// Tenable test, the results are reimported into that test.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if waiting for or exporting the scan fails, or if any
+    /// DefectDojo request fails.
+    pub async fn sync_scan(
+        &self,
+        nessus: &NessusClient,
+        scan_id: u32,
+        product_id: u64,
+        engagement_name: &str,
+    ) -> Result<ImportOutcome> {
+        let status = nessus.wait_for_scan(scan_id).await?;
+        info!("Scan {} finished with status {}", scan_id, status);
+
+        let report = nessus.export_scan(scan_id, ScanExportFormat::Nessus).await?;
+        let engagement_id = self.ensure_engagement(product_id, engagement_name).await?;
+        let file_name = format!("nessus-scan-{}.nessus", scan_id);
+
+        self.upload(engagement_id, report, &file_name).await
+    }
+
+    /// Find the engagement called `name` in product `product_id`, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a DefectDojo request fails.
+    pub async fn ensure_engagement(&self, product_id: u64, name: &str) -> Result<u64> {
+        let existing: Page<IdOnly> = self
+            .get_json(
+                "/api/v2/engagements/",
+                &[("product", product_id.to_string()), ("name", name.to_string())],
+            )
+            .await?;
+
+        if let Some(engagement) = existing.results.first() {
+            return Ok(engagement.id);
+        }
+
+        let today = Utc::now().date_naive();
+        let body = serde_json::json!({
+            "name": name,
+            "product": product_id,
+            "engagement_type": "CI/CD",
+            "status": "In Progress",
+            "target_start": today.to_string(),
+            "target_end": (today + ChronoDuration::days(365)).to_string(),
+        });
+
+        let resp = self
+            .http
+            .post(self.url("/api/v2/engagements/"))
+            .json(&body)
+            .send()
+            .await?;
+        let created: IdOnly = Self::decode(resp, "create engagement").await?;
+
+        info!("Created DefectDojo engagement {} ({})", created.id, name);
+        Ok(created.id)
+    }
+
+    /// Import a `.nessus` report into an engagement, reimporting into its
+    /// existing Tenable test if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a DefectDojo request fails.
+    pub async fn upload(
+        &self,
+        engagement_id: u64,
+        report: Vec<u8>,
+        file_name: &str,
+    ) -> Result<ImportOutcome> {
+        let tests: Page<IdOnly> = self
+            .get_json(
+                "/api/v2/tests/",
+                &[
+                    ("engagement", engagement_id.to_string()),
+                    ("test_type_name", SCAN_TYPE.to_string()),
+                ],
+            )
+            .await?;
+
+        let file = Part::bytes(report)
+            .file_name(file_name.to_string())
+            .mime_str("application/xml")
+            .map_err(NessusError::Http)?;
+        let form = Form::new()
+            .text("scan_type", SCAN_TYPE)
+            .text("active", "true")
+            .text("verified", "false")
+            .part("file", file);
+
+        let (path, form, reimported) = match tests.results.first() {
+            Some(test) => (
+                "/api/v2/reimport-scan/",
+                form.text("test", test.id.to_string()),
+                true,
+            ),
+            None => (
+                "/api/v2/import-scan/",
+                form.text("engagement", engagement_id.to_string()),
+                false,
+            ),
+        };
+
+        let resp = self
+            .http
+            .post(self.url(path))
+            .multipart(form)
+            .send()
+            .await?;
+        let imported: ImportResponse = Self::decode(resp, path).await?;
+
+        info!(
+            "{} {} into DefectDojo test {}",
+            if reimported { "Reimported" } else { "Imported" },
+            file_name,
+            imported.test
+        );
+
+        Ok(ImportOutcome {
+            engagement_id,
+            test_id: imported.test,
+            reimported,
+        })
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<T> {
+        let resp = self.http.get(self.url(path)).query(query).send().await?;
+        Self::decode(resp, path).await
+    }
+
+    async fn decode<T: DeserializeOwned>(resp: Response, what: &str) -> Result<T> {
+        let status = resp.status();
+        let body = resp.text().await?;
+
+        if !status.is_success() {
+            return Err(NessusError::Other(format!(
+                "DefectDojo {} failed with status {}: {}",
+                what, status, body
+            )));
+        }
+
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.url, path)
+    }
+}
@@ -0,0 +1,312 @@
+//! Jira issue creation for high-severity findings.
+//!
+//! Each finding at or above a severity threshold is tracked by one Jira
+//! issue. Issues carry a label derived from the finding's host and plugin,
+//! so reruns find and update the existing issue instead of filing a
+//! duplicate.
+//!
+//! ## Environment variables
+//!
+//! - `JIRA_URL` — Base URL of Jira (e.g. `https://example.atlassian.net`)
+//! - `JIRA_PROJECT` — Key of the project to file issues in
+//! - `JIRA_API_TOKEN` — API token (Cloud) or personal access token (Server/DC)
+//! - `JIRA_EMAIL` — Account email; when set, basic auth is used (Jira Cloud)
+//! - `JIRA_ISSUE_TYPE` — Issue type name (default `Bug`)
+//! - `JIRA_DEPLOYMENT` — `cloud` or `server` (Server and Data Center); by
+//!   default `cloud` if `JIRA_EMAIL` is set, `server` otherwise
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::sink::jira::{JiraClient, JiraConfig};
+//! use nessus_launcher::{NessusClient, NessusConfig, ResultFilter, Result, Severity};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let nessus = NessusClient::new(NessusConfig::from_env()?)?;
+//!     let findings = nessus.findings(5, &ResultFilter::new()).await?;
+//!
+//!     let jira = JiraClient::new(JiraConfig::from_env()?)?;
+//!     let outcome = jira.sync_findings(&findings, Severity::High).await?;
+//!     println!("{} created, {} updated", outcome.created.len(), outcome.updated.len());
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{Finding, NessusError, Result, Severity};
+use dotenvy::dotenv;
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::env;
+use tracing::info;
+
+/// The kind of Jira instance, which decides the search API used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JiraDeployment {
+    /// Jira Cloud, searched with `/rest/api/3/search/jql`.
+    Cloud,
+    /// Jira Server or Data Center, searched with `/rest/api/2/search`.
+    Server,
+}
+
+impl JiraDeployment {
+    fn search_path(self) -> &'static str {
+        match self {
+            Self::Cloud => "/rest/api/3/search/jql",
+            Self::Server => "/rest/api/2/search",
+        }
+    }
+}
+
+/// Connection and filing settings for Jira.
+#[derive(Debug, Clone)]
+pub struct JiraConfig {
+    /// Base URL of Jira.
+    pub url: String,
+    /// Key of the project issues are filed in.
+    pub project: String,
+    /// API token or personal access token.
+    pub api_token: String,
+    /// Account email for basic auth; bearer auth is used when `None`.
+    pub email: Option<String>,
+    /// Issue type name, e.g. `Bug`.
+    pub issue_type: String,
+    /// Whether Jira is Cloud or Server/Data Center.
+    pub deployment: JiraDeployment,
+}
+
+impl JiraConfig {
+    /// Load configuration from the `JIRA_*` environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if a required variable is missing or
+    /// `JIRA_DEPLOYMENT` is neither `cloud` nor `server`.
+    pub fn from_env() -> Result<Self> {
+        dotenv().ok();
+
+        let url = env::var("JIRA_URL").map_err(|_| NessusError::Config("Missing JIRA_URL".into()))?;
+        let project = env::var("JIRA_PROJECT")
+            .map_err(|_| NessusError::Config("Missing JIRA_PROJECT".into()))?;
+        let api_token = env::var("JIRA_API_TOKEN")
+            .map_err(|_| NessusError::Config("Missing JIRA_API_TOKEN".into()))?;
+        let email = env::var("JIRA_EMAIL").ok().filter(|e| !e.is_empty());
+        let deployment = match env::var("JIRA_DEPLOYMENT").ok().as_deref() {
+            Some("cloud") => JiraDeployment::Cloud,
+            Some("server") => JiraDeployment::Server,
+            None | Some("") if email.is_some() => JiraDeployment::Cloud,
+            None | Some("") => JiraDeployment::Server,
+            Some(other) => {
+                return Err(NessusError::Config(format!(
+                    "Invalid JIRA_DEPLOYMENT '{other}': expected cloud or server"
+                )));
+            }
+        };
+
+        Ok(Self {
+            url: url.trim_end_matches('/').to_string(),
+            project,
+            api_token,
+            email,
+            issue_type: env::var("JIRA_ISSUE_TYPE").unwrap_or_else(|_| "Bug".into()),
+            deployment,
+        })
+    }
+}
+
+/// Issues touched by [`JiraClient::sync_findings`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JiraSyncOutcome {
+    /// Keys of newly created issues.
+    pub created: Vec<String>,
+    /// Keys of existing issues that were updated.
+    pub updated: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    issues: Vec<IssueRef>,
+}
+
+#[derive(Deserialize)]
+struct IssueRef {
+    key: String,
+}
+
+/// The label deduplicating issues for `finding`: its plugin and host.
+///
+/// Characters Jira does not allow in labels are replaced with `_`.
+pub fn dedup_label(finding: &Finding) -> String {
+    let host: String = finding
+        .hostname
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect();
+    format!("nessus-{}-{}", finding.plugin_id, host)
+}
+
+/// A client filing findings as Jira issues.
+pub struct JiraClient {
+    http: Client,
+    config: JiraConfig,
+}
+
+impl JiraClient {
+    /// Create a new client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Other`] if the HTTP client cannot be built.
+    pub fn new(config: JiraConfig) -> Result<Self> {
+        let http = Client::builder()
+            .build()
+            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self { http, config })
+    }
+
+    /// Create or update one issue per finding at or above `threshold`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any Jira request fails. Issues processed before
+    /// the failure are left in place.
+    pub async fn sync_findings(
+        &self,
+        findings: &[Finding],
+        threshold: Severity,
+    ) -> Result<JiraSyncOutcome> {
+        let mut outcome = JiraSyncOutcome::default();
+
+        for finding in findings.iter().filter(|f| f.severity >= threshold) {
+            let label = dedup_label(finding);
+
+            match self.find_issue(&label).await? {
+                Some(key) => {
+                    self.update_issue(&key, finding).await?;
+                    outcome.updated.push(key);
+                }
+                None => {
+                    let key = self.create_issue(finding, &label).await?;
+                    outcome.created.push(key);
+                }
+            }
+        }
+
+        info!(
+            "Jira sync: {} created, {} updated",
+            outcome.created.len(),
+            outcome.updated.len()
+        );
+        Ok(outcome)
+    }
+
+    async fn find_issue(&self, label: &str) -> Result<Option<String>> {
+        let jql = format!(
+            "project = \"{}\" AND labels = \"{}\" ORDER BY created DESC",
+            self.config.project, label
+        );
+        let body = serde_json::json!({ "jql": jql, "maxResults": 1, "fields": ["key"] });
+
+        let path = self.config.deployment.search_path();
+        let resp: SearchResponse = self
+            .send(self.http.post(self.url(path)).json(&body), "search")
+            .await?;
+        Ok(resp.issues.into_iter().next().map(|i| i.key))
+    }
+
+    async fn create_issue(&self, finding: &Finding, label: &str) -> Result<String> {
+        let body = serde_json::json!({
+            "fields": {
+                "project": { "key": self.config.project },
+                "issuetype": { "name": self.config.issue_type },
+                "summary": summary(finding),
+                "description": description(finding),
+                "labels": ["nessus", label],
+            }
+        });
+
+        let created: IssueRef = self
+            .send(self.http.post(self.url("/rest/api/2/issue")).json(&body), "create issue")
+            .await?;
+        info!("Created Jira issue {} for {}", created.key, label);
+        Ok(created.key)
+    }
+
+    async fn update_issue(&self, key: &str, finding: &Finding) -> Result<()> {
+        let body = serde_json::json!({
+            "fields": {
+                "summary": summary(finding),
+                "description": description(finding),
+            }
+        });
+
+        let req = self
+            .http
+            .put(self.url(&format!("/rest/api/2/issue/{}", key)))
+            .json(&body);
+        self.execute(req, "update issue").await?;
+        Ok(())
+    }
+
+    async fn send<T: DeserializeOwned>(&self, req: RequestBuilder, what: &str) -> Result<T> {
+        let resp = self.execute(req, what).await?;
+        Ok(serde_json::from_str(&resp.text().await?)?)
+    }
+
+    async fn execute(&self, req: RequestBuilder, what: &str) -> Result<Response> {
+        let req = match &self.config.email {
+            Some(email) => req.basic_auth(email, Some(&self.config.api_token)),
+            None => req.bearer_auth(&self.config.api_token),
+        };
+
+        let resp = req.send().await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(NessusError::Other(format!(
+                "Jira {} failed with status {}: {}",
+                what, status, body
+            )));
+        }
+        Ok(resp)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.url, path)
+    }
+}
+
+fn summary(finding: &Finding) -> String {
+    format!(
+        "[Nessus] {} on {} ({})",
+        finding.plugin_name, finding.hostname, finding.severity
+    )
+}
+
+fn description(finding: &Finding) -> String {
+    let mut text = format!(
+        "*Host:* {}\n*Plugin:* {} - {}\n*Family:* {}\n*Severity:* {}\n*Scan:* {}\n",
+        finding.hostname,
+        finding.plugin_id,
+        finding.plugin_name,
+        finding.plugin_family,
+        finding.severity,
+        finding.scan_id
+    );
+    if !finding.cves.is_empty() {
+        text.push_str(&format!("*CVEs:* {}\n", finding.cves.join(", ")));
+    }
+    if let Some(epss) = finding.epss {
+        text.push_str(&format!("*EPSS:* {:.3}\n", epss));
+    }
+    if finding.kev {
+        text.push_str("*CISA KEV:* yes\n");
+    }
+    if let Some(output) = &finding.output {
+        text.push_str(&format!("\n{{noformat}}\n{}\n{{noformat}}\n", output));
+    }
+    text
+}
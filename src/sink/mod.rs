@@ -0,0 +1,12 @@
+//! Sinks that push scan results into external platforms.
+//!
+//! - `defectdojo` — [`defectdojo`]: import scans into DefectDojo engagements (feature `defectdojo`)
+//! - `jira` — [`jira`]: deduplicated Jira issues for high-severity findings (feature `jira`)
+//! - `servicenow` — [`servicenow`]: Table API records for critical findings and failed launches (feature `servicenow`)
+
+#[cfg(feature = "defectdojo")]
+pub mod defectdojo;
+#[cfg(feature = "jira")]
+pub mod jira;
+#[cfg(feature = "servicenow")]
+pub mod servicenow;
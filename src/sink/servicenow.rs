@@ -0,0 +1,259 @@
+//! ServiceNow integration via the Table API.
+//!
+//! Records are filed for critical findings and for scans that failed to
+//! launch. The fields of each record are produced from templates, so the
+//! mapping onto a particular table (`incident`, `sn_vul_vulnerable_item`,
+//! a custom table, ...) is configuration rather than code.
+//!
+//! Templates substitute `{name}` placeholders. Findings provide
+//! `scan_id`, `host_id`, `hostname`, `plugin_id`, `plugin_name`,
+//! `plugin_family`, `severity`, `cves`, `epss`, `kev`, and `output`;
+//! launch failures provide `scan_id` and `error`.
+//!
+//! [`ServiceNowConfig`] implements `Deserialize`, so it can be embedded in
+//! a configuration file, for example as TOML:
+//!
+//! ```toml
+//! [servicenow]
+//! url = "https://example.service-now.com"
+//! table = "incident"
+//! min_severity = "critical"
+//!
+//! [servicenow.finding_fields]
+//! short_description = "Nessus: {plugin_name} on {hostname}"
+//! urgency = "1"
+//! ```
+//!
+//! Credentials are read from `SERVICENOW_USERNAME` and
+//! `SERVICENOW_PASSWORD` unless set in the configuration.
+
+use crate::{Finding, LaunchFailure, NessusError, Result, Severity};
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use tracing::info;
+
+/// Settings for filing ServiceNow records.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceNowConfig {
+    /// Base URL of the instance, e.g. `https://example.service-now.com`.
+    pub url: String,
+    /// Table records are inserted into.
+    #[serde(default = "default_table")]
+    pub table: String,
+    /// Only file records for findings at or above this severity.
+    #[serde(default = "default_min_severity")]
+    pub min_severity: Severity,
+    /// Username; falls back to `SERVICENOW_USERNAME`.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Password; falls back to `SERVICENOW_PASSWORD`.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Field templates for finding records.
+    #[serde(default = "default_finding_fields")]
+    pub finding_fields: BTreeMap<String, String>,
+    /// Field templates for launch-failure records.
+    #[serde(default = "default_launch_failure_fields")]
+    pub launch_failure_fields: BTreeMap<String, String>,
+}
+
+fn default_table() -> String {
+    "incident".into()
+}
+
+fn default_min_severity() -> Severity {
+    Severity::Critical
+}
+
+fn default_finding_fields() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        (
+            "short_description".to_string(),
+            "Nessus: {plugin_name} on {hostname}".to_string(),
+        ),
+        (
+            "description".to_string(),
+            "Nessus scan {scan_id} reported plugin {plugin_id} ({plugin_name}) with severity \
+             {severity} on {hostname}.\nCVEs: {cves}\n\n{output}"
+                .to_string(),
+        ),
+    ])
+}
+
+fn default_launch_failure_fields() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        (
+            "short_description".to_string(),
+            "Nessus scan {scan_id} failed to launch".to_string(),
+        ),
+        ("description".to_string(), "{error}".to_string()),
+    ])
+}
+
+/// Substitute `{name}` placeholders in `template` with `values`.
+///
+/// Unknown placeholders are left untouched.
+pub fn render_template(template: &str, values: &HashMap<&str, String>) -> String {
+    let mut out = template.to_string();
+    for (name, value) in values {
+        out = out.replace(&format!("{{{name}}}"), value);
+    }
+    out
+}
+
+fn finding_values(finding: &Finding) -> HashMap<&'static str, String> {
+    HashMap::from([
+        ("scan_id", finding.scan_id.to_string()),
+        ("host_id", finding.host_id.to_string()),
+        ("hostname", finding.hostname.clone()),
+        ("plugin_id", finding.plugin_id.to_string()),
+        ("plugin_name", finding.plugin_name.clone()),
+        ("plugin_family", finding.plugin_family.clone()),
+        ("severity", finding.severity.to_string()),
+        ("cves", finding.cves.join(", ")),
+        (
+            "epss",
+            finding.epss.map(|e| format!("{e:.3}")).unwrap_or_default(),
+        ),
+        ("kev", finding.kev.to_string()),
+        ("output", finding.output.clone().unwrap_or_default()),
+    ])
+}
+
+#[derive(Deserialize)]
+struct InsertResponse {
+    result: InsertedRecord,
+}
+
+#[derive(Deserialize)]
+struct InsertedRecord {
+    sys_id: String,
+}
+
+/// A client inserting records through the ServiceNow Table API.
+pub struct ServiceNowClient {
+    http: Client,
+    config: ServiceNowConfig,
+    username: String,
+    password: String,
+}
+
+impl ServiceNowClient {
+    /// Create a new client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if no credentials are configured, or
+    /// [`NessusError::Other`] if the HTTP client cannot be built.
+    pub fn new(config: ServiceNowConfig) -> Result<Self> {
+        let username = config
+            .username
+            .clone()
+            .or_else(|| env::var("SERVICENOW_USERNAME").ok())
+            .ok_or_else(|| NessusError::Config("Missing SERVICENOW_USERNAME".into()))?;
+        let password = config
+            .password
+            .clone()
+            .or_else(|| env::var("SERVICENOW_PASSWORD").ok())
+            .ok_or_else(|| NessusError::Config("Missing SERVICENOW_PASSWORD".into()))?;
+
+        let http = Client::builder()
+            .build()
+            .map_err(|e| NessusError::Other(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self {
+            http,
+            config,
+            username,
+            password,
+        })
+    }
+
+    /// File one record per finding at or above the configured severity.
+    ///
+    /// Returns the `sys_id`s of the created records.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any insert fails.
+    pub async fn file_findings(&self, findings: &[Finding]) -> Result<Vec<String>> {
+        let mut ids = Vec::new();
+        for finding in findings
+            .iter()
+            .filter(|f| f.severity >= self.config.min_severity)
+        {
+            let record = self.render(&self.config.finding_fields, &finding_values(finding));
+            ids.push(self.insert(record).await?);
+        }
+
+        info!("Filed {} ServiceNow record(s) for findings", ids.len());
+        Ok(ids)
+    }
+
+    /// File one record per scan that failed to launch.
+    ///
+    /// Returns the `sys_id`s of the created records.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any insert fails.
+    pub async fn file_launch_failures(&self, failures: &[LaunchFailure]) -> Result<Vec<String>> {
+        let mut ids = Vec::new();
+        for failure in failures {
+            let values = HashMap::from([
+                ("scan_id", failure.scan_id.to_string()),
+                ("error", failure.error.clone()),
+            ]);
+            let record = self.render(&self.config.launch_failure_fields, &values);
+            ids.push(self.insert(record).await?);
+        }
+
+        info!(
+            "Filed {} ServiceNow record(s) for launch failures",
+            ids.len()
+        );
+        Ok(ids)
+    }
+
+    fn render(
+        &self,
+        fields: &BTreeMap<String, String>,
+        values: &HashMap<&str, String>,
+    ) -> BTreeMap<String, String> {
+        fields
+            .iter()
+            .map(|(field, template)| (field.clone(), render_template(template, values)))
+            .collect()
+    }
+
+    async fn insert(&self, record: BTreeMap<String, String>) -> Result<String> {
+        let url = format!(
+            "{}/api/now/table/{}",
+            self.config.url.trim_end_matches('/'),
+            self.config.table
+        );
+
+        let resp = self
+            .http
+            .post(url)
+            .basic_auth(&self.username, Some(&self.password))
+            .header("Accept", "application/json")
+            .json(&record)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        let body = resp.text().await?;
+        if !status.is_success() {
+            return Err(NessusError::Other(format!(
+                "ServiceNow insert into {} failed with status {}: {}",
+                self.config.table, status, body
+            )));
+        }
+
+        let inserted: InsertResponse = serde_json::from_str(&body)?;
+        Ok(inserted.result.sys_id)
+    }
+}
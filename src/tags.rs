@@ -0,0 +1,253 @@
+//! Tag and asset management.
+//!
+//! This module extends [`NessusClient`] with the tag and asset endpoints,
+//! so orchestration code can label assets, correlate results to asset
+//! groups, and select scans by the tags they target:
+//!
+//! - [`NessusClient::list_tags`] and [`NessusClient::create_tag`]
+//! - [`NessusClient::list_assets`]
+//! - [`NessusClient::assign_tags`] and [`NessusClient::unassign_tags`]
+//! - [`NessusClient::scans_with_tag`] and [`NessusClient::launch_scans_by_tag`]
+//!
+//! Tags are `category:value` pairs identified by UUID. Wherever a tag is
+//! looked up by reference, the reference may be its UUID, `Category:Value`,
+//! or just the value if that is unambiguous.
+
+use crate::client::Session;
+use crate::{LaunchReport, NessusClient, NessusError, Result, ScanSummary};
+use futures::stream::{self, StreamExt};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::warn;
+
+/// A tag value and the category it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tag {
+    /// Tag value UUID.
+    pub uuid: String,
+    /// Category name, e.g. `Environment`.
+    pub category_name: String,
+    /// Value within the category, e.g. `Production`.
+    pub value: String,
+    /// Free-form description.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl Tag {
+    /// Whether `reference` names this tag: its UUID, `Category:Value`
+    /// (case-insensitive), or its value (case-insensitive).
+    pub fn matches(&self, reference: &str) -> bool {
+        if self.uuid == reference {
+            return true;
+        }
+        match reference.split_once(':') {
+            Some((category, value)) => {
+                self.category_name.eq_ignore_ascii_case(category.trim())
+                    && self.value.eq_ignore_ascii_case(value.trim())
+            }
+            None => self.value.eq_ignore_ascii_case(reference),
+        }
+    }
+}
+
+/// An asset known to the scanner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Asset {
+    /// Asset UUID.
+    pub id: String,
+    /// Hostnames of the asset.
+    #[serde(default)]
+    pub hostname: Vec<String>,
+    /// Fully qualified domain names of the asset.
+    #[serde(default)]
+    pub fqdn: Vec<String>,
+    /// IPv4 addresses of the asset.
+    #[serde(default)]
+    pub ipv4: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    values: Vec<Tag>,
+}
+
+#[derive(Deserialize)]
+struct AssetsResponse {
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+struct ScanTargetsResponse {
+    info: ScanTargets,
+}
+
+#[derive(Deserialize)]
+struct ScanTargets {
+    #[serde(default)]
+    tag_targets: Option<Vec<String>>,
+}
+
+impl NessusClient {
+    /// List all tags.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the request fails.
+    pub async fn list_tags(&self) -> Result<Vec<Tag>> {
+        let session = self.authenticate().await?;
+        self.list_tags_with(&session).await
+    }
+
+    /// Create the tag `category:value`, creating the category if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the tag cannot be created.
+    pub async fn create_tag(
+        &self,
+        category: &str,
+        value: &str,
+        description: Option<&str>,
+    ) -> Result<Tag> {
+        let session = self.authenticate().await?;
+        self.send_json(
+            Method::POST,
+            &session,
+            "/tags/values",
+            &json!({
+                "category_name": category,
+                "value": value,
+                "description": description.unwrap_or_default(),
+            }),
+        )
+        .await
+    }
+
+    /// List all assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the request fails.
+    pub async fn list_assets(&self) -> Result<Vec<Asset>> {
+        let session = self.authenticate().await?;
+        let resp: AssetsResponse = self.get_json(&session, "/assets").await?;
+        Ok(resp.assets)
+    }
+
+    /// Assign the tags with UUIDs `tag_uuids` to the assets `asset_ids`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the assignment is rejected.
+    pub async fn assign_tags(&self, asset_ids: &[String], tag_uuids: &[String]) -> Result<()> {
+        self.change_assignments("add", asset_ids, tag_uuids).await
+    }
+
+    /// Remove the tags with UUIDs `tag_uuids` from the assets `asset_ids`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the change is rejected.
+    pub async fn unassign_tags(&self, asset_ids: &[String], tag_uuids: &[String]) -> Result<()> {
+        self.change_assignments("remove", asset_ids, tag_uuids)
+            .await
+    }
+
+    /// List the scans targeting the tag named by `reference`, in listing
+    /// order. Scans in the trash are never included.
+    ///
+    /// Scan details are fetched up to [`LaunchOptions::concurrency`] at a
+    /// time. A scan whose details cannot be fetched, e.g. because the user
+    /// may not view it, is logged and skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if no tag or more than one tag matches
+    /// `reference`, or an error if the tags or scans cannot be listed.
+    ///
+    /// [`LaunchOptions::concurrency`]: crate::LaunchOptions::concurrency
+    pub async fn scans_with_tag(&self, reference: &str) -> Result<Vec<ScanSummary>> {
+        let session = self.authenticate().await?;
+
+        let tags = self.list_tags_with(&session).await?;
+        let mut matching = tags.iter().filter(|t| t.matches(reference));
+        let tag = match (matching.next(), matching.next()) {
+            (Some(tag), None) => tag,
+            (None, _) => return Err(NessusError::Config(format!("No tag matches {reference}"))),
+            (Some(_), Some(_)) => {
+                return Err(NessusError::Config(format!(
+                    "Tag reference {reference} is ambiguous; use Category:Value or the UUID"
+                )));
+            }
+        };
+
+        let scans = self.untrashed_scans_with(&session).await?;
+        let concurrency = self.launch_options().concurrency.max(1);
+        let tagged = stream::iter(scans)
+            .map(|scan| {
+                let session = &session;
+                async move {
+                    let path = format!("/scans/{}", scan.id);
+                    match self.get_json::<ScanTargetsResponse>(session, &path).await {
+                        Ok(details) => details
+                            .info
+                            .tag_targets
+                            .is_some_and(|targets| targets.contains(&tag.uuid))
+                            .then_some(scan),
+                        Err(e) => {
+                            warn!("Skipping scan {} while matching tags: {}", scan.id, e);
+                            None
+                        }
+                    }
+                }
+            })
+            .buffered(concurrency)
+            .filter_map(std::future::ready)
+            .collect()
+            .await;
+
+        Ok(tagged)
+    }
+
+    /// Launch every scan targeting the tag named by `reference`.
+    ///
+    /// # Errors
+    ///
+    /// See [`NessusClient::scans_with_tag`] and
+    /// [`NessusClient::launch_scans_parallel`].
+    pub async fn launch_scans_by_tag(&self, reference: &str) -> Result<LaunchReport> {
+        let scan_ids = self
+            .scans_with_tag(reference)
+            .await?
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        self.launch_scans_parallel(scan_ids).await
+    }
+
+    async fn list_tags_with(&self, session: &Session) -> Result<Vec<Tag>> {
+        let resp: TagsResponse = self.get_listing(session, "/tags/values", false).await?;
+        Ok(resp.values)
+    }
+
+    async fn change_assignments(
+        &self,
+        action: &str,
+        asset_ids: &[String],
+        tag_uuids: &[String],
+    ) -> Result<()> {
+        let session = self.authenticate().await?;
+        let path = "/tags/assets/assignments";
+        let body = json!({ "action": action, "assets": asset_ids, "tags": tag_uuids });
+        self.send(
+            self.request(Method::POST, &session, path)?.json(&body),
+            path,
+        )
+        .await?;
+        Ok(())
+    }
+}
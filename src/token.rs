@@ -0,0 +1,38 @@
+//! Extraction of the X-API token from `nessus6.js`.
+//!
+//! Nessus embeds the token its web UI sends as `X-Api-Token` in a
+//! `getApiToken` function of `nessus6.js`. The minified shape of that
+//! function differs between Nessus versions, so several patterns are tried
+//! in turn. When none matches, set `NESSUS_X_API_TOKEN` (see
+//! [`NessusConfig`]) to pin the token instead.
+//!
+//! [`NessusConfig`]: crate::NessusConfig
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Patterns for the token, most specific first. Each captures the token as
+/// group 1.
+static PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        // {key:"getApiToken",value:function(){return"..."}}
+        r#"["']getApiToken["']\s*,\s*value\s*:\s*function\s*\(\s*\)\s*\{\s*return\s*["']([^"']+)["']"#,
+        // getApiToken:function(){return"..."} or getApiToken=function(){...}
+        r#"getApiToken["']?\s*[:=]\s*function\s*\(\s*\)\s*\{\s*return\s*["']([^"']+)["']"#,
+        // Any UUID-shaped string shortly after getApiToken.
+        r#"(?is)getApiToken.{0,200}?["']([0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})["']"#,
+    ]
+    .into_iter()
+    .map(|p| Regex::new(p).expect("token pattern is valid"))
+    .collect()
+});
+
+/// Extract the X-API token from the source of `nessus6.js`.
+///
+/// Returns `None` if no known pattern matches.
+pub fn extract_x_api_token(js: &str) -> Option<String> {
+    PATTERNS
+        .iter()
+        .find_map(|re| re.captures(js))
+        .map(|caps| caps[1].to_string())
+}
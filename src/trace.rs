@@ -0,0 +1,177 @@
+//! Redacted dumps of HTTP traffic for debugging.
+//!
+//! With `NESSUS_HTTP_TRACE=1` (see [`NessusConfig::http_trace_from_env`]) or
+//! [`NessusClient::with_http_trace`], every request and response is logged
+//! at `TRACE` level under the `nessus_launcher::http` target: method, URL,
+//! status, headers, and body. Secrets are redacted before anything is
+//! logged:
+//!
+//! - The `X-Cookie`, `X-Api-Token`, `X-ApiKeys`, `Authorization`, `Cookie`,
+//!   and `Set-Cookie` headers
+//! - JSON fields whose name mentions a password, token, secret, cookie, or
+//!   key, at any depth, even in JSON that does not parse
+//! - In other bodies, such as `nessus6.js`, anything shaped like an X-API
+//!   token
+//!
+//! Bodies are cut off after [`MAX_BODY_LEN`] bytes, so large exports do not
+//! flood the log.
+//!
+//! [`NessusConfig::http_trace_from_env`]: crate::NessusConfig::http_trace_from_env
+//! [`NessusClient::with_http_trace`]: crate::NessusClient::with_http_trace
+
+use crate::Result;
+use crate::response;
+use regex::Regex;
+use reqwest::header::HeaderMap;
+use reqwest::{Request, Response};
+use serde_json::Value;
+use std::sync::LazyLock;
+use tracing::trace;
+
+/// Target of the trace events, for filtering.
+const TARGET: &str = "nessus_launcher::http";
+
+/// Maximum number of bytes of a body that are logged.
+const MAX_BODY_LEN: usize = 64 * 1024;
+
+/// Replacement for redacted values.
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+/// Headers whose value is always redacted.
+const SECRET_HEADERS: [&str; 6] = [
+    "x-cookie",
+    "x-api-token",
+    "x-apikeys",
+    "authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// Fragments of JSON field names whose value is redacted.
+const SECRET_FIELDS: [&str; 5] = ["password", "token", "secret", "cookie", "key"];
+
+/// UUID-shaped strings, the shape of the X-API token.
+static UUID: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}")
+        .expect("UUID pattern is valid")
+});
+
+/// String fields named like a secret in JSON that does not parse, e.g. a
+/// truncated body.
+static SECRET_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)("[^"]*(?:password|token|secret|cookie|key)[^"]*"\s*:\s*)"(?:[^"\\]|\\.)*"?"#)
+        .expect("secret field pattern is valid")
+});
+
+/// Log `req` as it is about to be sent.
+pub(crate) fn request(req: &Request) {
+    let body = match req.body() {
+        None => String::new(),
+        Some(body) => match body.as_bytes() {
+            Some(bytes) => redact_body(bytes),
+            None => "<streamed body>".into(),
+        },
+    };
+    trace!(
+        target: TARGET,
+        "--> {} {}\n{}\n{}",
+        req.method(),
+        req.url(),
+        redact_headers(req.headers()),
+        body
+    );
+}
+
+/// Log `resp`, reading its body, and return an equivalent response with
+/// the body still unread.
+///
+/// # Errors
+///
+/// Returns [`NessusError::Http`] if the body cannot be read.
+///
+/// [`NessusError::Http`]: crate::NessusError::Http
+pub(crate) async fn response(resp: Response) -> Result<Response> {
+    let status = resp.status();
+    let version = resp.version();
+    let headers = resp.headers().clone();
+    let bytes = resp.bytes().await?;
+
+    trace!(
+        target: TARGET,
+        "<-- {}\n{}\n{}",
+        status,
+        redact_headers(&headers),
+        redact_body(&bytes)
+    );
+
+    response::rebuild(status, version, headers, bytes)
+}
+
+/// `headers`, one per line, with secret values redacted.
+fn redact_headers(headers: &HeaderMap) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_secret_header(name.as_str()) {
+                REDACTED
+            } else {
+                value.to_str().unwrap_or("<binary>")
+            };
+            format!("{name}: {value}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whether the value of the header `name` (lowercase) is a secret.
+pub(crate) fn is_secret_header(name: &str) -> bool {
+    SECRET_HEADERS.contains(&name)
+}
+
+/// `body` as text, with secrets redacted and cut off after
+/// [`MAX_BODY_LEN`] bytes.
+fn redact_body(body: &[u8]) -> String {
+    truncate(redact(body))
+}
+
+/// `body` as text, with secrets redacted.
+pub(crate) fn redact(body: &[u8]) -> String {
+    if let Ok(mut json) = serde_json::from_slice::<Value>(body) {
+        redact_json(&mut json);
+        return json.to_string();
+    }
+    let text = String::from_utf8_lossy(body);
+    let text = SECRET_FIELD.replace_all(&text, format!("${{1}}\"{REDACTED}\""));
+    UUID.replace_all(&text, REDACTED).into_owned()
+}
+
+/// Redact the values of secret fields in `value`, at any depth.
+fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                let name = name.to_lowercase();
+                if SECRET_FIELDS.iter().any(|secret| name.contains(secret)) {
+                    *field = Value::String(REDACTED.into());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+fn truncate(mut text: String) -> String {
+    if text.len() > MAX_BODY_LEN {
+        let mut end = MAX_BODY_LEN;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let total = text.len();
+        text.truncate(end);
+        text.push_str(&format!("... ({total} bytes)"));
+    }
+    text
+}
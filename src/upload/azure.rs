@@ -0,0 +1,42 @@
+//! Azure Blob Storage uploads.
+//!
+//! Upload URLs take the form `az://container/prefix/`. The storage account
+//! and credentials are taken from the standard environment variables
+//! (`AZURE_STORAGE_ACCOUNT_NAME` plus `AZURE_STORAGE_ACCOUNT_KEY`,
+//! `AZURE_STORAGE_SAS_KEY`, or a service principal via `AZURE_CLIENT_ID`,
+//! `AZURE_CLIENT_SECRET`, and `AZURE_TENANT_ID`).
+
+use crate::Result;
+use crate::upload::{ArtifactStore, UploadTarget, config_error, put_object};
+use async_trait::async_trait;
+use object_store::azure::{MicrosoftAzure, MicrosoftAzureBuilder};
+
+/// Uploads report files to an Azure Blob Storage container.
+pub struct AzureStore {
+    target: UploadTarget,
+}
+
+impl AzureStore {
+    /// Create a store writing below `target`.
+    pub fn new(target: UploadTarget) -> Self {
+        Self { target }
+    }
+
+    fn client(&self) -> Result<MicrosoftAzure> {
+        MicrosoftAzureBuilder::from_env()
+            .with_container_name(&self.target.bucket)
+            .build()
+            .map_err(|e| config_error("Azure", e))
+    }
+}
+
+#[async_trait]
+impl ArtifactStore for AzureStore {
+    fn target(&self) -> &UploadTarget {
+        &self.target
+    }
+
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        put_object(&self.client()?, &self.target, key, body).await
+    }
+}
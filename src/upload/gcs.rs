@@ -0,0 +1,40 @@
+//! Google Cloud Storage uploads.
+//!
+//! Upload URLs take the form `gs://bucket/prefix/`. Credentials are taken
+//! from `GOOGLE_SERVICE_ACCOUNT` (a service account key file),
+//! `GOOGLE_APPLICATION_CREDENTIALS`, or the instance metadata server.
+
+use crate::Result;
+use crate::upload::{ArtifactStore, UploadTarget, config_error, put_object};
+use async_trait::async_trait;
+use object_store::gcp::{GoogleCloudStorage, GoogleCloudStorageBuilder};
+
+/// Uploads report files to a Google Cloud Storage bucket.
+pub struct GcsStore {
+    target: UploadTarget,
+}
+
+impl GcsStore {
+    /// Create a store writing below `target`.
+    pub fn new(target: UploadTarget) -> Self {
+        Self { target }
+    }
+
+    fn client(&self) -> Result<GoogleCloudStorage> {
+        GoogleCloudStorageBuilder::from_env()
+            .with_bucket_name(&self.target.bucket)
+            .build()
+            .map_err(|e| config_error("GCS", e))
+    }
+}
+
+#[async_trait]
+impl ArtifactStore for GcsStore {
+    fn target(&self) -> &UploadTarget {
+        &self.target
+    }
+
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        put_object(&self.client()?, &self.target, key, body).await
+    }
+}
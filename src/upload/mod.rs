@@ -0,0 +1,198 @@
+//! Uploading exported reports to object storage.
+//!
+//! An upload destination is a URL whose scheme selects the backend:
+//!
+//! - `s3://bucket/prefix/` — [`s3`]: Amazon S3 and S3-compatible stores (feature `s3`)
+//! - `az://container/prefix/` — [`azure`]: Azure Blob Storage (feature `azure`)
+//! - `gs://bucket/prefix/` — [`gcs`]: Google Cloud Storage (feature `gcs`)
+//!
+//! Every backend implements [`ArtifactStore`]; [`open`] picks one by
+//! scheme. Object names below the prefix come from an
+//! [`ObjectNameTemplate`], so repeated runs can write dated or per-scan
+//! keys instead of overwriting each other.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::upload::{self, ObjectNameTemplate};
+//!
+//! # async fn run() -> nessus_launcher::Result<()> {
+//! let store = upload::open("s3://reports/nessus/")?;
+//! let template = ObjectNameTemplate::new("{date}/scan-{scan_id}.{ext}");
+//! let url = store.upload(&template, 5, "html", b"<html></html>".to_vec()).await?;
+//! println!("uploaded to {url}");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{NessusError, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use object_store::ObjectStore;
+use object_store::path::Path;
+use tracing::info;
+
+#[cfg(feature = "azure")]
+pub mod azure;
+#[cfg(feature = "gcs")]
+pub mod gcs;
+#[cfg(feature = "s3")]
+pub mod s3;
+
+/// A parsed upload destination, e.g. `s3://bucket/prefix/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadTarget {
+    /// URL scheme, e.g. `s3`.
+    pub scheme: String,
+    /// Bucket or container name.
+    pub bucket: String,
+    /// Key prefix objects are written under, without leading or trailing `/`.
+    pub prefix: String,
+}
+
+impl UploadTarget {
+    /// Parse a `scheme://bucket/prefix` URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NessusError::Config`] if the URL has no scheme or bucket.
+    pub fn parse(url: &str) -> Result<Self> {
+        let (scheme, rest) = url
+            .split_once("://")
+            .ok_or_else(|| NessusError::Config(format!("Invalid upload URL: {url}")))?;
+        let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+        if scheme.is_empty() || bucket.is_empty() {
+            return Err(NessusError::Config(format!("Invalid upload URL: {url}")));
+        }
+
+        Ok(Self {
+            scheme: scheme.to_string(),
+            bucket: bucket.to_string(),
+            prefix: prefix.trim_matches('/').to_string(),
+        })
+    }
+
+    /// The full object key for `name` below the prefix.
+    pub fn key(&self, name: &str) -> String {
+        if self.prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", self.prefix, name)
+        }
+    }
+
+    /// The URL of the object with the given full key.
+    pub fn url(&self, key: &str) -> String {
+        format!("{}://{}/{}", self.scheme, self.bucket, key)
+    }
+}
+
+/// A template producing object names.
+///
+/// Supported placeholders:
+///
+/// - `{scan_id}` — the scan the report belongs to
+/// - `{date}` — the upload date, `YYYY-MM-DD` (UTC)
+/// - `{timestamp}` — the upload time, `YYYYMMDDTHHMMSSZ`
+/// - `{ext}` — the file extension of the report format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectNameTemplate(String);
+
+impl Default for ObjectNameTemplate {
+    /// `scan-{scan_id}-{timestamp}.{ext}`
+    fn default() -> Self {
+        Self::new("scan-{scan_id}-{timestamp}.{ext}")
+    }
+}
+
+impl ObjectNameTemplate {
+    /// Create a template from a string with placeholders.
+    pub fn new(template: impl Into<String>) -> Self {
+        Self(template.into())
+    }
+
+    /// Render the object name for a report of `scan_id` uploaded at `time`.
+    pub fn render(&self, scan_id: u32, ext: &str, time: DateTime<Utc>) -> String {
+        self.0
+            .replace("{scan_id}", &scan_id.to_string())
+            .replace("{date}", &time.format("%Y-%m-%d").to_string())
+            .replace("{timestamp}", &time.format("%Y%m%dT%H%M%SZ").to_string())
+            .replace("{ext}", ext)
+    }
+}
+
+/// A destination exported reports can be uploaded to.
+#[async_trait]
+pub trait ArtifactStore: Send + Sync {
+    /// The bucket and prefix objects are written below.
+    fn target(&self) -> &UploadTarget;
+
+    /// Write `body` to the object with the full key `key`.
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()>;
+
+    /// Upload the report of `scan_id` with file extension `ext`, naming the
+    /// object from `template`.
+    ///
+    /// Returns the URL of the uploaded object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be configured or the upload fails.
+    async fn upload(
+        &self,
+        template: &ObjectNameTemplate,
+        scan_id: u32,
+        ext: &str,
+        body: Vec<u8>,
+    ) -> Result<String> {
+        let target = self.target();
+        let key = target.key(&template.render(scan_id, ext, Utc::now()));
+        self.put(&key, body).await?;
+
+        let url = target.url(&key);
+        info!("Uploaded {}", url);
+        Ok(url)
+    }
+}
+
+/// Open the store for an upload URL, selecting the backend by scheme.
+///
+/// Backends are configured from their standard environment variables.
+///
+/// # Errors
+///
+/// Returns [`NessusError::Config`] if the URL is invalid or its scheme is
+/// not supported by the enabled features.
+pub fn open(url: &str) -> Result<Box<dyn ArtifactStore>> {
+    let target = UploadTarget::parse(url)?;
+    match target.scheme.as_str() {
+        #[cfg(feature = "s3")]
+        "s3" => Ok(Box::new(s3::S3Store::new(target))),
+        #[cfg(feature = "azure")]
+        "az" | "azure" => Ok(Box::new(azure::AzureStore::new(target))),
+        #[cfg(feature = "gcs")]
+        "gs" => Ok(Box::new(gcs::GcsStore::new(target))),
+        scheme => Err(NessusError::Config(format!(
+            "Unsupported upload scheme: {scheme}://"
+        ))),
+    }
+}
+
+/// Put `body` into an `object_store` backend, mapping errors.
+async fn put_object(
+    store: &dyn ObjectStore,
+    target: &UploadTarget,
+    key: &str,
+    body: Vec<u8>,
+) -> Result<()> {
+    store
+        .put(&Path::from(key), body.into())
+        .await
+        .map_err(|e| NessusError::Other(format!("Upload to {} failed: {e}", target.url(key))))?;
+    Ok(())
+}
+
+/// Map an `object_store` configuration error.
+fn config_error(backend: &str, e: object_store::Error) -> NessusError {
+    NessusError::Other(format!("Failed to configure {backend} client: {e}"))
+}
@@ -0,0 +1,96 @@
+//! Amazon S3 uploads.
+//!
+//! Credentials and region are taken from the standard AWS environment
+//! variables (`AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`,
+//! `AWS_SESSION_TOKEN`, `AWS_REGION`, `AWS_ENDPOINT` for S3-compatible
+//! stores, ...).
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::upload::s3::{S3Store, ServerSideEncryption};
+//! use nessus_launcher::upload::{ArtifactStore, ObjectNameTemplate, UploadTarget};
+//!
+//! # async fn run() -> nessus_launcher::Result<()> {
+//! let store = S3Store::new(UploadTarget::parse("s3://reports/nessus/")?)
+//!     .with_encryption(ServerSideEncryption::Kms { key_id: None });
+//! let template = ObjectNameTemplate::new("{date}/scan-{scan_id}.{ext}");
+//! let url = store.upload(&template, 5, "html", b"<html></html>".to_vec()).await?;
+//! println!("uploaded to {url}");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::Result;
+use crate::upload::{ArtifactStore, UploadTarget, config_error, put_object};
+use async_trait::async_trait;
+use object_store::aws::{AmazonS3, AmazonS3Builder, AmazonS3ConfigKey};
+
+/// Server-side encryption applied to uploaded objects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerSideEncryption {
+    /// Use the bucket's default encryption.
+    BucketDefault,
+    /// SSE-S3 (`AES256`).
+    S3Managed,
+    /// SSE-KMS, with the given key or the account's default key.
+    Kms {
+        /// KMS key ID or ARN.
+        key_id: Option<String>,
+    },
+}
+
+/// Uploads report files to an S3 bucket.
+pub struct S3Store {
+    target: UploadTarget,
+    encryption: ServerSideEncryption,
+}
+
+impl S3Store {
+    /// Create a store writing below `target`.
+    pub fn new(target: UploadTarget) -> Self {
+        Self {
+            target,
+            encryption: ServerSideEncryption::BucketDefault,
+        }
+    }
+
+    /// Set the server-side encryption of uploaded objects.
+    pub fn with_encryption(mut self, encryption: ServerSideEncryption) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    fn client(&self) -> Result<AmazonS3> {
+        let mut builder = AmazonS3Builder::from_env().with_bucket_name(&self.target.bucket);
+
+        let sse_type = match &self.encryption {
+            ServerSideEncryption::BucketDefault => None,
+            ServerSideEncryption::S3Managed => Some("AES256"),
+            ServerSideEncryption::Kms { key_id: Some(key) } => {
+                builder = builder.with_sse_kms_encryption(key);
+                None
+            }
+            ServerSideEncryption::Kms { key_id: None } => Some("aws:kms"),
+        };
+        if let Some(sse_type) = sse_type {
+            let key: AmazonS3ConfigKey = "aws_server_side_encryption"
+                .parse()
+                .map_err(|e| config_error("S3", e))?;
+            builder = builder.with_config(key, sse_type);
+        }
+
+        builder.build().map_err(|e| config_error("S3", e))
+    }
+}
+
+#[async_trait]
+impl ArtifactStore for S3Store {
+    fn target(&self) -> &UploadTarget {
+        &self.target
+    }
+
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        put_object(&self.client()?, &self.target, key, body).await
+    }
+}
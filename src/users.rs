@@ -0,0 +1,219 @@
+//! Scanner user management.
+//!
+//! This module extends [`NessusClient`] with the user endpoints, so scanner
+//! accounts can be provisioned alongside scans:
+//!
+//! - [`NessusClient::list_users`]
+//! - [`NessusClient::create_user`]
+//! - [`NessusClient::delete_user`]
+//! - [`NessusClient::change_password`]
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use nessus_launcher::{NessusClient, NewUser, UserRole};
+//!
+//! # async fn example(client: NessusClient) -> nessus_launcher::Result<()> {
+//! let user = client
+//!     .create_user(&NewUser::new("ci-bot", "s3cret").with_role(UserRole::Standard))
+//!     .await?;
+//! println!("Created user {}", user.id);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{NessusClient, Result};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// The permission level of a scanner user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserRole {
+    /// Can view scan results shared with them.
+    Basic,
+    /// Can create and run scans.
+    Standard,
+    /// Can also manage policies and share resources.
+    Administrator,
+    /// Full control of the scanner, including users.
+    SystemAdministrator,
+}
+
+impl UserRole {
+    /// The numeric permission level used by the API.
+    pub fn permissions(self) -> u32 {
+        match self {
+            UserRole::Basic => 16,
+            UserRole::Standard => 32,
+            UserRole::Administrator => 64,
+            UserRole::SystemAdministrator => 128,
+        }
+    }
+
+    /// The role for a numeric permission level, if it is a known one.
+    pub fn from_permissions(permissions: u32) -> Option<Self> {
+        match permissions {
+            16 => Some(UserRole::Basic),
+            32 => Some(UserRole::Standard),
+            64 => Some(UserRole::Administrator),
+            128 => Some(UserRole::SystemAdministrator),
+            _ => None,
+        }
+    }
+}
+
+/// A scanner user as listed by `GET /users`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct User {
+    /// User identifier.
+    pub id: u32,
+    /// Login name.
+    pub username: String,
+    /// Display name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Email address.
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Numeric permission level; see [`UserRole`].
+    pub permissions: u32,
+    /// Account type, e.g. `local` or `ldap`.
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    /// Unix timestamp of the last login, if any.
+    #[serde(default)]
+    pub lastlogin: Option<u64>,
+}
+
+impl User {
+    /// The user's role, if their permission level is a known one.
+    pub fn role(&self) -> Option<UserRole> {
+        UserRole::from_permissions(self.permissions)
+    }
+}
+
+/// A user to create with [`NessusClient::create_user`].
+#[derive(Debug, Clone)]
+pub struct NewUser {
+    username: String,
+    password: String,
+    role: UserRole,
+    name: Option<String>,
+    email: Option<String>,
+}
+
+impl NewUser {
+    /// A local [`UserRole::Standard`] user with the given credentials.
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+            role: UserRole::Standard,
+            name: None,
+            email: None,
+        }
+    }
+
+    /// Set the user's role.
+    pub fn with_role(mut self, role: UserRole) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Set the user's display name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the user's email address.
+    pub fn with_email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct UsersResponse {
+    #[serde(default)]
+    users: Vec<User>,
+}
+
+impl NessusClient {
+    /// List all scanner users.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the request fails.
+    pub async fn list_users(&self) -> Result<Vec<User>> {
+        let session = self.authenticate().await?;
+        let resp: UsersResponse = self.get_json(&session, "/users").await?;
+        Ok(resp.users)
+    }
+
+    /// Create a local scanner user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the user cannot be
+    /// created, e.g. because the username is taken.
+    pub async fn create_user(&self, user: &NewUser) -> Result<User> {
+        let session = self.authenticate().await?;
+        self.send_json(
+            Method::POST,
+            &session,
+            "/users",
+            &json!({
+                "username": user.username,
+                "password": user.password,
+                "permissions": user.role.permissions(),
+                "name": user.name,
+                "email": user.email,
+                "type": "local",
+            }),
+        )
+        .await
+    }
+
+    /// Delete the user with the given ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the user cannot be deleted.
+    pub async fn delete_user(&self, user_id: u32) -> Result<()> {
+        let session = self.authenticate().await?;
+        let path = format!("/users/{}", user_id);
+        self.send(self.request(Method::DELETE, &session, &path)?, &path)
+            .await?;
+        Ok(())
+    }
+
+    /// Set a new password for the user with the given ID.
+    ///
+    /// Administrators may change other users' passwords; changing one's own
+    /// password requires `current_password`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authentication fails or the change is rejected.
+    pub async fn change_password(
+        &self,
+        user_id: u32,
+        password: &str,
+        current_password: Option<&str>,
+    ) -> Result<()> {
+        let session = self.authenticate().await?;
+        let path = format!("/users/{}/chpasswd", user_id);
+        let mut body = json!({ "password": password });
+        if let Some(current) = current_password {
+            body["current_password"] = json!(current);
+        }
+        self.send(
+            self.request(Method::PUT, &session, &path)?.json(&body),
+            &path,
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use nessus_launcher::{Agent, AgentFilter};
+
+fn agent(name: &str, last_connect: Option<u64>) -> Agent {
+    serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "name": name,
+        "last_connect": last_connect,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_agent_filter_last_seen() {
+    let stale = AgentFilter::new().last_seen_before(1_000);
+    assert!(stale.matches(&agent("web-01", Some(999))));
+    assert!(stale.matches(&agent("web-02", None)));
+    assert!(!stale.matches(&agent("web-03", Some(1_000))));
+
+    let recent = AgentFilter::new().last_seen_after(1_000).name("db");
+    assert!(recent.matches(&agent("db-01", Some(1_000))));
+    assert!(!recent.matches(&agent("db-02", None)));
+    assert!(!recent.matches(&agent("web-01", Some(2_000))));
+
+    assert!(AgentFilter::new().to_query().is_empty());
+    assert_eq!(
+        stale.to_query(),
+        vec![
+            ("f".to_string(), "last_connect:lt:1000".to_string()),
+            ("ft".to_string(), "and".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_agent_group_defaults() {
+    let group: nessus_launcher::AgentGroup =
+        serde_json::from_str(r#"{"id": 7, "name": "patch-verify"}"#).unwrap();
+    assert_eq!(group.agents_count, 0);
+    assert_eq!(group.uuid, "");
+}
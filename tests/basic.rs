@@ -1,10 +1,25 @@
 use nessus_launcher::{NessusClient, NessusConfig};
+use std::sync::Mutex;
+
+/// Tests in this file mutate process-wide environment variables.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+fn set_valid_env() {
+    unsafe {
+        std::env::set_var("NESSUS_HOST", "http://example.com");
+        std::env::set_var("NESSUS_USERNAME", "admin");
+        std::env::set_var("NESSUS_PASSWORD", "pass");
+    }
+}
 
 #[test]
 fn test_config_missing_env() {
-    std::env::remove_var("NESSUS_HOST");
-    std::env::remove_var("NESSUS_USERNAME");
-    std::env::remove_var("NESSUS_PASSWORD");
+    let _guard = ENV_LOCK.lock().unwrap();
+    unsafe {
+        std::env::remove_var("NESSUS_HOST");
+        std::env::remove_var("NESSUS_USERNAME");
+        std::env::remove_var("NESSUS_PASSWORD");
+    }
 
     let cfg = NessusConfig::from_env();
     assert!(cfg.is_err());
@@ -12,9 +27,8 @@ fn test_config_missing_env() {
 
 #[test]
 fn test_config_valid() {
-    std::env::set_var("NESSUS_HOST", "https://example.com");
-    std::env::set_var("NESSUS_USERNAME", "admin");
-    std::env::set_var("NESSUS_PASSWORD", "pass");
+    let _guard = ENV_LOCK.lock().unwrap();
+    set_valid_env();
 
     let cfg = NessusConfig::from_env();
     assert!(cfg.is_ok());
@@ -22,12 +36,54 @@ fn test_config_valid() {
 
 #[test]
 fn test_client_constructs() {
-    std::env::set_var("NESSUS_HOST", "https://example.com");
-    std::env::set_var("NESSUS_USERNAME", "admin");
-    std::env::set_var("NESSUS_PASSWORD", "pass");
+    let _guard = ENV_LOCK.lock().unwrap();
+    set_valid_env();
 
     let cfg = NessusConfig::from_env().unwrap();
     let client = NessusClient::new(cfg);
     assert!(client.is_ok());
 }
 
+#[test]
+fn test_client_with_http_client() {
+    let cfg = NessusConfig {
+        host: "https://example.com".into(),
+        username: "admin".into(),
+        password: "pass".into(),
+        x_api_token: None,
+    };
+    let http = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .unwrap();
+
+    let client = NessusClient::with_http_client(http, cfg);
+    assert_eq!(client.launch_options().retries, 5);
+}
+
+#[test]
+fn test_parse_scan_ids() {
+    assert_eq!(NessusConfig::parse_scan_ids("5, 8,11,").unwrap(), vec![5, 8, 11]);
+    assert!(NessusConfig::parse_scan_ids("").unwrap().is_empty());
+
+    let err = NessusConfig::parse_scan_ids("5,eight").unwrap_err();
+    assert!(err.to_string().contains("Invalid scan ID 'eight'"), "{err}");
+}
+
+#[test]
+fn test_default_scan_ids_from_env_rejects_invalid_entries() {
+    let _guard = ENV_LOCK.lock().unwrap();
+    unsafe {
+        std::env::set_var("DEFAULT_SCAN_IDS", "5,x");
+    }
+    let result = NessusConfig::default_scan_ids_from_env();
+    unsafe {
+        std::env::set_var("DEFAULT_SCAN_IDS", "5,8");
+    }
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Configuration error: Invalid scan ID 'x' in DEFAULT_SCAN_IDS"
+    );
+    assert_eq!(NessusConfig::default_scan_ids_from_env().unwrap(), vec![5, 8]);
+}
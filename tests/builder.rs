@@ -0,0 +1,124 @@
+use async_trait::async_trait;
+use nessus_launcher::{
+    Interceptor, LaunchOptions, NessusClient, NessusConfig, NessusError, PollStrategy, Result,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Records every request URL and rejects it before it reaches the network.
+struct Offline {
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Interceptor for Offline {
+    async fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+        self.seen.lock().unwrap().push(request.url().to_string());
+        Err(NessusError::Other("offline".into()))
+    }
+}
+
+fn config(host: &str) -> NessusConfig {
+    NessusConfig {
+        host: host.into(),
+        username: "admin".into(),
+        password: "pass".into(),
+        x_api_token: Some("api-token".into()),
+    }
+}
+
+fn assert_config_error(result: Result<NessusClient>, expected: &str) {
+    match result {
+        Err(NessusError::Config(msg)) => assert!(msg.contains(expected), "{msg}"),
+        Err(e) => panic!("expected a configuration error, got {e}"),
+        Ok(_) => panic!("expected a configuration error, got a client"),
+    }
+}
+
+#[tokio::test]
+async fn test_builder_applies_options() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = NessusClient::builder(config("http://nessus.invalid:8834/"))
+        .with_proxy("http://proxy.invalid:3128")
+        .with_timeout(Duration::from_secs(60))
+        .with_launch_options(LaunchOptions::new().with_alt_targets(["10.0.0.1"]))
+        .with_retries(2)
+        .with_concurrency(4)
+        .with_poll_strategy(PollStrategy::new(Duration::from_secs(1)))
+        .with_session("session-token")
+        .with_interceptor(Offline { seen: seen.clone() })
+        .build()
+        .unwrap();
+
+    assert_eq!(client.launch_options().retries, 2);
+    assert_eq!(client.launch_options().concurrency, 4);
+    assert_eq!(client.launch_options().alt_targets, vec!["10.0.0.1"]);
+    assert_eq!(client.poll_strategy().interval, Duration::from_secs(1));
+
+    client.list_scans().await.unwrap_err();
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec!["http://nessus.invalid:8834/scans".to_string()]
+    );
+}
+
+#[test]
+fn test_builder_rejects_invalid_options() {
+    assert_config_error(
+        NessusClient::builder(config("nessus.example.com")).build(),
+        "Invalid Nessus host",
+    );
+    assert_config_error(
+        NessusClient::builder(config("ftp://nessus.example.com")).build(),
+        "must be an http or https URL",
+    );
+    assert_config_error(
+        NessusClient::builder(config("http://nessus.example.com"))
+            .with_concurrency(0)
+            .build(),
+        "Concurrency",
+    );
+    assert_config_error(
+        NessusClient::builder(config("http://nessus.example.com"))
+            .with_timeout(Duration::ZERO)
+            .build(),
+        "Timeouts",
+    );
+    assert_config_error(
+        NessusClient::builder(config("http://nessus.example.com"))
+            .with_proxy("not a url")
+            .build(),
+        "Invalid proxy URL",
+    );
+    assert_config_error(
+        NessusClient::builder(config("http://nessus.example.com"))
+            .with_http_client(reqwest::Client::new())
+            .with_proxy("http://proxy.invalid:3128")
+            .build(),
+        "custom HTTP client",
+    );
+}
+
+#[test]
+fn test_new_validates_host() {
+    assert!(NessusClient::new(config("http://nessus.example.com")).is_ok());
+    #[cfg(any(feature = "rustls", feature = "native-tls"))]
+    assert!(NessusClient::new(config("https://nessus.example.com")).is_ok());
+    assert_config_error(NessusClient::new(config("")), "Invalid Nessus host");
+}
+
+#[test]
+#[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+fn test_https_host_needs_tls_feature() {
+    assert_config_error(
+        NessusClient::new(config("https://nessus.example.com")),
+        "needs TLS",
+    );
+    assert!(NessusClient::new(config("http://nessus.example.com")).is_ok());
+    assert!(
+        NessusClient::builder(config("https://nessus.example.com"))
+            .with_http_client(reqwest::Client::new())
+            .build()
+            .is_ok()
+    );
+}
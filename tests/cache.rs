@@ -0,0 +1,102 @@
+mod common;
+
+use common::Response;
+use nessus_launcher::{ApiVersion, NessusClient, NessusConfig, ScanSettingsPatch};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const SCANS: &str = r#"{"scans":[{"id":5,"name":"weekly","status":"completed"}],"folders":[{"id":3,"name":"My Scans","type":"main"}]}"#;
+const ETAG: &str = "\"v1\"";
+
+/// The `If-None-Match` header of every request, in order.
+type Conditions = Arc<Mutex<Vec<Option<String>>>>;
+
+/// Serve `GET /scans` with an `ETag`, answering `304 Not Modified` when it
+/// matches, and `200 {}` to anything else.
+async fn mock_server() -> (String, common::Seen, Conditions) {
+    let conditions = Conditions::default();
+    let server_conditions = Arc::clone(&conditions);
+    let (base, seen) = common::mock_server_fn(move |request| {
+        let if_none_match = request.header("if-none-match");
+        server_conditions
+            .lock()
+            .unwrap()
+            .push(if_none_match.map(str::to_string));
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/scans") if if_none_match == Some(ETAG) => Response::json(304, ""),
+            ("GET", "/scans") => Response::json(200, SCANS).with_header("ETag", ETAG),
+            _ => Response::json(200, "{}"),
+        }
+    })
+    .await;
+    (base, seen, conditions)
+}
+
+fn requests(seen: &common::Seen) -> Vec<(String, String)> {
+    seen.lock()
+        .unwrap()
+        .iter()
+        .map(|(method, path, _)| (method.clone(), path.clone()))
+        .collect()
+}
+
+fn client(base: &str, ttl: Duration) -> NessusClient {
+    NessusClient::builder(NessusConfig {
+        host: base.into(),
+        username: String::new(),
+        password: String::new(),
+        x_api_token: Some("api-token".into()),
+    })
+    .with_session("session-token")
+    .with_api_version(ApiVersion::V7)
+    .with_listing_cache(ttl)
+    .build()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_listings_within_ttl_are_served_from_memory() {
+    let (base, seen, _) = mock_server().await;
+    let client = client(&base, Duration::from_secs(60));
+
+    let scans = client.list_scans().await.unwrap();
+    let folders = client.clone().list_folders().await.unwrap();
+    assert_eq!(scans[0].id, 5);
+    assert_eq!(folders[0].name, "My Scans");
+    assert_eq!(seen.lock().unwrap().len(), 1);
+
+    client.clear_listing_cache();
+    client.list_scans().await.unwrap();
+    assert_eq!(seen.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_expired_listings_are_revalidated() {
+    let (base, seen, conditions) = mock_server().await;
+    let client = client(&base, Duration::ZERO);
+
+    let first = client.list_scans().await.unwrap();
+    let second = client.list_scans().await.unwrap();
+    assert_eq!(first, second);
+    let scans = ("GET".to_string(), "/scans".to_string());
+    assert_eq!(requests(&seen), [scans.clone(), scans]);
+    assert_eq!(*conditions.lock().unwrap(), [None, Some(ETAG.to_string())]);
+}
+
+#[tokio::test]
+async fn test_changes_empty_the_cache() {
+    let (base, seen, conditions) = mock_server().await;
+    let client = client(&base, Duration::from_secs(60));
+
+    client.list_scans().await.unwrap();
+    client
+        .update_scan(5, &ScanSettingsPatch::new().with_enabled(false))
+        .await
+        .unwrap();
+    client.list_scans().await.unwrap();
+
+    let seen = requests(&seen);
+    assert_eq!(seen.len(), 3);
+    assert_eq!(seen[2], ("GET".to_string(), "/scans".to_string()));
+    assert_eq!(conditions.lock().unwrap()[2], None);
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use nessus_launcher::{Cassette, NessusClient, NessusConfig, NessusError};
+use std::path::PathBuf;
+
+/// Just enough of Nessus to log in and list scans.
+const ROUTES: &[common::Route] = &[
+    (
+        "GET",
+        "/server/properties",
+        200,
+        r#"{"server_version":"10.6.1"}"#,
+    ),
+    ("POST", "/session", 200, r#"{"token":"session-secret"}"#),
+    (
+        "GET",
+        "/scans",
+        200,
+        r#"{"scans":[{"id":5,"name":"weekly","status":"completed"}]}"#,
+    ),
+    (
+        "GET",
+        "/nessus6.js",
+        200,
+        r#"getApiToken:function(){return"0b9e6a8c-1d2f-4e3a-9b7c-5d4e3f2a1b0c"}"#,
+    ),
+];
+
+fn config(host: String) -> NessusConfig {
+    NessusConfig {
+        host,
+        username: "admin".into(),
+        password: "hunter2".into(),
+        x_api_token: None,
+    }
+}
+
+fn cassette_path() -> PathBuf {
+    std::env::temp_dir().join(format!("nessus-cassette-{}.json", uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn test_recorded_interactions_replay_without_a_server() {
+    let path = cassette_path();
+    let (base, _) = common::mock_server(ROUTES).await;
+
+    let recording = Cassette::record(&path);
+    let client = NessusClient::new(config(base))
+        .unwrap()
+        .with_cassette(recording.clone());
+    let recorded = client.list_scans().await.unwrap();
+    assert_eq!(recording.remaining(), 4);
+
+    let raw = std::fs::read_to_string(&path).unwrap();
+    for secret in [
+        "hunter2",
+        "session-secret",
+        "0b9e6a8c-1d2f-4e3a-9b7c-5d4e3f2a1b0c",
+    ] {
+        assert!(!raw.contains(secret), "{secret} leaked: {raw}");
+    }
+
+    let replay = Cassette::replay(&path).unwrap();
+    let client = NessusClient::new(config("http://nessus.invalid".into()))
+        .unwrap()
+        .with_cassette(replay.clone());
+    assert_eq!(client.list_scans().await.unwrap(), recorded);
+    assert_eq!(replay.remaining(), 0);
+
+    // Every recorded response has been used up.
+    match client.list_scans().await {
+        Err(NessusError::Other(msg)) => assert!(msg.contains("No recorded response"), "{msg}"),
+        other => panic!("expected an exhausted cassette, got {other:?}"),
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_replay_rejects_invalid_cassettes() {
+    let path = cassette_path();
+    std::fs::write(&path, "not json").unwrap();
+    let err = Cassette::replay(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(err, NessusError::Config(_)), "{err}");
+}
@@ -0,0 +1,196 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+use nessus_launcher::{ApiVersion, Finding, NessusClient, NessusConfig, Severity};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A finding of `plugin_id` on `hostname` in scan 5, without enrichment.
+///
+/// Override other fields with struct update syntax, so new `Finding`
+/// fields only need a default here.
+pub fn finding(hostname: &str, plugin_id: u32, severity: Severity) -> Finding {
+    Finding {
+        scan_id: 5,
+        host_id: 1,
+        hostname: hostname.into(),
+        plugin_id,
+        plugin_name: format!("Plugin {plugin_id}"),
+        plugin_family: "Misc.".into(),
+        severity,
+        cves: Vec::new(),
+        epss: None,
+        kev: false,
+        cve_details: Vec::new(),
+        output: None,
+    }
+}
+
+/// A request seen by a [`mock_server`]: method, path with query, and body.
+pub type Seen = Arc<Mutex<Vec<(String, String, String)>>>;
+
+/// A canned response: method, path without query, status, and JSON body.
+pub type Route = (&'static str, &'static str, u16, &'static str);
+
+/// A request received by a [`mock_server_fn`].
+pub struct Request {
+    /// Method, e.g. `GET`.
+    pub method: String,
+    /// Path without query.
+    pub path: String,
+    /// Headers, in the order received.
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// The value of the header `name`, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A response of a [`mock_server_fn`]. A JSON content type is always sent.
+pub struct Response {
+    /// Status code.
+    pub status: u16,
+    /// Headers besides `Content-Type` and `Content-Length`.
+    pub headers: Vec<(&'static str, String)>,
+    /// JSON body.
+    pub body: String,
+}
+
+impl Response {
+    /// A response with `status` and the JSON `body`.
+    pub fn json(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Also send the header `name`.
+    pub fn with_header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+}
+
+/// Serve `routes` over plain HTTP, answering `404 {}` to anything else.
+///
+/// Returns the server's base URL and the requests it has seen.
+pub async fn mock_server(routes: &[Route]) -> (String, Seen) {
+    let routes = routes.to_vec();
+    mock_server_fn(move |request| {
+        routes
+            .iter()
+            .find(|(m, p, _, _)| *m == request.method && *p == request.path)
+            .map_or(Response::json(404, "{}"), |(_, _, status, body)| {
+                Response::json(*status, body)
+            })
+    })
+    .await
+}
+
+/// Serve plain HTTP, answering each request with the response `respond`
+/// returns for it.
+///
+/// Returns the server's base URL and the requests it has seen.
+pub async fn mock_server_fn<F>(respond: F) -> (String, Seen)
+where
+    F: Fn(&Request) -> Response + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let respond = Arc::new(respond);
+    let seen = Seen::default();
+    let server_seen = Arc::clone(&seen);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let respond = Arc::clone(&respond);
+            let seen = Arc::clone(&server_seen);
+            tokio::spawn(async move {
+                let _ = handle(stream, &*respond, &seen).await;
+            });
+        }
+    });
+    (format!("http://{addr}"), seen)
+}
+
+async fn handle<F>(stream: TcpStream, respond: &F, seen: &Seen) -> std::io::Result<()>
+where
+    F: Fn(&Request) -> Response,
+{
+    let mut stream = BufReader::new(stream);
+    loop {
+        let mut line = String::new();
+        if stream.read_line(&mut line).await? == 0 {
+            return Ok(());
+        }
+        let mut parts = line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let target = parts.next().unwrap_or_default().to_string();
+
+        let mut headers = Vec::new();
+        loop {
+            line.clear();
+            stream.read_line(&mut line).await?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                headers.push((name.to_string(), value.trim().to_string()));
+            }
+        }
+        let request = Request {
+            method,
+            path: target.split('?').next().unwrap_or_default().to_string(),
+            headers,
+        };
+        let content_length = request
+            .header("content-length")
+            .and_then(|len| len.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; content_length];
+        stream.read_exact(&mut body).await?;
+
+        let reply = respond(&request);
+        let mut response = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+            reply.status,
+            reply.body.len()
+        );
+        for (name, value) in &reply.headers {
+            response.push_str(&format!("{name}: {value}\r\n"));
+        }
+        response.push_str("\r\n");
+        response.push_str(&reply.body);
+        seen.lock().unwrap().push((
+            request.method,
+            target,
+            String::from_utf8_lossy(&body).into_owned(),
+        ));
+        stream.get_mut().write_all(response.as_bytes()).await?;
+    }
+}
+
+/// A client for the mock server at `base`, with a session already injected
+/// so no login or version scrape is sent.
+pub fn client(base: &str) -> NessusClient {
+    NessusClient::builder(NessusConfig {
+        host: base.into(),
+        username: String::new(),
+        password: String::new(),
+        x_api_token: Some("api-token".into()),
+    })
+    .with_session("session-token")
+    .with_api_version(ApiVersion::V7)
+    .build()
+    .unwrap()
+}
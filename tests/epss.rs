@@ -0,0 +1,119 @@
+#![cfg(feature = "epss")]
+
+mod common;
+
+use common::finding;
+use nessus_launcher::enrich::epss::EpssClient;
+use nessus_launcher::{Finding, Severity};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A response of the FIRST EPSS API, as captured for two of three CVEs.
+const RESPONSE: &str = r#"{
+    "status": "OK",
+    "status-code": 200,
+    "version": "1.0",
+    "access": "public",
+    "total": 2,
+    "offset": 0,
+    "limit": 100,
+    "data": [
+        {"cve": "CVE-2021-44228", "epss": "0.944620000", "percentile": "0.999990000", "date": "2024-05-01"},
+        {"cve": "CVE-2022-27225", "epss": "0.000850000", "percentile": "0.355370000", "date": "2024-05-01"}
+    ]
+}"#;
+
+#[tokio::test]
+async fn test_scores_parse_api_response_and_are_cached() {
+    let (base, seen) = common::mock_server(&[("GET", "/data/v1/epss", 200, RESPONSE)]).await;
+    let mut epss = EpssClient::new()
+        .unwrap()
+        .with_base_url(format!("{base}/data/v1/epss"));
+    let cves = ["CVE-2022-27225", "CVE-2021-44228", "CVE-1999-0001"].map(String::from);
+
+    let scores = epss.scores(&cves).await.unwrap();
+    assert_eq!(scores.len(), 2);
+    let log4shell = &scores["CVE-2021-44228"];
+    assert_eq!(log4shell.epss, 0.94462);
+    assert_eq!(log4shell.percentile, 0.99999);
+    assert_eq!(log4shell.date, "2024-05-01");
+    assert_eq!(scores["CVE-2022-27225"].epss, 0.00085);
+    assert!(!scores.contains_key("CVE-1999-0001"));
+
+    let mut findings = vec![Finding {
+        cves: vec!["CVE-2022-27225".into(), "CVE-2021-44228".into()],
+        ..finding("web01", 155999, Severity::Critical)
+    }];
+    epss.enrich(&mut findings).await.unwrap();
+    assert_eq!(findings[0].epss, Some(0.94462));
+
+    let paths: Vec<_> = seen
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(_, path, _)| path.clone())
+        .collect();
+    assert_eq!(
+        paths,
+        ["/data/v1/epss?cve=CVE-1999-0001%2CCVE-2021-44228%2CCVE-2022-27225"]
+    );
+}
+
+#[tokio::test]
+async fn test_unscored_cves_are_cached() {
+    let (base, seen) = common::mock_server(&[("GET", "/data/v1/epss", 200, RESPONSE)]).await;
+    let path = std::env::temp_dir().join(format!("nessus-epss-miss-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let client = || {
+        EpssClient::new()
+            .unwrap()
+            .with_base_url(format!("{base}/data/v1/epss"))
+            .with_cache_file(&path)
+            .unwrap()
+    };
+    let cves = ["CVE-1999-0001".to_string()];
+
+    let mut epss = client();
+    assert!(epss.scores(&cves).await.unwrap().is_empty());
+    assert!(epss.scores(&cves).await.unwrap().is_empty());
+
+    // The miss is remembered across runs, too.
+    let mut epss = client();
+    assert!(epss.scores(&cves).await.unwrap().is_empty());
+    assert_eq!(seen.lock().unwrap().len(), 1);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_scores_fetched_before_a_failure_are_saved() {
+    let calls = AtomicUsize::new(0);
+    let (base, seen) = common::mock_server_fn(move |_| {
+        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            common::Response::json(200, RESPONSE)
+        } else {
+            common::Response::json(500, "{}")
+        }
+    })
+    .await;
+    let path =
+        std::env::temp_dir().join(format!("nessus-epss-partial-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let client = || {
+        EpssClient::new()
+            .unwrap()
+            .with_base_url(&base)
+            .with_cache_file(&path)
+            .unwrap()
+    };
+    // Two chunks: CVE-2000-0000..0099, then the rest.
+    let mut cves: Vec<String> = (0..100).map(|i| format!("CVE-2000-{i:04}")).collect();
+    cves.extend(["CVE-2021-44228", "CVE-2022-27225"].map(String::from));
+
+    assert!(client().scores(&cves).await.is_err());
+    assert_eq!(seen.lock().unwrap().len(), 2);
+
+    // The first chunk is answered from the saved cache.
+    let mut epss = client();
+    assert!(epss.scores(&cves[..100]).await.unwrap().is_empty());
+    assert_eq!(seen.lock().unwrap().len(), 2);
+    let _ = std::fs::remove_file(&path);
+}
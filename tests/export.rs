@@ -0,0 +1,93 @@
+mod common;
+
+use nessus_launcher::{Finding, Severity};
+
+fn finding() -> Finding {
+    Finding {
+        host_id: 2,
+        plugin_name: "Apache Log4j, \"Log4Shell\"".into(),
+        cves: vec!["CVE-2021-44228".into(), "CVE-2021-45046".into()],
+        epss: Some(0.97),
+        kev: true,
+        ..common::finding("web-01", 156032, Severity::Critical)
+    }
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn test_csv_selected_columns() {
+    use nessus_launcher::export::csv::{CsvColumn, CsvExporter};
+
+    let columns = "hostname, plugin_id,severity,cves,kev"
+        .split(',')
+        .map(|c| c.parse::<CsvColumn>().unwrap())
+        .collect();
+
+    let csv = CsvExporter::new()
+        .with_columns(columns)
+        .to_string(&[finding()])
+        .unwrap();
+
+    assert_eq!(
+        csv,
+        "hostname,plugin_id,severity,cves,kev\n\
+         web-01,156032,critical,CVE-2021-44228;CVE-2021-45046,true\n"
+    );
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn test_csv_quotes_values() {
+    use nessus_launcher::export::csv::{CsvColumn, CsvExporter};
+
+    let csv = CsvExporter::new()
+        .with_columns(vec![CsvColumn::PluginName])
+        .to_string(&[finding()])
+        .unwrap();
+
+    assert_eq!(csv, "plugin_name\n\"Apache Log4j, \"\"Log4Shell\"\"\"\n");
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn test_unknown_column_is_rejected() {
+    use nessus_launcher::export::csv::CsvColumn;
+
+    assert!("risk".parse::<CsvColumn>().is_err());
+}
+
+#[test]
+fn test_jsonl_round_trip() {
+    use nessus_launcher::export::jsonl;
+
+    let mut with_output = finding();
+    with_output.output = Some("Vulnerable JAR: /opt/app/log4j-core-2.14.jar".into());
+    let findings = vec![finding(), with_output];
+
+    let mut buf = Vec::new();
+    jsonl::write(&mut buf, &findings).unwrap();
+
+    let text = String::from_utf8(buf.clone()).unwrap();
+    assert_eq!(text.lines().count(), 2);
+    assert!(text.lines().next().unwrap().starts_with("{\"scan_id\":5,"));
+
+    assert_eq!(jsonl::read(buf.as_slice()).unwrap(), findings);
+}
+
+#[cfg(feature = "gitlab")]
+#[test]
+fn test_gitlab_report_maps_findings_to_vulnerabilities() {
+    use nessus_launcher::export::gitlab::{GitlabExporter, SCHEMA_VERSION};
+
+    let json = GitlabExporter::new().to_string(&[finding()]).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(report["version"], SCHEMA_VERSION);
+    assert_eq!(report["scan"]["type"], "dast");
+
+    let vuln = &report["vulnerabilities"][0];
+    assert_eq!(vuln["severity"], "Critical");
+    assert_eq!(vuln["location"]["hostname"], "web-01");
+    assert_eq!(vuln["identifiers"][0]["type"], "nessus_plugin");
+    assert_eq!(vuln["identifiers"][1]["type"], "cve");
+}
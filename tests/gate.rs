@@ -0,0 +1,54 @@
+mod common;
+
+use nessus_launcher::{BreachReason, Finding, GatePolicy, Severity};
+
+fn finding(severity: Severity, epss: Option<f64>) -> Finding {
+    Finding {
+        plugin_name: "Example".into(),
+        cves: vec!["CVE-2024-0001".into()],
+        epss,
+        ..common::finding("10.0.0.1", 1000, severity)
+    }
+}
+
+#[test]
+fn test_empty_policy_passes() {
+    let report = GatePolicy::new().evaluate(&[finding(Severity::Critical, Some(0.9))]);
+    assert!(report.passed());
+}
+
+#[test]
+fn test_severity_and_epss_breaches() {
+    let policy = GatePolicy::new()
+        .fail_on_severity(Severity::High)
+        .fail_on_epss(0.5);
+
+    let findings = [
+        finding(Severity::Medium, Some(0.1)),
+        finding(Severity::Critical, None),
+        finding(Severity::Low, Some(0.7)),
+    ];
+    let report = policy.evaluate(&findings);
+
+    let reasons: Vec<_> = report.breaches.iter().map(|b| b.reason.clone()).collect();
+    assert_eq!(
+        reasons,
+        vec![
+            BreachReason::Severity(Severity::Critical),
+            BreachReason::Epss(0.7)
+        ]
+    );
+}
+
+#[test]
+fn test_kev_breach() {
+    let mut exploited = finding(Severity::Low, None);
+    exploited.kev = true;
+
+    let report = GatePolicy::new()
+        .fail_on_kev(true)
+        .evaluate(&[finding(Severity::Low, None), exploited]);
+
+    assert_eq!(report.breaches.len(), 1);
+    assert_eq!(report.breaches[0].reason, BreachReason::Kev);
+}
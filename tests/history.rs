@@ -0,0 +1,230 @@
+#![cfg(feature = "history")]
+
+mod common;
+
+use chrono::{Duration, TimeZone, Utc};
+use common::finding;
+use nessus_launcher::history::{HistoryStore, LaunchOutcome, LaunchRecord};
+use nessus_launcher::{LaunchFailure, LaunchReport, Severity};
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("nessus-history-{}-{name}.json", std::process::id()))
+}
+
+#[test]
+fn test_trend_counts_new_fixed_and_mttr() {
+    let path = temp_path("trend");
+    let _ = std::fs::remove_file(&path);
+    let day0 = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+
+    let mut store = HistoryStore::open(&path).unwrap();
+    store.record(
+        5,
+        day0,
+        &[
+            finding("web01", 1, Severity::Critical),
+            finding("web01", 2, Severity::High),
+        ],
+    );
+    store.record(
+        5,
+        day0 + Duration::days(4),
+        &[
+            finding("web01", 2, Severity::High),
+            finding("web02", 3, Severity::Low),
+        ],
+    );
+    store.record(5, day0 + Duration::days(10), &[]);
+    store.record(8, day0, &[finding("db01", 4, Severity::Medium)]);
+    store.save().unwrap();
+
+    let trend = HistoryStore::open(&path).unwrap().trend(5);
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(trend.points.len(), 3);
+    assert_eq!(trend.points[0].counts.critical, 1);
+    assert_eq!((trend.points[1].new, trend.points[1].fixed), (1, 1));
+    assert_eq!((trend.points[2].new, trend.points[2].fixed), (0, 2));
+    // Fixed after 4, 10, and 6 days.
+    assert_eq!(trend.mttr_days, Some(20.0 / 3.0));
+
+    let mut csv = Vec::new();
+    trend.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("recorded_at,critical,high,medium,low,info,total,new,fixed\n"));
+    assert_eq!(csv.lines().count(), 4);
+}
+
+#[test]
+fn test_missing_history_file_is_empty() {
+    let store = HistoryStore::open(temp_path("missing")).unwrap();
+    assert!(store.trend(5).points.is_empty());
+}
+
+#[test]
+fn test_finding_ages_restart_when_a_finding_returns() {
+    let day0 = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    let mut store = HistoryStore::open(temp_path("ages-unsaved")).unwrap();
+    store.record(5, day0, &[finding("web01", 1, Severity::High)]);
+    store.record(5, day0 + Duration::days(5), &[]);
+    store.record(
+        5,
+        day0 + Duration::days(9),
+        &[
+            finding("web01", 1, Severity::High),
+            finding("web01", 2, Severity::Low),
+        ],
+    );
+    store.record(
+        5,
+        day0 + Duration::days(12),
+        &[finding("web01", 2, Severity::Low)],
+    );
+
+    let ages = store.finding_ages(5);
+    assert_eq!(ages.len(), 2);
+    assert_eq!(ages[0].first_seen, day0 + Duration::days(9));
+    assert_eq!(ages[0].last_seen, day0 + Duration::days(9));
+    assert!(!ages[0].open);
+    assert_eq!(ages[1].first_seen, day0 + Duration::days(9));
+    assert!(ages[1].open);
+}
+
+#[test]
+fn test_sla_policy_flags_findings_past_their_window() {
+    use nessus_launcher::BreachReason;
+    use nessus_launcher::history::SlaPolicy;
+
+    let day0 = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    let current = [
+        finding("web01", 1, Severity::Critical),
+        finding("web01", 2, Severity::High),
+        finding("web01", 3, Severity::Critical),
+    ];
+    let mut store = HistoryStore::open(temp_path("sla-unsaved")).unwrap();
+    store.record(5, day0, &current[..2]);
+    store.record(5, day0 + Duration::days(20), &current);
+
+    let now = day0 + Duration::days(20);
+    let policy = SlaPolicy::default().with_window(Severity::High, None);
+
+    let ages = store.finding_ages(5);
+    let overdue = policy.overdue(&ages, now);
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue[0].finding.plugin_id, 1);
+
+    let breaches = policy.evaluate(&store, 5, &current, now);
+    assert_eq!(breaches.len(), 1);
+    assert_eq!(
+        breaches[0].reason,
+        BreachReason::Sla {
+            age_days: 20,
+            window_days: 14
+        }
+    );
+}
+
+#[test]
+fn test_launches_are_recorded_and_filtered() {
+    let path = temp_path("launches");
+    let _ = std::fs::remove_file(&path);
+    let day0 = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    let report = LaunchReport {
+        succeeded: vec![5],
+        failed: vec![LaunchFailure {
+            scan_id: 8,
+            error: "HTTP 403".into(),
+        }],
+        skipped: vec![9],
+    };
+
+    let mut store = HistoryStore::open(&path).unwrap();
+    let week_later = day0 + Duration::days(7);
+    store.record_launches(LaunchRecord::from_report(
+        &report,
+        "launch --scan",
+        Some("alice"),
+        week_later,
+        week_later + Duration::seconds(90),
+    ));
+    store.record_launches(LaunchRecord::from_report(
+        &report,
+        "launch",
+        None,
+        day0,
+        day0 + Duration::seconds(3),
+    ));
+    store.save().unwrap();
+
+    let store = HistoryStore::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let scan5 = store.launches(Some(5), None);
+    assert_eq!(scan5.len(), 2);
+    assert_eq!(scan5[0].launched_at, day0, "oldest first");
+    assert_eq!(scan5[1].user.as_deref(), Some("alice"));
+    assert_eq!(scan5[1].duration(), Duration::seconds(90));
+
+    let recent = store.launches(None, Some(day0 + Duration::days(1)));
+    let outcomes: Vec<_> = recent.iter().map(|l| (l.scan_id, l.outcome)).collect();
+    assert_eq!(
+        outcomes,
+        [
+            (5, LaunchOutcome::Launched),
+            (8, LaunchOutcome::Failed),
+            (9, LaunchOutcome::Skipped)
+        ]
+    );
+    assert_eq!(recent[1].error.as_deref(), Some("HTTP 403"));
+}
+
+#[test]
+fn test_concurrent_updates_keep_every_record() {
+    let dir = std::env::temp_dir().join(format!("nessus-history-{}-updates", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.join("history.json");
+
+    std::thread::scope(|scope| {
+        for scan_id in 0..8 {
+            let path = &path;
+            scope.spawn(move || {
+                HistoryStore::update(path, |store| {
+                    store.record(scan_id, Utc::now(), &[finding("web01", 10, Severity::High)])
+                })
+                .unwrap();
+            });
+        }
+    });
+
+    let store = HistoryStore::open(&path).unwrap();
+    assert!((0..8).all(|scan_id| store.runs(scan_id).len() == 1));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_concurrent_saves_leave_a_complete_file() {
+    let dir = std::env::temp_dir().join(format!("nessus-history-{}-saves", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.join("history.json");
+
+    std::thread::scope(|scope| {
+        for scan_id in 0..8 {
+            let path = &path;
+            scope.spawn(move || {
+                let mut store = HistoryStore::open(path).unwrap();
+                store.record(scan_id, Utc::now(), &[finding("web01", 10, Severity::High)]);
+                store.save().unwrap();
+            });
+        }
+    });
+
+    let store = HistoryStore::open(&path).unwrap();
+    assert!((0..8).any(|scan_id| store.runs(scan_id).len() == 1));
+    let mut files: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(files, ["history.json", "history.json.lock"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use nessus_launcher::{Interceptor, NessusClient, NessusConfig, NessusError, Result};
+use std::sync::{Arc, Mutex};
+
+/// Records every request path and rejects it before it reaches the network.
+struct Offline {
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Interceptor for Offline {
+    async fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+        self.seen.lock().unwrap().push(request.url().path().to_string());
+        Err(NessusError::Other("offline".into()))
+    }
+}
+
+#[tokio::test]
+async fn test_interceptor_sees_and_can_abort_requests() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = NessusClient::with_http_client(
+        reqwest::Client::new(),
+        NessusConfig {
+            host: "https://nessus.invalid".into(),
+            username: "admin".into(),
+            password: "pass".into(),
+            x_api_token: None,
+        },
+    )
+    .with_interceptor(Offline { seen: seen.clone() });
+
+    let err = client.list_scans().await.unwrap_err();
+    assert_eq!(err.to_string(), "Error: offline");
+    assert_eq!(*seen.lock().unwrap(), vec!["/server/properties".to_string()]);
+}
+
+#[tokio::test]
+async fn test_injected_session_skips_scrape_and_login() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = NessusClient::with_http_client(
+        reqwest::Client::new(),
+        NessusConfig {
+            host: "https://nessus.invalid".into(),
+            username: String::new(),
+            password: String::new(),
+            x_api_token: None,
+        },
+    )
+    .with_x_api_token("api-token")
+    .with_session("session-token")
+    .with_interceptor(Offline { seen: seen.clone() });
+
+    client.list_scans().await.unwrap_err();
+    assert_eq!(*seen.lock().unwrap(), vec!["/scans".to_string()]);
+}
+
+/// Records the correlation ID of every request, then rejects it.
+struct RequestIds {
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Interceptor for RequestIds {
+    async fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+        let id = request.headers()[nessus_launcher::REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        self.seen.lock().unwrap().push(id);
+        Err(NessusError::Other("offline".into()))
+    }
+}
+
+#[tokio::test]
+async fn test_requests_carry_unique_correlation_ids() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = NessusClient::with_http_client(
+        reqwest::Client::new(),
+        NessusConfig {
+            host: "https://nessus.invalid".into(),
+            username: "admin".into(),
+            password: "pass".into(),
+            x_api_token: None,
+        },
+    )
+    .with_interceptor(RequestIds { seen: seen.clone() });
+
+    client.list_scans().await.unwrap_err();
+    client.list_scans().await.unwrap_err();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 2);
+    assert_eq!(seen[0].len(), 36);
+    assert_ne!(seen[0], seen[1]);
+}
+
+#[tokio::test]
+async fn test_waiting_for_a_batch_lists_scans_once_per_poll() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = NessusClient::with_http_client(
+        reqwest::Client::new(),
+        NessusConfig {
+            host: "https://nessus.invalid".into(),
+            username: String::new(),
+            password: String::new(),
+            x_api_token: None,
+        },
+    )
+    .with_x_api_token("api-token")
+    .with_session("session-token")
+    .with_interceptor(Offline { seen: seen.clone() });
+
+    let scan_ids: Vec<u32> = (1..=500).collect();
+    let waited = client.wait_for_scans(&scan_ids).await.unwrap();
+    assert_eq!(waited.len(), 500);
+    assert!(waited.values().all(|w| w.is_err()));
+    assert_eq!(*seen.lock().unwrap(), vec!["/scans".to_string()]);
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "kev")]
+
+use nessus_launcher::enrich::kev::KevCatalog;
+
+#[test]
+fn test_catalog_lookup_is_case_insensitive() {
+    let catalog = KevCatalog::from_json(
+        r#"{
+            "title": "CISA Catalog of Known Exploited Vulnerabilities",
+            "vulnerabilities": [
+                {
+                    "cveID": "CVE-2021-44228",
+                    "vendorProject": "Apache",
+                    "product": "Log4j2",
+                    "vulnerabilityName": "Apache Log4j2 Remote Code Execution Vulnerability",
+                    "dateAdded": "2021-12-10",
+                    "dueDate": "2021-12-24",
+                    "knownRansomwareCampaignUse": "Known"
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(catalog.len(), 1);
+    assert!(catalog.contains("cve-2021-44228"));
+    assert_eq!(catalog.get("CVE-2021-44228").unwrap().product, "Log4j2");
+    assert!(!catalog.contains("CVE-2020-0001"));
+}
@@ -0,0 +1,116 @@
+use nessus_launcher::LaunchOptions;
+
+#[test]
+fn test_launch_options_builder() {
+    let defaults = LaunchOptions::new();
+    assert_eq!(defaults.retries, 5);
+    assert!(defaults.alt_targets.is_empty());
+    assert_eq!(defaults.concurrency, 16);
+
+    let options = LaunchOptions::new()
+        .with_retries(0)
+        .with_concurrency(4)
+        .with_alt_targets(["10.0.0.5", "web-01.example.com"]);
+    assert_eq!(options.retries, 0);
+    assert_eq!(options.concurrency, 4);
+    assert_eq!(options.alt_targets, vec!["10.0.0.5", "web-01.example.com"]);
+}
+
+#[test]
+fn test_launch_report_collects_outcomes() {
+    use nessus_launcher::{LaunchFailure, LaunchReport, ScanLaunchResult};
+
+    let outcomes: Vec<ScanLaunchResult> = vec![
+        Ok(5),
+        Err(LaunchFailure {
+            scan_id: 8,
+            error: "boom".into(),
+        }),
+        Ok(11),
+    ];
+    let report: LaunchReport = outcomes.into_iter().collect();
+
+    assert_eq!(report.succeeded, vec![5, 11]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].scan_id, 8);
+    assert_eq!(report.total(), 3);
+}
+
+#[test]
+fn test_failure_policy() {
+    use nessus_launcher::{FailurePolicy, LaunchFailure, LaunchReport};
+
+    let failure = || LaunchFailure {
+        scan_id: 8,
+        error: "boom".into(),
+    };
+    let partial = LaunchReport {
+        succeeded: vec![5],
+        failed: vec![failure()],
+        skipped: vec![],
+    };
+    let total = LaunchReport {
+        succeeded: vec![],
+        failed: vec![failure()],
+        skipped: vec![],
+    };
+    let clean = LaunchReport {
+        succeeded: vec![5],
+        failed: vec![],
+        skipped: vec![],
+    };
+
+    assert!(!FailurePolicy::ReportOnly.is_failure(&total));
+    assert!(FailurePolicy::AnyFailed.is_failure(&partial));
+    assert!(!FailurePolicy::AnyFailed.is_failure(&clean));
+    assert!(!FailurePolicy::AllFailed.is_failure(&partial));
+    assert!(FailurePolicy::AllFailed.is_failure(&total));
+    assert_eq!(LaunchOptions::new().failure_policy, FailurePolicy::ReportOnly);
+}
+
+#[test]
+fn test_skipped_scans_count_towards_total() {
+    use nessus_launcher::LaunchReport;
+
+    let report = LaunchReport {
+        succeeded: vec![5],
+        failed: vec![],
+        skipped: vec![8, 11],
+    };
+
+    assert!(!report.is_success());
+    assert_eq!(report.total(), 3);
+    assert!(!LaunchOptions::new().fail_fast);
+    assert!(LaunchOptions::new().with_fail_fast(true).fail_fast);
+}
+
+#[test]
+fn test_dedupe_scan_ids_keeps_first_occurrence() {
+    use nessus_launcher::dedupe_scan_ids;
+
+    assert_eq!(dedupe_scan_ids(vec![8, 5, 8, 11, 5]), vec![8, 5, 11]);
+    assert_eq!(dedupe_scan_ids(vec![]), Vec::<u32>::new());
+    assert!(!LaunchOptions::new().verify_scan_ids);
+}
+
+#[test]
+fn test_scan_request_overrides_launch_options() {
+    use nessus_launcher::ScanRequest;
+
+    let batch = LaunchOptions::new()
+        .with_retries(5)
+        .with_alt_targets(["10.0.0.0/8"]);
+
+    let plain = ScanRequest::from(5).launch_options(&batch);
+    assert_eq!(plain, batch);
+
+    let request = ScanRequest::new(8)
+        .with_alt_targets(["10.1.0.5"])
+        .with_retries(1);
+    let options = request.launch_options(&batch);
+    assert_eq!(options.retries, 1);
+    assert_eq!(options.alt_targets, vec!["10.1.0.5"]);
+    assert_eq!(options.concurrency, batch.concurrency);
+    assert!(!request.needs_finishing());
+    assert!(request.with_wait(true).needs_finishing());
+}
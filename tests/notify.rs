@@ -0,0 +1,111 @@
+use nessus_launcher::notify::NotifyEvent;
+use nessus_launcher::{LaunchFailure, LaunchReport};
+
+fn partial_failure() -> NotifyEvent {
+    NotifyEvent::LaunchCompleted {
+        report: LaunchReport {
+            succeeded: vec![5],
+            failed: vec![
+                LaunchFailure {
+                    scan_id: 11,
+                    error: "status 500".into(),
+                },
+                LaunchFailure {
+                    scan_id: 8,
+                    error: "status 403".into(),
+                },
+            ],
+            skipped: Vec::new(),
+        },
+    }
+}
+
+#[test]
+fn test_event_serializes_with_tag() {
+    let json = serde_json::to_value(partial_failure()).unwrap();
+
+    assert_eq!(json["event"], "launch_completed");
+    assert_eq!(json["report"]["succeeded"][0], 5);
+}
+
+#[cfg(feature = "pagerduty")]
+#[test]
+fn test_pagerduty_triggers_only_on_failures() {
+    use nessus_launcher::notify::pagerduty::{EVENTS_URL, PagerDutyConfig, PagerDutyNotifier};
+
+    let notifier = PagerDutyNotifier::new(PagerDutyConfig {
+        routing_key: "key".into(),
+        url: EVENTS_URL.into(),
+        source: "nessus.example.com".into(),
+    })
+    .unwrap();
+
+    let event = notifier.event_for(&partial_failure()).unwrap();
+    assert_eq!(event.dedup_key, "nessus-launch-failed-8-11");
+    assert_eq!(event.payload.severity, "error");
+    assert_eq!(event.payload.custom_details["scan 8"], "status 403");
+
+    let success = NotifyEvent::LaunchCompleted {
+        report: LaunchReport {
+            succeeded: vec![5],
+            failed: Vec::new(),
+            skipped: Vec::new(),
+        },
+    };
+    assert!(notifier.event_for(&success).is_none());
+}
+
+#[cfg(feature = "teams")]
+#[test]
+fn test_teams_cards_respect_enabled_events() {
+    use nessus_launcher::ScanStatus;
+    use nessus_launcher::notify::teams::{TeamsConfig, TeamsNotifier};
+
+    let notifier = TeamsNotifier::new(TeamsConfig {
+        webhook_url: "https://example.invalid/webhook".into(),
+        launch_summaries: true,
+        completion_notices: false,
+    })
+    .unwrap();
+
+    let message = notifier.message_for(&partial_failure()).unwrap();
+    let card = &message["attachments"][0]["content"];
+    assert_eq!(card["type"], "AdaptiveCard");
+    assert_eq!(
+        card["body"][0]["text"],
+        "❌ 2 of 3 Nessus scan(s) failed to launch"
+    );
+    assert_eq!(card["body"][1]["facts"][2]["value"], "status 403");
+
+    let finished = NotifyEvent::ScanFinished {
+        scan_id: 5,
+        status: ScanStatus::Completed,
+    };
+    assert!(notifier.message_for(&finished).is_none());
+}
+
+#[cfg(feature = "webhook")]
+#[test]
+fn test_webhook_renders_body_from_event() {
+    use nessus_launcher::notify::webhook::{WebhookConfig, WebhookNotifier};
+    use std::collections::BTreeMap;
+
+    let notifier = WebhookNotifier::new(WebhookConfig {
+        url: "https://example.invalid/hook".into(),
+        method: "post".into(),
+        headers: BTreeMap::new(),
+        body: r#"{"kind": {{ event | json_encode() }}, "failed": {{ report.failed | length }}}"#
+            .into(),
+        events: Some(vec!["launch_completed".into()]),
+    })
+    .unwrap();
+
+    let body = notifier.render(&partial_failure()).unwrap().unwrap();
+    assert_eq!(body, r#"{"kind": "launch_completed", "failed": 2}"#);
+
+    let finished = NotifyEvent::ScanFinished {
+        scan_id: 5,
+        status: nessus_launcher::ScanStatus::Completed,
+    };
+    assert!(notifier.render(&finished).unwrap().is_none());
+}
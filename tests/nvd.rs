@@ -0,0 +1,129 @@
+#![cfg(feature = "nvd")]
+
+mod common;
+
+use common::finding;
+use nessus_launcher::enrich::nvd::NvdClient;
+use nessus_launcher::{Finding, Severity};
+
+/// A response of the NVD CVE API 2.0, as captured for one CVE and trimmed.
+const LOG4SHELL: &str = r#"{
+    "resultsPerPage": 1,
+    "startIndex": 0,
+    "totalResults": 1,
+    "format": "NVD_CVE",
+    "version": "2.0",
+    "timestamp": "2024-05-01T12:00:00.000",
+    "vulnerabilities": [{
+        "cve": {
+            "id": "CVE-2021-44228",
+            "sourceIdentifier": "security@apache.org",
+            "published": "2021-12-10T10:15:09.143",
+            "vulnStatus": "Analyzed",
+            "descriptions": [
+                {"lang": "es", "value": "Apache Log4j2 2.0-beta9 hasta 2.15.0 ..."},
+                {"lang": "en", "value": "Apache Log4j2 2.0-beta9 through 2.15.0 JNDI features do not protect against attacker controlled LDAP endpoints."}
+            ],
+            "metrics": {
+                "cvssMetricV31": [
+                    {
+                        "source": "security@apache.org",
+                        "type": "Secondary",
+                        "cvssData": {"version": "3.1", "vectorString": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H", "baseScore": 9.8, "baseSeverity": "CRITICAL"}
+                    },
+                    {
+                        "source": "nvd@nist.gov",
+                        "type": "Primary",
+                        "cvssData": {"version": "3.1", "vectorString": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H", "baseScore": 10.0, "baseSeverity": "CRITICAL"}
+                    }
+                ],
+                "cvssMetricV2": [
+                    {
+                        "source": "nvd@nist.gov",
+                        "type": "Primary",
+                        "cvssData": {"version": "2.0", "vectorString": "AV:N/AC:M/Au:N/C:C/I:C/A:C", "baseScore": 9.3}
+                    }
+                ]
+            }
+        }
+    }]
+}"#;
+
+/// The NVD's answer for a CVE it does not know.
+const UNKNOWN: &str = r#"{
+    "resultsPerPage": 0,
+    "startIndex": 0,
+    "totalResults": 0,
+    "format": "NVD_CVE",
+    "version": "2.0",
+    "timestamp": "2024-05-01T12:00:00.000",
+    "vulnerabilities": []
+}"#;
+
+fn requests(seen: &common::Seen) -> Vec<String> {
+    seen.lock()
+        .unwrap()
+        .iter()
+        .map(|(_, path, _)| path.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_enrich_parses_api_response() {
+    let (base, seen) = common::mock_server(&[("GET", "/known", 200, LOG4SHELL)]).await;
+    let mut nvd = NvdClient::new()
+        .unwrap()
+        .with_base_url(format!("{base}/known"));
+    let mut findings = vec![Finding {
+        cves: vec!["CVE-2021-44228".into()],
+        ..finding("web01", 155999, Severity::Critical)
+    }];
+
+    nvd.enrich(&mut findings).await.unwrap();
+
+    let [details] = findings[0].cve_details.as_slice() else {
+        panic!("expected one CVE, got {:?}", findings[0].cve_details);
+    };
+    assert_eq!(details.id, "CVE-2021-44228");
+    assert!(
+        details
+            .description
+            .starts_with("Apache Log4j2 2.0-beta9 through")
+    );
+    assert_eq!(
+        details.cvss_vector.as_deref(),
+        Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H")
+    );
+    assert_eq!(details.cvss_score, Some(10.0));
+    assert_eq!(requests(&seen), ["/known?cveId=CVE-2021-44228"]);
+}
+
+#[tokio::test]
+async fn test_unknown_cves_are_cached() {
+    let (base, seen) = common::mock_server(&[("GET", "/unknown", 200, UNKNOWN)]).await;
+    let path = std::env::temp_dir().join(format!("nessus-nvd-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let client = || {
+        NvdClient::new()
+            .unwrap()
+            .with_base_url(format!("{base}/unknown"))
+            .with_cache_file(&path)
+            .unwrap()
+    };
+
+    let mut nvd = client();
+    assert_eq!(nvd.cve("CVE-2099-0001").await.unwrap(), None);
+    assert_eq!(nvd.cve("CVE-2099-0001").await.unwrap(), None);
+    let mut findings = vec![Finding {
+        cves: vec!["CVE-2099-0001".into()],
+        ..finding("web01", 155999, Severity::Critical)
+    }];
+    nvd.enrich(&mut findings).await.unwrap();
+    assert!(findings[0].cve_details.is_empty());
+
+    // The miss is remembered across runs, too.
+    let mut nvd = client();
+    assert_eq!(nvd.cve("CVE-2099-0001").await.unwrap(), None);
+    assert_eq!(requests(&seen), ["/unknown?cveId=CVE-2099-0001"]);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,28 @@
+use nessus_launcher::{AclEntry, AclKind, ScanPermission};
+
+#[test]
+fn test_acl_entries_round_trip() {
+    let acls: Vec<AclEntry> = serde_json::from_str(
+        r#"[
+            {"type": "default", "permissions": 0},
+            {"type": "user", "id": 1, "name": "admin", "permissions": 128, "owner": 1},
+            {"type": "group", "id": 3, "name": "secops", "permissions": 32}
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(acls[0].permission(), Some(ScanPermission::NoAccess));
+    assert_eq!(acls[1].permission(), Some(ScanPermission::Owner));
+    assert_eq!(acls[2].kind, AclKind::Group);
+
+    let entry = AclEntry::group(3, ScanPermission::Control);
+    assert_eq!(entry.id, Some(3));
+    assert_eq!(
+        serde_json::to_value(&entry).unwrap(),
+        serde_json::json!({"type": "group", "id": 3, "permissions": 32})
+    );
+    assert_eq!(
+        serde_json::to_value(AclEntry::default_access(ScanPermission::View)).unwrap(),
+        serde_json::json!({"type": "default", "permissions": 16})
+    );
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use async_trait::async_trait;
+use nessus_launcher::{
+    FinishedScan, Interceptor, LaunchPlan, LaunchReport, NessusClient, NessusConfig, NessusError,
+    PlanReport, PollStrategy, Result, ScanExportFormat, ScanRequest, ScanStatus,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Records every request path and body and rejects it before it reaches
+/// the network.
+struct Offline {
+    seen: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+#[async_trait]
+impl Interceptor for Offline {
+    async fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+        let body = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(|b| String::from_utf8_lossy(b).into_owned())
+            .unwrap_or_default();
+        self.seen
+            .lock()
+            .unwrap()
+            .push((request.url().path().to_string(), body));
+        Err(NessusError::Other("offline".into()))
+    }
+}
+
+#[test]
+fn test_launch_plan_from_json() {
+    let plan: LaunchPlan = serde_json::from_str(
+        r#"{"scans": [
+            {"scan_id": 5, "alt_targets": ["10.0.0.5"], "retries": 1},
+            {"scan_id": 8, "wait": true,
+             "export": {"format": "nessus", "path": "reports/scan-8.nessus"}}
+        ]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        plan.scans,
+        vec![
+            ScanRequest::new(5)
+                .with_alt_targets(["10.0.0.5"])
+                .with_retries(1),
+            ScanRequest::new(8)
+                .with_wait(true)
+                .with_export(ScanExportFormat::Nessus, "reports/scan-8.nessus"),
+        ]
+    );
+
+    let typo = serde_json::from_str::<LaunchPlan>(r#"{"scans": [{"scan_id": 5, "wiat": true}]}"#);
+    assert!(typo.is_err());
+}
+
+#[test]
+fn test_plan_report_success_needs_clean_finish() {
+    let mut report = PlanReport::default();
+    report.launch.succeeded = vec![5];
+    report.finished.push(FinishedScan {
+        scan_id: 5,
+        status: Some(ScanStatus::Completed),
+        export: None,
+        error: None,
+    });
+    assert!(report.is_success());
+
+    report.finished[0].error = Some("export failed".into());
+    assert!(!report.is_success());
+}
+
+#[tokio::test]
+async fn test_launch_requests_applies_per_scan_targets() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = NessusClient::with_http_client(
+        reqwest::Client::new(),
+        NessusConfig {
+            host: "https://nessus.invalid".into(),
+            username: String::new(),
+            password: String::new(),
+            x_api_token: Some("api-token".into()),
+        },
+    )
+    .with_session("session-token")
+    .with_interceptor(Offline { seen: seen.clone() });
+
+    let report = client
+        .launch_requests(vec![
+            ScanRequest::new(5)
+                .with_alt_targets(["10.0.0.5"])
+                .with_retries(0),
+        ])
+        .await
+        .unwrap();
+
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![(
+            "/scans/5/launch".to_string(),
+            r#"{"alt_targets":["10.0.0.5"]}"#.to_string()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn test_finish_requests_exports_scans_whose_batch_mates_fail() {
+    let (base, _) = common::mock_server(&[
+        (
+            "GET",
+            "/scans",
+            200,
+            r#"{"scans": [
+                {"id": 5, "name": "done", "status": "completed"},
+                {"id": 7, "name": "stuck", "status": "running"}
+            ]}"#,
+        ),
+        ("POST", "/scans/5/export", 200, r#"{"file": 11}"#),
+        (
+            "GET",
+            "/scans/5/export/11/status",
+            200,
+            r#"{"status": "ready"}"#,
+        ),
+        (
+            "GET",
+            "/scans/5/export/11/download",
+            200,
+            "<NessusClientData_v2/>",
+        ),
+    ])
+    .await;
+    let client = common::client(&base).with_poll_strategy(
+        PollStrategy::new(Duration::from_millis(10)).with_max_duration(Duration::from_millis(50)),
+    );
+    let path = std::env::temp_dir().join(format!("nessus-plan-{}.nessus", std::process::id()));
+    let requests = [
+        ScanRequest::new(5).with_export(ScanExportFormat::Nessus, &path),
+        ScanRequest::new(6).with_wait(true),
+        ScanRequest::new(7).with_export(ScanExportFormat::Nessus, &path),
+    ];
+    let launched = LaunchReport {
+        succeeded: vec![5, 6, 7],
+        ..LaunchReport::default()
+    };
+
+    let mut finished = client.finish_requests(&requests, &launched).await;
+    finished.sort_by_key(|f| f.scan_id);
+
+    assert_eq!(finished[0].status, Some(ScanStatus::Completed));
+    assert_eq!(finished[0].export.as_deref(), Some(path.as_path()));
+    assert_eq!(finished[0].error, None);
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "<NessusClientData_v2/>"
+    );
+    assert!(
+        finished[1]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("no longer listed")
+    );
+    assert!(finished[2].error.as_ref().unwrap().contains("timed out"));
+    assert_eq!((finished[2].status, &finished[2].export), (None, &None));
+    let _ = std::fs::remove_file(&path);
+}
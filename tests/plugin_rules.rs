@@ -0,0 +1,174 @@
+mod common;
+
+use common::finding;
+use nessus_launcher::{
+    GatePolicy, NewPluginRule, PluginRule, PluginRuleAction, ResultFilter, Severity,
+    apply_plugin_rules,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[test]
+fn test_plugin_rules_deserialize_api_shapes() {
+    let rules: Vec<PluginRule> = serde_json::from_str(
+        r#"[
+            {"id": 1, "plugin_id": "51192", "host": "", "type": "exclude", "date": null, "owner": "admin"},
+            {"id": 2, "plugin_id": 19506, "host": "web01", "type": "recast_low", "date": 1735689600},
+            {"id": 3, "plugin_id": 10180, "host": "*", "type": "recast_critical", "date": ""}
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(rules[0].plugin_id, 51192);
+    assert_eq!(rules[0].host, None);
+    assert_eq!(rules[0].action, PluginRuleAction::Exclude);
+    assert_eq!(rules[0].expires, None);
+    assert_eq!(rules[1].host.as_deref(), Some("web01"));
+    assert_eq!(rules[1].action, PluginRuleAction::Recast(Severity::Low));
+    assert_eq!(rules[1].expires, Some(1735689600));
+    assert_eq!(rules[2].host, None);
+    assert_eq!(rules[2].action.as_type(), "recast_critical");
+
+    assert!(
+        serde_json::from_str::<PluginRule>(r#"{"id": 4, "plugin_id": 1, "type": "hide"}"#).is_err()
+    );
+
+    let expires = UNIX_EPOCH + Duration::from_secs(1735689600);
+    let new = NewPluginRule::new(19506, PluginRuleAction::Recast(Severity::Low))
+        .with_host("web01")
+        .expiring_at(expires);
+    assert_eq!(new.expires, rules[1].expires);
+}
+
+#[test]
+fn test_apply_plugin_rules_excludes_and_recasts() {
+    let rule = |id, plugin_id, host: Option<&str>, action, expires| PluginRule {
+        id,
+        plugin_id,
+        host: host.map(str::to_string),
+        action,
+        expires,
+        owner: None,
+    };
+    let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let rules = [
+        rule(1, 10, None, PluginRuleAction::Recast(Severity::Low), None),
+        rule(2, 10, Some("WEB01"), PluginRuleAction::Exclude, None),
+        rule(
+            3,
+            20,
+            None,
+            PluginRuleAction::Recast(Severity::Critical),
+            None,
+        ),
+        // Expired, so ignored.
+        rule(4, 30, None, PluginRuleAction::Exclude, Some(1_600_000_000)),
+    ];
+    let mut findings = vec![
+        finding("web01", 10, Severity::High),
+        finding("db01", 10, Severity::High),
+        finding("db01", 20, Severity::Medium),
+        finding("db01", 30, Severity::High),
+        finding("db01", 40, Severity::Info),
+    ];
+
+    apply_plugin_rules(&mut findings, &rules, now);
+
+    let remaining: Vec<_> = findings
+        .iter()
+        .map(|f| (f.hostname.as_str(), f.plugin_id, f.severity))
+        .collect();
+    assert_eq!(
+        remaining,
+        [
+            ("db01", 10, Severity::Low),
+            ("db01", 20, Severity::Critical),
+            ("db01", 30, Severity::High),
+            ("db01", 40, Severity::Info),
+        ]
+    );
+    assert!(!rules[0].is_expired(SystemTime::now()));
+    assert!(rules[3].is_expired(now));
+}
+
+#[test]
+fn test_gate_passes_once_recast_is_applied() {
+    let rules = [PluginRule {
+        id: 1,
+        plugin_id: 10,
+        host: None,
+        action: PluginRuleAction::Recast(Severity::Low),
+        expires: None,
+        owner: None,
+    }];
+    let policy = GatePolicy::new().fail_on_severity(Severity::High);
+    let mut findings = vec![finding("web01", 10, Severity::Critical)];
+    assert!(!policy.evaluate(&findings).passed());
+
+    apply_plugin_rules(&mut findings, &rules, SystemTime::now());
+    assert!(policy.evaluate(&findings).passed());
+}
+
+#[tokio::test]
+async fn test_findings_with_plugin_rules_filter_by_recast_severity() {
+    let (base, seen) = common::mock_server(&[
+        (
+            "GET",
+            "/scans/5",
+            200,
+            r#"{"hosts": [{"host_id": 1, "hostname": "web01"}]}"#,
+        ),
+        (
+            "GET",
+            "/scans/5/hosts/1",
+            200,
+            r#"{"vulnerabilities": [
+                {"plugin_id": 10, "plugin_name": "A", "severity": 1},
+                {"plugin_id": 20, "plugin_name": "B", "severity": 4},
+                {"plugin_id": 30, "plugin_name": "C", "severity": 3}
+            ]}"#,
+        ),
+        (
+            "GET",
+            "/plugins/plugin/10",
+            200,
+            r#"{"id": 10, "name": "A"}"#,
+        ),
+        (
+            "GET",
+            "/plugins/plugin/20",
+            200,
+            r#"{"id": 20, "name": "B"}"#,
+        ),
+        (
+            "GET",
+            "/plugins/plugin/30",
+            200,
+            r#"{"id": 30, "name": "C"}"#,
+        ),
+        (
+            "GET",
+            "/plugin-rules",
+            200,
+            r#"{"plugin_rules": [
+                {"id": 1, "plugin_id": 10, "host": "", "type": "recast_critical"},
+                {"id": 2, "plugin_id": 20, "host": "web01", "type": "exclude"}
+            ]}"#,
+        ),
+    ])
+    .await;
+    let client = common::client(&base);
+
+    let findings = client
+        .findings_with_plugin_rules(5, &ResultFilter::new().min_severity(Severity::High))
+        .await
+        .unwrap();
+    let kept: Vec<_> = findings.iter().map(|f| (f.plugin_id, f.severity)).collect();
+    assert_eq!(kept, [(10, Severity::Critical), (30, Severity::High)]);
+    // The low finding recast to critical must not be filtered out by Nessus.
+    assert!(
+        seen.lock()
+            .unwrap()
+            .iter()
+            .all(|(_, path, _)| !path.contains("filter"))
+    );
+}
@@ -0,0 +1,29 @@
+use nessus_launcher::PluginDetails;
+
+#[test]
+fn test_plugin_details_accessors() {
+    let details: PluginDetails = serde_json::from_str(
+        r#"{
+            "id": 12345,
+            "name": "Example Plugin",
+            "family_name": "General",
+            "attributes": [
+                {"attribute_name": "description", "attribute_value": "A bad thing."},
+                {"attribute_name": "solution", "attribute_value": "Patch it."},
+                {"attribute_name": "cve", "attribute_value": "CVE-2024-0001"},
+                {"attribute_name": "cve", "attribute_value": "CVE-2024-0002"},
+                {"attribute_name": "see_also", "attribute_value": "https://a.example\nhttps://b.example"}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(details.description(), Some("A bad thing."));
+    assert_eq!(details.solution(), Some("Patch it."));
+    assert_eq!(details.synopsis(), None);
+    assert_eq!(details.cves(), vec!["CVE-2024-0001", "CVE-2024-0002"]);
+    assert_eq!(
+        details.references(),
+        vec!["https://a.example", "https://b.example"]
+    );
+}
@@ -0,0 +1,56 @@
+use nessus_launcher::PollStrategy;
+use std::time::Duration;
+
+#[test]
+fn test_next_delay_backs_off_to_max_interval() {
+    let strategy = PollStrategy::new(Duration::from_secs(2))
+        .with_backoff(2.0)
+        .with_max_interval(Duration::from_secs(5));
+
+    assert_eq!(strategy.next_delay(Duration::from_secs(2)), Duration::from_secs(4));
+    assert_eq!(strategy.next_delay(Duration::from_secs(4)), Duration::from_secs(5));
+}
+
+#[test]
+fn test_next_delay_survives_huge_backoff() {
+    let max = Duration::from_secs(60);
+    for backoff in [f64::INFINITY, f64::MAX, 1e300, f64::NAN] {
+        let strategy = PollStrategy::new(Duration::from_secs(10))
+            .with_backoff(backoff)
+            .with_max_interval(max);
+        let expected = if backoff.is_nan() { Duration::from_secs(10) } else { max };
+        assert_eq!(strategy.next_delay(Duration::from_secs(10)), expected, "{backoff}");
+    }
+
+    let strategy = PollStrategy {
+        backoff: f64::INFINITY,
+        ..PollStrategy::new(Duration::from_secs(10))
+    };
+    assert_eq!(strategy.next_delay(Duration::MAX), Duration::from_secs(10));
+}
+
+#[tokio::test]
+async fn test_poll_returns_first_ready_value() {
+    let strategy = PollStrategy::new(Duration::from_millis(1));
+    let mut calls = 0;
+
+    let value = strategy
+        .poll(|| {
+            calls += 1;
+            let ready = calls == 3;
+            async move { Ok(ready.then_some(calls)) }
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(value, 3);
+}
+
+#[tokio::test]
+async fn test_poll_times_out() {
+    let strategy = PollStrategy::new(Duration::from_millis(5))
+        .with_max_duration(Duration::from_millis(20));
+
+    let result = strategy.poll(|| async { Ok(None::<()>) }).await;
+    assert!(result.is_err());
+}
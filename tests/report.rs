@@ -0,0 +1,132 @@
+mod common;
+
+use nessus_launcher::report::Report;
+use nessus_launcher::{Finding, Severity};
+
+fn finding(hostname: &str, plugin_id: u32, severity: Severity) -> Finding {
+    // Markup in the name checks that renderers escape it.
+    Finding {
+        plugin_name: format!("Plugin <{plugin_id}>"),
+        plugin_family: "General".into(),
+        ..common::finding(hostname, plugin_id, severity)
+    }
+}
+
+fn sample() -> Vec<Finding> {
+    vec![
+        finding("web-01", 10, Severity::Low),
+        finding("db-01", 20, Severity::Critical),
+        finding("web-01", 30, Severity::High),
+    ]
+}
+
+#[test]
+fn test_report_groups_by_host_most_severe_first() {
+    let report = Report::new("Weekly", sample());
+
+    assert_eq!(report.counts.total(), 3);
+    assert_eq!(report.counts.critical, 1);
+
+    let hosts: Vec<_> = report.hosts.iter().map(|h| h.hostname.as_str()).collect();
+    assert_eq!(hosts, vec!["db-01", "web-01"]);
+
+    let web: Vec<_> = report.hosts[1].findings.iter().map(|f| f.plugin_id).collect();
+    assert_eq!(web, vec![30, 10]);
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn test_html_report_escapes_values() {
+    use nessus_launcher::report::html::HtmlRenderer;
+
+    let page = HtmlRenderer::new()
+        .render(&Report::new("Weekly", sample()))
+        .unwrap();
+
+    assert!(page.contains("<h2>db-01</h2>"));
+    assert!(page.contains("Plugin &lt;20&gt;"));
+    assert!(!page.contains("Plugin <20>"));
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn test_html_report_custom_template() {
+    use nessus_launcher::report::html::HtmlRenderer;
+
+    let page = HtmlRenderer::new()
+        .with_template("{{ title }}: {{ total }}")
+        .render(&Report::new("Weekly", sample()))
+        .unwrap();
+
+    assert_eq!(page, "Weekly: 3");
+}
+
+#[test]
+fn test_findings_diff() {
+    use nessus_launcher::report::FindingsDiff;
+
+    let previous = vec![
+        finding("web-01", 10, Severity::Low),
+        finding("db-01", 40, Severity::Medium),
+    ];
+    let diff = FindingsDiff::between(&previous, &sample());
+
+    let new: Vec<_> = diff.new.iter().map(|f| f.plugin_id).collect();
+    let fixed: Vec<_> = diff.fixed.iter().map(|f| f.plugin_id).collect();
+    assert_eq!(new, vec![20, 30]);
+    assert_eq!(fixed, vec![40]);
+    assert_eq!(diff.unchanged, 1);
+}
+
+#[test]
+fn test_markdown_report_sections() {
+    use nessus_launcher::report::markdown::MarkdownRenderer;
+
+    let md = MarkdownRenderer::new()
+        .with_top(2)
+        .with_previous(vec![finding("web-01", 10, Severity::Low)])
+        .render(&Report::new("Weekly", sample()));
+
+    assert!(md.starts_with("# Weekly\n"));
+    assert!(md.contains("| 1 | 1 | 0 | 1 | 0 | 3 |"));
+    assert!(md.contains("## Top 2 findings"));
+    assert!(md.contains("2 new, 0 fixed, 1 unchanged."));
+    assert!(md.contains("### db-01"));
+}
+
+#[test]
+fn test_github_annotation_escapes_workflow_command_syntax() {
+    use nessus_launcher::report::github::{Annotation, AnnotationLevel};
+
+    let annotation = Annotation::new(
+        AnnotationLevel::Warning,
+        "Scan 5: failed, retried",
+        "100% broken\nsecond line",
+    );
+
+    assert_eq!(
+        annotation.to_string(),
+        "::warning title=Scan 5%3A failed%2C retried::100%25 broken%0Asecond line"
+    );
+}
+
+#[test]
+fn test_github_launch_summary_lists_every_scan() {
+    use nessus_launcher::report::github::launch_summary;
+    use nessus_launcher::{LaunchFailure, LaunchReport};
+
+    let report = LaunchReport {
+        succeeded: vec![5],
+        failed: vec![LaunchFailure {
+            scan_id: 8,
+            error: "status 403".into(),
+        }],
+        skipped: vec![11],
+    };
+    let md = launch_summary(&report);
+
+    assert!(md.contains("| 5 | ✅ launched | |"));
+    assert!(md.contains("| 8 | ❌ failed | status 403 |"));
+    assert!(md.contains("| 11 | ⏭️ skipped | batch stopped early |"));
+    assert!(md.contains("1 of 3 scan(s) launched."));
+}
@@ -0,0 +1,74 @@
+use nessus_launcher::UnexpectedResponse;
+
+#[test]
+fn test_unexpected_response_hints_at_login_page() {
+    let body = r#"<!DOCTYPE html>
+        <html><head><title>Sign in</title></head>
+        <body><form action="/login"><input type="password"></form></body></html>"#;
+    let e = UnexpectedResponse::new("POST /session", "expected value at line 1 column 1", body);
+
+    assert!(
+        e.hint.as_deref().unwrap().contains("HTML login page"),
+        "{e}"
+    );
+    assert!(e.snippet.starts_with("<!DOCTYPE html> <html><head>"), "{e}");
+    assert!(
+        e.to_string()
+            .starts_with("POST /session: expected value at line 1 column 1; response looks like"),
+        "{e}"
+    );
+}
+
+#[test]
+fn test_unexpected_response_hints() {
+    let hint = |body| UnexpectedResponse::new("GET /scans", "missing field `scans`", body).hint;
+
+    assert!(
+        hint("<html><body>502 Bad Gateway</body></html>")
+            .unwrap()
+            .contains("not JSON")
+    );
+    assert_eq!(
+        hint(r#"{"error": "Invalid Credentials"}"#).as_deref(),
+        Some("the server reported: Invalid Credentials")
+    );
+    assert!(
+        hint(r#"{"folders": []}"#)
+            .unwrap()
+            .contains("unexpected shape")
+    );
+    assert!(hint("").unwrap().contains("empty response"));
+    assert_eq!(hint("not json"), None);
+}
+
+#[test]
+fn test_unexpected_response_truncates_snippet() {
+    let body = format!("{{\"data\": \"{}\"}}", "x".repeat(1000));
+    let e = UnexpectedResponse::new("GET /scans", "missing field `scans`", &body);
+
+    assert_eq!(e.snippet.chars().count(), 203);
+    assert!(e.snippet.ends_with("..."));
+    assert!(
+        UnexpectedResponse::new("GET /scans", "eof", "")
+            .to_string()
+            .ends_with("(empty body)")
+    );
+}
+
+#[test]
+fn test_unexpected_response_redacts_snippet() {
+    let snippet =
+        |body| UnexpectedResponse::new("POST /session", "missing field `token`", body).snippet;
+
+    let parsed = snippet(r#"{"session": {"token": "5f1c0d9e"}, "user": "admin"}"#);
+    assert!(parsed.contains(r#""token":"[REDACTED]""#), "{parsed}");
+    assert!(!parsed.contains("5f1c0d9e"), "{parsed}");
+
+    let truncated = snippet(r#"{"user": "admin", "password": "hunter2", "token": "5f1c0d9e"#);
+    assert!(!truncated.contains("hunter2"), "{truncated}");
+    assert!(!truncated.contains("5f1c0d9e"), "{truncated}");
+    assert!(truncated.contains(r#""user": "admin""#), "{truncated}");
+
+    let html = snippet("<p>API key 0c2d7a8e-1111-2222-3333-444455556666</p>");
+    assert_eq!(html, "<p>API key [REDACTED]</p>");
+}
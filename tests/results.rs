@@ -0,0 +1,47 @@
+use nessus_launcher::{ComplianceStatus, ResultFilter, Severity};
+
+#[test]
+fn test_compliance_status_from_severity() {
+    assert_eq!(ComplianceStatus::from_severity(0), ComplianceStatus::Passed);
+    assert_eq!(ComplianceStatus::from_severity(1), ComplianceStatus::Warning);
+    assert_eq!(ComplianceStatus::from_severity(2), ComplianceStatus::Warning);
+    assert_eq!(ComplianceStatus::from_severity(3), ComplianceStatus::Failed);
+    assert_eq!(ComplianceStatus::from_severity(4), ComplianceStatus::Failed);
+}
+
+#[test]
+fn test_severity_parse_and_order() {
+    assert_eq!("High".parse::<Severity>().unwrap(), Severity::High);
+    assert_eq!("4".parse::<Severity>().unwrap(), Severity::Critical);
+    assert!("severe".parse::<Severity>().is_err());
+    assert!(Severity::Critical > Severity::Medium);
+}
+
+#[test]
+fn test_empty_filter_has_no_query() {
+    assert!(ResultFilter::new().is_empty());
+    assert!(ResultFilter::new().to_query().is_empty());
+}
+
+#[test]
+fn test_filter_query_parameters() {
+    let query = ResultFilter::new()
+        .min_severity(Severity::High)
+        .cve("CVE-2021-44228")
+        .to_query();
+
+    let expected: Vec<(String, String)> = [
+        ("filter.search_type", "and"),
+        ("filter.0.filter", "severity"),
+        ("filter.0.quality", "gt"),
+        ("filter.0.value", "2"),
+        ("filter.1.filter", "cve"),
+        ("filter.1.quality", "match"),
+        ("filter.1.value", "CVE-2021-44228"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect();
+
+    assert_eq!(query, expected);
+}
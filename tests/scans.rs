@@ -0,0 +1,33 @@
+use nessus_launcher::{Folder, ScanSettingsPatch};
+
+#[test]
+fn test_scan_settings_patch_serializes_only_set_fields() {
+    assert!(ScanSettingsPatch::new().is_empty());
+
+    let patch = ScanSettingsPatch::new()
+        .with_targets(["10.0.0.0/24", " web-01.example.com "])
+        .with_enabled(false);
+
+    assert!(!patch.is_empty());
+    assert_eq!(
+        serde_json::to_value(&patch).unwrap(),
+        serde_json::json!({
+            "text_targets": "10.0.0.0/24,web-01.example.com",
+            "enabled": false
+        })
+    );
+}
+
+#[test]
+fn test_folder_matches_id_or_name() {
+    let folder = Folder {
+        id: 12,
+        name: "Production".into(),
+        kind: "custom".into(),
+    };
+
+    assert!(folder.matches("12"));
+    assert!(folder.matches("production"));
+    assert!(!folder.matches("1"));
+    assert!(!folder.matches("Prod"));
+}
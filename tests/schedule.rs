@@ -0,0 +1,95 @@
+#![cfg(feature = "schedule")]
+
+use chrono::{DateTime, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::{America, Europe};
+use nessus_launcher::schedule::{GapPolicy, OverlapPolicy, Schedule};
+
+fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+}
+
+fn half_past_two() -> NaiveTime {
+    NaiveTime::from_hms_opt(2, 30, 0).unwrap()
+}
+
+#[test]
+fn test_gap_policy_when_clocks_jump_forward() {
+    // Berlin jumps from 02:00 CET to 03:00 CEST on 2024-03-31.
+    let before = utc(2024, 3, 30, 12, 0);
+    let shift = Schedule::daily(half_past_two(), Europe::Berlin);
+    assert_eq!(shift.next_after(before), Some(utc(2024, 3, 31, 1, 30)));
+
+    let skip = shift.clone().with_gap_policy(GapPolicy::Skip);
+    assert_eq!(skip.next_after(before), Some(utc(2024, 4, 1, 0, 30)));
+}
+
+#[test]
+fn test_overlap_policy_when_clocks_fall_back() {
+    // Berlin falls back from 03:00 CEST to 02:00 CET on 2024-10-27, so
+    // 02:30 is at 00:30 UTC and again at 01:30 UTC.
+    let once = Schedule::daily(half_past_two(), Europe::Berlin);
+    let first = once.next_after(utc(2024, 10, 26, 12, 0));
+    assert_eq!(first, Some(utc(2024, 10, 27, 0, 30)));
+    assert_eq!(
+        once.next_after(first.unwrap()),
+        Some(utc(2024, 10, 28, 1, 30))
+    );
+
+    let twice = once.with_overlap_policy(OverlapPolicy::Twice);
+    assert_eq!(
+        twice.next_after(first.unwrap()),
+        Some(utc(2024, 10, 27, 1, 30))
+    );
+}
+
+#[test]
+fn test_days_are_local_weekdays() {
+    // Fridays at 22:00 in New York are Saturdays in UTC.
+    let schedule = Schedule::daily(
+        NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+        America::New_York,
+    )
+    .with_days(vec![Weekday::Fri]);
+    assert_eq!(
+        schedule.next_after(utc(2024, 3, 2, 1, 0)),
+        Some(utc(2024, 3, 2, 3, 0))
+    );
+    assert_eq!(
+        schedule.next_after(utc(2024, 3, 2, 3, 0)),
+        Some(utc(2024, 3, 9, 3, 0))
+    );
+    // After the clocks change on 2024-03-10, the same local time is an hour
+    // earlier in UTC.
+    assert_eq!(
+        schedule.next_after(utc(2024, 3, 9, 3, 0)),
+        Some(utc(2024, 3, 16, 2, 0))
+    );
+}
+
+#[test]
+fn test_schedule_deserializes_with_defaults() {
+    let schedule: Schedule = serde_json::from_str(
+        r#"{"at": "02:30", "days": ["sat", "Sunday"], "timezone": "Europe/Berlin", "on_gap": "skip"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        schedule,
+        Schedule::daily(half_past_two(), Europe::Berlin)
+            .with_days(vec![Weekday::Sat, Weekday::Sun])
+            .with_gap_policy(GapPolicy::Skip)
+    );
+
+    let schedule: Schedule = serde_json::from_str(r#"{"at": "06:00:30"}"#).unwrap();
+    assert_eq!(schedule.timezone, chrono_tz::UTC);
+    assert_eq!(schedule.on_overlap, OverlapPolicy::Once);
+
+    for invalid in [
+        r#"{"at": "25:00"}"#,
+        r#"{"at": "02:30", "timezone": "Mars/Olympus_Mons"}"#,
+    ] {
+        assert!(
+            serde_json::from_str::<Schedule>(invalid).is_err(),
+            "{invalid}"
+        );
+    }
+}
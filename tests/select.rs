@@ -0,0 +1,82 @@
+mod common;
+
+use nessus_launcher::{NessusError, ScanNamePattern};
+
+const SCANS: &str = r#"{
+    "folders": [
+        {"id": 1, "name": "Trash", "type": "trash"},
+        {"id": 3, "name": "My Scans", "type": "main"}
+    ],
+    "scans": [
+        {"id": 5, "name": "prod-web-weekly", "status": "completed", "folder_id": 3, "enabled": true},
+        {"id": 6, "name": "prod-db-weekly", "status": "completed", "folder_id": 3, "enabled": false},
+        {"id": 7, "name": "prod-old-weekly", "status": "completed", "folder_id": 1, "enabled": true},
+        {"id": 8, "name": "staging-web-weekly", "status": "completed", "folder_id": 3, "enabled": true}
+    ]
+}"#;
+
+#[test]
+fn test_glob_matches_whole_name() {
+    let pattern = ScanNamePattern::glob("prod-*-weekly");
+
+    assert!(pattern.matches("prod-web-weekly"));
+    assert!(pattern.matches("prod--weekly"));
+    assert!(!pattern.matches("prod-web-weekly-old"));
+    assert!(!pattern.matches("staging-web-weekly"));
+    assert_eq!(pattern.to_string(), "prod-*-weekly");
+}
+
+#[test]
+fn test_glob_escapes_regex_syntax() {
+    let pattern = ScanNamePattern::glob("pci (q?) [ext].v2");
+
+    assert!(pattern.matches("pci (q1) [ext].v2"));
+    assert!(!pattern.matches("pci (q1) [ext]xv2"));
+    assert!(!pattern.matches("pci (q12) [ext].v2"));
+}
+
+#[test]
+fn test_regex_matches_anywhere() {
+    let pattern = ScanNamePattern::regex(r"db-\d+").unwrap();
+
+    assert!(pattern.matches("prod-db-01-weekly"));
+    assert!(!pattern.matches("prod-web-weekly"));
+}
+
+#[test]
+fn test_invalid_regex_is_config_error() {
+    let err = ScanNamePattern::regex("prod-(").unwrap_err();
+    assert!(matches!(err, NessusError::Config(_)));
+}
+
+#[tokio::test]
+async fn test_matching_skips_trashed_and_disabled_scans() {
+    let (base, seen) = common::mock_server(&[
+        ("GET", "/scans", 200, SCANS),
+        ("POST", "/scans/5/launch", 200, r#"{"scan_uuid": "a"}"#),
+    ])
+    .await;
+    let client = common::client(&base);
+    let pattern = ScanNamePattern::glob("prod-*");
+
+    let matching: Vec<_> = client
+        .scans_matching(&pattern)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.id)
+        .collect();
+    assert_eq!(matching, vec![5, 6]);
+
+    let report = client.launch_matching(&pattern).await.unwrap();
+    assert_eq!(report.succeeded, vec![5]);
+    assert!(report.is_success());
+    let launched: Vec<_> = seen
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(method, _, _)| method == "POST")
+        .map(|(_, path, _)| path.clone())
+        .collect();
+    assert_eq!(launched, vec!["/scans/5/launch"]);
+}
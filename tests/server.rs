@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use nessus_launcher::{
+    ApiVersion, Interceptor, NessusClient, NessusConfig, NessusError, NessusVersion, Result,
+    ServerProperties,
+};
+use std::sync::{Arc, Mutex};
+
+/// Records every request path and rejects it before it reaches the network.
+struct Offline {
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Interceptor for Offline {
+    async fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+        self.seen
+            .lock()
+            .unwrap()
+            .push(request.url().path().to_string());
+        Err(NessusError::Other("offline".into()))
+    }
+}
+
+fn offline_client(seen: &Arc<Mutex<Vec<String>>>) -> NessusClient {
+    NessusClient::with_http_client(
+        reqwest::Client::new(),
+        NessusConfig {
+            host: "https://nessus.invalid".into(),
+            username: "admin".into(),
+            password: "pass".into(),
+            x_api_token: None,
+        },
+    )
+    .with_interceptor(Offline { seen: seen.clone() })
+}
+
+#[test]
+fn test_parse_nessus_version() {
+    let v = |major, minor, patch| NessusVersion {
+        major,
+        minor,
+        patch,
+    };
+    assert_eq!(NessusVersion::parse("10.6.1"), Some(v(10, 6, 1)));
+    assert_eq!(
+        NessusVersion::parse("8.15.2 (#10) LINUX"),
+        Some(v(8, 15, 2))
+    );
+    assert_eq!(NessusVersion::parse("6.11"), Some(v(6, 11, 0)));
+    assert_eq!(NessusVersion::parse("nessus"), None);
+    assert_eq!(NessusVersion::parse(""), None);
+    assert!(v(10, 0, 0) > v(8, 15, 2));
+    assert_eq!(v(8, 15, 2).to_string(), "8.15.2");
+}
+
+#[test]
+fn test_server_properties_version_falls_back_to_ui_version() {
+    let properties: ServerProperties =
+        serde_json::from_str(r#"{"nessus_ui_version": "10.4.2", "nessus_type": "Nessus Manager"}"#)
+            .unwrap();
+    assert_eq!(properties.version(), NessusVersion::parse("10.4.2"));
+
+    let properties: ServerProperties = serde_json::from_str("{}").unwrap();
+    assert_eq!(properties.version(), None);
+}
+
+#[test]
+fn test_api_version_for_version() {
+    let for_version = |s| ApiVersion::for_version(NessusVersion::parse(s).unwrap());
+
+    assert_eq!(for_version("6.12.1").unwrap(), ApiVersion::V6);
+    assert_eq!(for_version("7.0.0").unwrap(), ApiVersion::V7);
+    assert_eq!(for_version("10.6.1").unwrap(), ApiVersion::V7);
+    assert!(!ApiVersion::V6.requires_x_api_token());
+    assert!(ApiVersion::V7.requires_x_api_token());
+
+    let err = for_version("5.2.7").unwrap_err();
+    assert!(matches!(err, NessusError::UnsupportedVersion(_)), "{err}");
+    assert!(err.to_string().contains("Nessus 5.2.7"), "{err}");
+}
+
+#[tokio::test]
+async fn test_pinned_api_version_routes_authentication() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = offline_client(&seen).with_api_version(ApiVersion::V6);
+    client.list_scans().await.unwrap_err();
+    assert_eq!(*seen.lock().unwrap(), vec!["/session".to_string()]);
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = offline_client(&seen).with_api_version(ApiVersion::V7);
+    client.list_scans().await.unwrap_err();
+    assert_eq!(*seen.lock().unwrap(), vec!["/nessus6.js".to_string()]);
+}
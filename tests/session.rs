@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use nessus_launcher::{
+    Interceptor, KeepAliveOptions, NessusClient, NessusConfig, NessusError, Result,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Records every request path and rejects it before it reaches the network.
+struct Offline {
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Interceptor for Offline {
+    async fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+        self.seen
+            .lock()
+            .unwrap()
+            .push(request.url().path().to_string());
+        Err(NessusError::Other("offline".into()))
+    }
+}
+
+#[tokio::test]
+async fn test_keep_alive_touches_session_until_dropped() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = NessusClient::with_http_client(
+        reqwest::Client::new(),
+        NessusConfig {
+            host: "https://nessus.invalid".into(),
+            username: String::new(),
+            password: String::new(),
+            x_api_token: Some("api-token".into()),
+        },
+    )
+    .with_session("session-token")
+    .with_interceptor(Offline { seen: seen.clone() });
+
+    let keep_alive = client
+        .keep_session_alive(KeepAliveOptions::default().with_interval(Duration::from_millis(5)))
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    drop(keep_alive);
+
+    let touches = seen.lock().unwrap().len();
+    assert!(touches >= 2, "expected repeated touches, saw {touches}");
+    assert!(seen.lock().unwrap().iter().all(|p| p == "/session"));
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert_eq!(seen.lock().unwrap().len(), touches);
+}
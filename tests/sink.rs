@@ -0,0 +1,242 @@
+mod common;
+
+#[cfg(feature = "jira")]
+#[test]
+fn test_jira_dedup_label_is_stable_and_label_safe() {
+    use nessus_launcher::sink::jira::dedup_label;
+    use nessus_launcher::{Finding, Severity};
+
+    let finding = Finding {
+        plugin_name: "Log4Shell".into(),
+        ..common::finding("web 01.example.com", 156032, Severity::Critical)
+    };
+
+    assert_eq!(dedup_label(&finding), "nessus-156032-web_01.example.com");
+}
+
+#[cfg(feature = "jira")]
+#[tokio::test]
+async fn test_jira_searches_with_the_deployment_search_api() {
+    use nessus_launcher::Severity;
+    use nessus_launcher::sink::jira::{JiraClient, JiraConfig, JiraDeployment, JiraSyncOutcome};
+
+    let (base, seen) = common::mock_server(&[
+        (
+            "POST",
+            "/rest/api/3/search/jql",
+            200,
+            r#"{"issues": [{"id": "10001", "key": "SEC-1"}], "isLast": true}"#,
+        ),
+        ("PUT", "/rest/api/2/issue/SEC-1", 204, ""),
+        (
+            "POST",
+            "/rest/api/2/search",
+            200,
+            r#"{"startAt": 0, "total": 0, "issues": []}"#,
+        ),
+        (
+            "POST",
+            "/rest/api/2/issue",
+            201,
+            r#"{"id": "10002", "key": "SEC-2"}"#,
+        ),
+    ])
+    .await;
+    let findings = [common::finding("web01", 156032, Severity::Critical)];
+    let jira = |deployment| {
+        JiraClient::new(JiraConfig {
+            url: base.clone(),
+            project: "SEC".into(),
+            api_token: "jira-token".into(),
+            email: None,
+            issue_type: "Bug".into(),
+            deployment,
+        })
+        .unwrap()
+    };
+
+    let cloud = jira(JiraDeployment::Cloud)
+        .sync_findings(&findings, Severity::High)
+        .await
+        .unwrap();
+    assert_eq!(
+        cloud,
+        JiraSyncOutcome {
+            created: Vec::new(),
+            updated: vec!["SEC-1".into()],
+        }
+    );
+    let server = jira(JiraDeployment::Server)
+        .sync_findings(&findings, Severity::High)
+        .await
+        .unwrap();
+    assert_eq!(server.created, ["SEC-2"]);
+
+    let seen = seen.lock().unwrap();
+    let paths: Vec<_> = seen.iter().map(|(m, p, _)| format!("{m} {p}")).collect();
+    assert_eq!(
+        paths,
+        [
+            "POST /rest/api/3/search/jql",
+            "PUT /rest/api/2/issue/SEC-1",
+            "POST /rest/api/2/search",
+            "POST /rest/api/2/issue",
+        ]
+    );
+    let search: serde_json::Value = serde_json::from_str(&seen[0].2).unwrap();
+    assert_eq!(
+        search["jql"],
+        "project = \"SEC\" AND labels = \"nessus-156032-web01\" ORDER BY created DESC"
+    );
+}
+
+#[cfg(feature = "defectdojo")]
+#[tokio::test]
+async fn test_defectdojo_creates_engagement_and_reimports_into_existing_test() {
+    use nessus_launcher::sink::defectdojo::{DefectDojoClient, DefectDojoConfig, ImportOutcome};
+
+    let (base, seen) = common::mock_server(&[
+        (
+            "GET",
+            "/api/v2/engagements/",
+            200,
+            r#"{"count": 0, "results": []}"#,
+        ),
+        (
+            "POST",
+            "/api/v2/engagements/",
+            201,
+            r#"{"id": 42, "name": "Weekly Nessus"}"#,
+        ),
+        (
+            "GET",
+            "/api/v2/tests/",
+            200,
+            r#"{"count": 1, "results": [{"id": 7}]}"#,
+        ),
+        (
+            "POST",
+            "/api/v2/reimport-scan/",
+            201,
+            r#"{"test": 7, "scan_type": "Tenable Scan"}"#,
+        ),
+    ])
+    .await;
+    let dojo = DefectDojoClient::new(DefectDojoConfig {
+        url: base,
+        api_key: "dojo-key".into(),
+    })
+    .unwrap();
+
+    let engagement_id = dojo.ensure_engagement(1, "Weekly Nessus").await.unwrap();
+    assert_eq!(engagement_id, 42);
+    let outcome = dojo
+        .upload(
+            engagement_id,
+            b"<NessusClientData_v2/>".to_vec(),
+            "scan-5.nessus",
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        outcome,
+        ImportOutcome {
+            engagement_id: 42,
+            test_id: 7,
+            reimported: true,
+        }
+    );
+
+    let seen = seen.lock().unwrap();
+    let paths: Vec<_> = seen.iter().map(|(m, p, _)| format!("{m} {p}")).collect();
+    assert_eq!(
+        paths,
+        [
+            "GET /api/v2/engagements/?product=1&name=Weekly+Nessus",
+            "POST /api/v2/engagements/",
+            "GET /api/v2/tests/?engagement=42&test_type_name=Tenable+Scan",
+            "POST /api/v2/reimport-scan/",
+        ]
+    );
+    let engagement: serde_json::Value = serde_json::from_str(&seen[1].2).unwrap();
+    assert_eq!(engagement["name"], "Weekly Nessus");
+    assert_eq!(engagement["product"], 1);
+    let form = &seen[3].2;
+    for field in [
+        "name=\"scan_type\"",
+        "Tenable Scan",
+        "name=\"test\"",
+        "<NessusClientData_v2/>",
+    ] {
+        assert!(form.contains(field), "{field} missing from {form}");
+    }
+    assert!(!form.contains("name=\"engagement\""), "{form}");
+}
+
+#[cfg(feature = "defectdojo")]
+#[tokio::test]
+async fn test_defectdojo_imports_into_existing_engagement_without_test() {
+    use nessus_launcher::sink::defectdojo::{DefectDojoClient, DefectDojoConfig};
+
+    let (base, seen) = common::mock_server(&[
+        (
+            "GET",
+            "/api/v2/engagements/",
+            200,
+            r#"{"count": 1, "results": [{"id": 9}]}"#,
+        ),
+        (
+            "GET",
+            "/api/v2/tests/",
+            200,
+            r#"{"count": 0, "results": []}"#,
+        ),
+        ("POST", "/api/v2/import-scan/", 201, r#"{"test": 11}"#),
+    ])
+    .await;
+    let dojo = DefectDojoClient::new(DefectDojoConfig {
+        url: base,
+        api_key: "dojo-key".into(),
+    })
+    .unwrap();
+
+    let engagement_id = dojo.ensure_engagement(1, "Weekly Nessus").await.unwrap();
+    let outcome = dojo
+        .upload(
+            engagement_id,
+            b"<NessusClientData_v2/>".to_vec(),
+            "scan-5.nessus",
+        )
+        .await
+        .unwrap();
+    assert_eq!((outcome.engagement_id, outcome.test_id), (9, 11));
+    assert!(!outcome.reimported);
+
+    let seen = seen.lock().unwrap();
+    assert!(
+        seen.iter()
+            .all(|(m, p, _)| m == "GET" || p == "/api/v2/import-scan/")
+    );
+    assert!(
+        seen[2].2.contains("name=\"engagement\"\r\n\r\n9\r\n"),
+        "{}",
+        seen[2].2
+    );
+}
+
+#[cfg(feature = "servicenow")]
+#[test]
+fn test_servicenow_render_template_substitutes_known_placeholders() {
+    use nessus_launcher::sink::servicenow::render_template;
+    use std::collections::HashMap;
+
+    let values = HashMap::from([
+        ("plugin_name", "Log4Shell".to_string()),
+        ("hostname", "web01".to_string()),
+    ]);
+
+    assert_eq!(
+        render_template("Nessus: {plugin_name} on {hostname} ({unknown})", &values),
+        "Nessus: Log4Shell on web01 ({unknown})"
+    );
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use nessus_launcher::{NessusError, Tag};
+
+#[test]
+fn test_tag_matches_uuid_pair_and_value() {
+    let tag: Tag = serde_json::from_str(
+        r#"{
+            "uuid": "0c2d7a8e-1111-2222-3333-444455556666",
+            "category_name": "Environment",
+            "value": "Production"
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(tag.description, None);
+    assert!(tag.matches("0c2d7a8e-1111-2222-3333-444455556666"));
+    assert!(tag.matches("Environment:Production"));
+    assert!(tag.matches("environment: production"));
+    assert!(tag.matches("production"));
+    assert!(!tag.matches("Owner:Production"));
+    assert!(!tag.matches("Staging"));
+}
+
+#[tokio::test]
+async fn test_scans_with_tag_skips_trashed_and_forbidden_scans() {
+    let (base, seen) = common::mock_server(&[
+        (
+            "GET",
+            "/tags/values",
+            200,
+            r#"{"values": [{"uuid": "0c2d7a8e-1111-2222-3333-444455556666", "category_name": "Environment", "value": "Production"}]}"#,
+        ),
+        (
+            "GET",
+            "/scans",
+            200,
+            r#"{
+                "folders": [{"id": 1, "name": "Trash", "type": "trash"}, {"id": 3, "name": "My Scans", "type": "main"}],
+                "scans": [
+                    {"id": 5, "name": "prod", "status": "completed", "folder_id": 3},
+                    {"id": 6, "name": "forbidden", "status": "completed", "folder_id": 3},
+                    {"id": 7, "name": "trashed", "status": "completed", "folder_id": 1},
+                    {"id": 8, "name": "staging", "status": "completed", "folder_id": 3}
+                ]
+            }"#,
+        ),
+        (
+            "GET",
+            "/scans/5",
+            200,
+            r#"{"info": {"tag_targets": ["0c2d7a8e-1111-2222-3333-444455556666"]}}"#,
+        ),
+        ("GET", "/scans/6", 403, r#"{"error": "forbidden"}"#),
+        ("GET", "/scans/8", 200, r#"{"info": {"tag_targets": null}}"#),
+    ])
+    .await;
+    let client = common::client(&base);
+
+    let scans = client.scans_with_tag("Production").await.unwrap();
+    assert_eq!(scans.iter().map(|s| s.id).collect::<Vec<_>>(), vec![5]);
+
+    let mut fetched: Vec<_> = seen
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(_, path, _)| path.clone())
+        .filter(|path| path.starts_with("/scans/"))
+        .collect();
+    fetched.sort();
+    assert_eq!(fetched, ["/scans/5", "/scans/6", "/scans/8"]);
+
+    let err = client.scans_with_tag("Staging").await.unwrap_err();
+    assert!(matches!(err, NessusError::Config(_)), "{err}");
+}
@@ -0,0 +1,22 @@
+use nessus_launcher::extract_x_api_token;
+
+const TOKEN: &str = "7A8D2B0C-1E3F-4A5B-9C6D-0123456789AB";
+
+#[test]
+fn test_extract_x_api_token_known_shapes() {
+    let shapes = [
+        format!(r#"}},{{key:"getApiToken",value:function(){{return"{TOKEN}"}}}},{{key:"x""#),
+        format!(r#"a.getApiToken=function() {{ return '{TOKEN}'; }};"#),
+        format!(r#"getApiToken:function(){{return"{TOKEN}"}},"#),
+        format!(r#"getApiToken",get:function(){{var t="{TOKEN}";return t}}"#),
+    ];
+
+    for js in &shapes {
+        assert_eq!(extract_x_api_token(js).as_deref(), Some(TOKEN), "{js}");
+    }
+}
+
+#[test]
+fn test_extract_x_api_token_missing() {
+    assert_eq!(extract_x_api_token("function(){return\"nope\"}"), None);
+}
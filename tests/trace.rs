@@ -0,0 +1,60 @@
+mod common;
+
+use nessus_launcher::{ApiVersion, NessusClient, NessusConfig};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+const BODY: &str =
+    r#"{"token":"session-secret","scans":[{"id":5,"name":"weekly","status":"completed"}]}"#;
+
+/// Collects everything logged.
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_http_trace_redacts_secrets() {
+    let (base, _) = common::mock_server_fn(|_| common::Response::json(200, BODY)).await;
+    let capture = Capture::default();
+    let writer = capture.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let client = NessusClient::builder(NessusConfig {
+        host: base,
+        username: "admin".into(),
+        password: "hunter2".into(),
+        x_api_token: Some("x-api-secret".into()),
+    })
+    .with_api_version(ApiVersion::V7)
+    .with_http_trace(true)
+    .build()
+    .unwrap();
+
+    // The traced response is still decoded as usual.
+    let scans = client.list_scans().await.unwrap();
+    assert_eq!(scans[0].id, 5);
+
+    let log = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+    assert!(log.contains("--> POST"), "{log}");
+    assert!(log.contains("/scans"), "{log}");
+    assert!(log.contains(r#""name":"weekly""#), "{log}");
+    assert!(log.contains("[REDACTED]"), "{log}");
+    for secret in ["hunter2", "session-secret", "x-api-secret"] {
+        assert!(!log.contains(secret), "{secret} leaked: {log}");
+    }
+}
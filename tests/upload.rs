@@ -0,0 +1,47 @@
+#![cfg(any(feature = "s3", feature = "azure", feature = "gcs"))]
+
+use chrono::{TimeZone, Utc};
+use nessus_launcher::upload::{self, ObjectNameTemplate, UploadTarget};
+
+#[test]
+fn test_upload_target_parses_bucket_and_prefix() {
+    let target = UploadTarget::parse("s3://reports/nessus/weekly/").unwrap();
+
+    assert_eq!(target.scheme, "s3");
+    assert_eq!(target.bucket, "reports");
+    assert_eq!(target.prefix, "nessus/weekly");
+    assert_eq!(target.key("a.html"), "nessus/weekly/a.html");
+
+    let bare = UploadTarget::parse("s3://reports").unwrap();
+    assert_eq!(bare.key("a.html"), "a.html");
+
+    assert!(UploadTarget::parse("reports/nessus").is_err());
+}
+
+#[test]
+fn test_object_name_template_placeholders() {
+    let time = Utc.with_ymd_and_hms(2024, 3, 9, 14, 5, 0).unwrap();
+    let template = ObjectNameTemplate::new("{date}/scan-{scan_id}-{timestamp}.{ext}");
+
+    assert_eq!(
+        template.render(5, "html", time),
+        "2024-03-09/scan-5-20240309T140500Z.html"
+    );
+}
+
+#[test]
+fn test_open_rejects_unknown_scheme() {
+    assert!(upload::open("ftp://reports/nessus/").is_err());
+}
+
+#[cfg(feature = "gcs")]
+#[test]
+fn test_open_selects_backend_by_scheme() {
+    let store = upload::open("gs://reports/nessus").unwrap();
+
+    assert_eq!(store.target().bucket, "reports");
+    assert_eq!(
+        store.target().url("nessus/a.html"),
+        "gs://reports/nessus/a.html"
+    );
+}
@@ -0,0 +1,29 @@
+use nessus_launcher::{User, UserRole};
+
+#[test]
+fn test_user_role_from_permissions() {
+    let user: User = serde_json::from_str(
+        r#"{
+            "id": 4,
+            "username": "ci-bot",
+            "permissions": 32,
+            "type": "local",
+            "lastlogin": 1700000000
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(user.role(), Some(UserRole::Standard));
+    assert_eq!(user.kind, "local");
+    assert_eq!(user.email, None);
+
+    for role in [
+        UserRole::Basic,
+        UserRole::Standard,
+        UserRole::Administrator,
+        UserRole::SystemAdministrator,
+    ] {
+        assert_eq!(UserRole::from_permissions(role.permissions()), Some(role));
+    }
+    assert_eq!(UserRole::from_permissions(0), None);
+}